@@ -0,0 +1,229 @@
+// This file is part of EVM Account Mapping Pallet.
+
+// Copyright (C) HashForest Technology Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Craft, sign and submit `meta_call` extrinsics of the EVM Account Mapping pallet.
+//!
+//! The EIP-712 domain is read from the pallet constants and the nonce from its storage, so the
+//! runtime needs no extra runtime API.
+
+use anyhow::{anyhow, Context, Result};
+use clap::Parser;
+use codec::{Decode, Encode};
+use pallet_evm_account_mapping::{
+	eip712::EIP712Domain, parachain_eip712_salt, test_utils, EIP712Signature, MetaSignature, Nonce,
+	PayloadVersion, SubstrateAddressConverter,
+};
+use sp_core::{
+	crypto::AccountId32,
+	ecdsa,
+	hashing::{blake2_128, twox_128},
+	Pair,
+};
+use subxt::{ext::scale_value, tx::SubmittableExtrinsic, Metadata, OnlineClient, PolkadotConfig};
+
+const PALLET: &str = "EvmAccountMapping";
+
+/// The version byte of an unsigned extrinsic.
+const UNSIGNED_EXTRINSIC_VERSION: u8 = 4;
+
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Cli {
+	/// The WebSocket endpoint of the node.
+	#[arg(long, default_value = "ws://127.0.0.1:9944")]
+	url: String,
+
+	/// The hex encoded secp256k1 private key of the EVM account.
+	#[arg(long, env = "EVM_PRIVATE_KEY", hide_env_values = true)]
+	private_key: String,
+
+	/// The hex encoded SCALE encoded call to dispatch.
+	#[arg(long, required_unless_present = "call", conflicts_with = "call")]
+	call_data: Option<String>,
+
+	/// The call to dispatch as `Pallet.call`, e.g. `System.remark_with_event`.
+	#[arg(long)]
+	call: Option<String>,
+
+	/// An argument of `--call` in the scale-value syntax, e.g. `(72, 105)` for the bytes
+	/// `Hi`, repeat for every argument.
+	#[arg(long = "arg", requires = "call")]
+	args: Vec<String>,
+
+	/// The tip for the block author, the payload is signed as version 1 if given.
+	#[arg(long)]
+	tip: Option<u128>,
+
+	/// The nonce of the meta-transaction, fetched from the chain if not given.
+	#[arg(long)]
+	nonce: Option<Nonce>,
+
+	/// Print the signed extrinsic instead of submitting it.
+	#[arg(long)]
+	dry_run: bool,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+	let cli = Cli::parse();
+
+	let keypair = ecdsa::Pair::from_seed_slice(&decode_hex(&cli.private_key)?)
+		.map_err(|_| anyhow!("The private key must be 32 bytes"))?;
+	let api = OnlineClient::<PolkadotConfig>::from_url(&cli.url)
+		.await
+		.with_context(|| format!("Failed to connect to {}", cli.url))?;
+	let metadata = api.metadata();
+
+	let call_data = match (&cli.call_data, &cli.call) {
+		(Some(call_data), _) => decode_hex(call_data)?,
+		(None, Some(call)) => encode_call(&api, call, &cli.args)?,
+		(None, None) => unreachable!("clap requires either of them"),
+	};
+
+	let who = test_utils::mapped_account::<SubstrateAddressConverter>(&keypair)
+		.ok_or_else(|| anyhow!("The public key can't be converted to an account"))?;
+	let ss58_who = test_utils::ss58_address(&who, constant(&metadata, "System", "SS58Prefix")?);
+	let nonce = match cli.nonce {
+		Some(nonce) => nonce,
+		None => account_nonce(&api, &who).await?,
+	};
+	let version = if cli.tip.is_some() { PayloadVersion::V1 } else { PayloadVersion::V0 };
+	let message_hash = test_utils::meta_call_message_hash(
+		version,
+		&ss58_who,
+		&call_data,
+		nonce,
+		cli.tip.unwrap_or_default(),
+		&eip712_domain(&metadata)?,
+	);
+	let signature = test_utils::sign_message_hash(&keypair, &message_hash);
+	println!("who: {ss58_who}");
+	println!("nonce: {nonce}");
+	println!("signature: 0x{}", hex::encode(signature));
+
+	let extrinsic =
+		meta_call_extrinsic(&metadata, &who, &call_data, nonce, &signature, cli.tip, version)?;
+	if cli.dry_run {
+		println!("extrinsic: 0x{}", hex::encode(extrinsic));
+		return Ok(())
+	}
+
+	let events = SubmittableExtrinsic::from_bytes(api.clone(), extrinsic)
+		.submit_and_watch()
+		.await
+		.context("Failed to submit the extrinsic")?
+		.wait_for_finalized_success()
+		.await?;
+	println!("extrinsic hash: {:?}", events.extrinsic_hash());
+	println!("finalized in block: {:?}", events.block_hash());
+
+	Ok(())
+}
+
+/// Encode `call` with the scale-value arguments `args` against the metadata of the chain.
+fn encode_call(api: &OnlineClient<PolkadotConfig>, call: &str, args: &[String]) -> Result<Vec<u8>> {
+	let (pallet, call) = call
+		.split_once('.')
+		.ok_or_else(|| anyhow!("Expected `Pallet.call`, got `{call}`"))?;
+	let args = args
+		.iter()
+		.map(|arg| {
+			let (value, rest) = scale_value::stringify::from_str(arg);
+			let value = value.map_err(|e| anyhow!("Invalid argument `{arg}`: {e}"))?;
+			if !rest.trim().is_empty() {
+				return Err(anyhow!("Unexpected `{rest}` after the argument `{arg}`"))
+			}
+			Ok(value)
+		})
+		.collect::<Result<Vec<_>>>()?;
+
+	api.tx()
+		.call_data(&subxt::dynamic::tx(pallet, call, args))
+		.with_context(|| format!("Failed to encode {pallet}.{call}"))
+}
+
+/// The EIP-712 domain of the pallet, i.e. `Pallet::eip712_domain` of the runtime.
+fn eip712_domain(metadata: &Metadata) -> Result<EIP712Domain> {
+	let salt = constant(metadata, PALLET, "EIP712Salt")?;
+	// The runtimes before the parachain id was added to the domain don't have the constant
+	let para_id: Option<u32> = constant(metadata, PALLET, "EIP712ParaId").unwrap_or_default();
+	Ok(EIP712Domain {
+		name: constant(metadata, PALLET, "EIP712Name")?,
+		version: constant(metadata, PALLET, "EIP712Version")?,
+		chain_id: constant(metadata, PALLET, "EIP712ChainID")?,
+		verifying_contract: constant(metadata, PALLET, "EIP712VerifyingContractAddress")?,
+		salt: match para_id {
+			Some(para_id) => Some(parachain_eip712_salt(para_id, salt)),
+			None => salt,
+		},
+	})
+}
+
+fn constant<T: Decode>(metadata: &Metadata, pallet: &str, name: &str) -> Result<T> {
+	let value = metadata
+		.pallet_by_name(pallet)
+		.and_then(|pallet| pallet.constant_by_name(name).map(|constant| constant.value().to_vec()))
+		.ok_or_else(|| anyhow!("The runtime has no constant {pallet}.{name}"))?;
+	T::decode(&mut &value[..]).with_context(|| format!("Failed to decode {pallet}.{name}"))
+}
+
+/// The next nonce of `who`, read from `AccountNonce`.
+async fn account_nonce(api: &OnlineClient<PolkadotConfig>, who: &AccountId32) -> Result<Nonce> {
+	let who: &[u8] = who.as_ref();
+	let key = [&twox_128(PALLET.as_bytes())[..], &twox_128(b"AccountNonce"), &blake2_128(who), who]
+		.concat();
+	let nonce = api.storage().at_latest().await?.fetch_raw(key).await?;
+
+	Ok(nonce
+		.map(|nonce| Nonce::decode(&mut &nonce[..]))
+		.transpose()?
+		.unwrap_or_default())
+}
+
+/// The SCALE encoded unsigned `meta_call` extrinsic.
+fn meta_call_extrinsic(
+	metadata: &Metadata,
+	who: &AccountId32,
+	call_data: &[u8],
+	nonce: Nonce,
+	signature: &EIP712Signature,
+	tip: Option<u128>,
+	version: PayloadVersion,
+) -> Result<Vec<u8>> {
+	let pallet = metadata
+		.pallet_by_name(PALLET)
+		.ok_or_else(|| anyhow!("The runtime has no pallet {PALLET}"))?;
+	let call = pallet
+		.call_variant_by_name("meta_call")
+		.ok_or_else(|| anyhow!("The runtime has no call {PALLET}.meta_call"))?;
+
+	let mut extrinsic = vec![UNSIGNED_EXTRINSIC_VERSION, pallet.index(), call.index];
+	who.encode_to(&mut extrinsic);
+	// The inner call is a `Box<RuntimeCall>`, its encoding is the call data itself
+	extrinsic.extend_from_slice(call_data);
+	nonce.encode_to(&mut extrinsic);
+	MetaSignature::from(*signature).encode_to(&mut extrinsic);
+	tip.encode_to(&mut extrinsic);
+	version.encode_to(&mut extrinsic);
+
+	// Extrinsics are length prefixed
+	Ok(extrinsic.encode())
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>> {
+	hex::decode(s.trim_start_matches("0x")).with_context(|| format!("Invalid hex `{s}`"))
+}
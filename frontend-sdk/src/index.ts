@@ -1,12 +1,12 @@
 import type { ApiPromise, SubmittableResult } from '@polkadot/api'
 import type { ApiTypes, Signer as InjectedSigner } from '@polkadot/api/types'
 import type { AddressOrPair, SubmittableExtrinsic } from '@polkadot/api-base/types/submittable'
-import type { U256, U64 } from '@polkadot/types-codec'
+import type { Option, U256, U32, U64, U8aFixed } from '@polkadot/types-codec'
 import { hexToString, hexToU8a, u8aToHex } from '@polkadot/util'
 import { blake2AsU8a, encodeAddress, secp256k1Compress } from '@polkadot/util-crypto'
 
 import type { Account, Address, Hex, TestClient, WalletClient } from 'viem'
-import { hashMessage, recoverPublicKey } from 'viem'
+import { encodeAbiParameters, hashMessage, keccak256, recoverPublicKey } from 'viem'
 import type { signTypedData } from 'viem/wallet'
 import { signMessage } from 'viem/wallet'
 
@@ -38,6 +38,7 @@ export interface Eip712Domain {
   version: string
   chainId: number
   verifyingContract: Address
+  salt?: Hex
 }
 
 export function createEip712Domain(api: ApiPromise): Eip712Domain {
@@ -46,11 +47,24 @@ export function createEip712Domain(api: ApiPromise): Eip712Domain {
     const version = hexToString(api.consts.evmAccountMapping.eip712Version.toString())
     const chainId = (api.consts.evmAccountMapping.eip712ChainID as U256).toNumber()
     const verifyingContract = api.consts.evmAccountMapping.eip712VerifyingContractAddress.toString() as Address
+    const configuredSalt = api.consts.evmAccountMapping.eip712Salt as Option<U8aFixed>
+    const paraId = api.consts.evmAccountMapping.eip712ParaId as Option<U32> | undefined
+    let salt = configuredSalt.isSome ? configuredSalt.unwrap().toHex() : undefined
+    if (paraId?.isSome) {
+      // keccak256(abi.encode(uint32 paraId, bytes32 salt)), see `parachain_eip712_salt` of the pallet
+      salt = keccak256(
+        encodeAbiParameters(
+          [{ type: 'uint32' }, { type: 'bytes32' }],
+          [paraId.unwrap().toNumber(), salt ?? `0x${'00'.repeat(32)}`]
+        )
+      )
+    }
     return {
       name,
       version,
       chainId,
       verifyingContract,
+      ...(salt ? { salt } : {}),
     }
   } catch (_err) {
     throw new Error(
@@ -109,6 +123,7 @@ export function createEip712StructedDataSubstrateCall(
           name: 'verifyingContract',
           type: 'address',
         },
+        ...(domain.salt ? [{ name: 'salt', type: 'bytes32' }] : []),
       ],
       SubstrateCall: [
         { name: 'who', type: 'string' },
@@ -231,8 +246,9 @@ export async function signAndSendEvm<TSubmittableResult extends SubmittableResul
         account.address,
         substrateCall.callData,
         substrateCall.nonce,
-        signature,
-        null
+        { Eip712Secp256k1: { Standard: signature } },
+        null,
+        'V0'
       )
       return _extrinsic.send((result) => callback(resolve, reject, result))
     } catch (error) {
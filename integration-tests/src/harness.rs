@@ -0,0 +1,209 @@
+// This file is part of EVM Account Mapping Pallet.
+
+// Copyright (C) HashForest Technology Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A minimal chain: a transaction pool validating the transactions like a node does and a block
+//! builder applying them through `frame_executive`.
+
+use crate::runtime::*;
+use codec::Encode;
+use sp_core::{sr25519, Pair};
+use sp_runtime::{
+	generic::{Era, SignedPayload},
+	traits::Header as _,
+	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
+	ApplyExtrinsicResult, BuildStorage,
+};
+
+/// Build the genesis state of the tests.
+#[derive(Default)]
+pub struct ExtBuilder {
+	balances: Vec<(AccountId, Balance)>,
+	assets: Vec<(AssetId, AccountId, Balance)>,
+}
+
+impl ExtBuilder {
+	/// Endow `who` with `amount` of the native token.
+	pub fn balance(mut self, who: AccountId, amount: Balance) -> Self {
+		self.balances.push((who, amount));
+		self
+	}
+
+	/// Endow `who` with `amount` of the sufficient asset `asset_id`, created if needed.
+	pub fn asset_balance(mut self, asset_id: AssetId, who: AccountId, amount: Balance) -> Self {
+		self.assets.push((asset_id, who, amount));
+		self
+	}
+
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut asset_ids: Vec<_> = self.assets.iter().map(|(asset_id, ..)| *asset_id).collect();
+		asset_ids.sort();
+		asset_ids.dedup();
+
+		RuntimeGenesisConfig {
+			balances: pallet_balances::GenesisConfig { balances: self.balances },
+			assets: pallet_assets::GenesisConfig {
+				assets: asset_ids
+					.into_iter()
+					.map(|asset_id| (asset_id, TIP_COLLECTOR, true, 1))
+					.collect(),
+				accounts: self.assets,
+				..Default::default()
+			},
+			..Default::default()
+		}
+		.build_storage()
+		.expect("Valid genesis")
+		.into()
+	}
+}
+
+/// An sr25519 dev key, e.g. `//Alice`, signing the native transactions of the tests.
+pub fn dev_key(name: &str) -> sr25519::Pair {
+	sr25519::Pair::from_string(&format!("//{name}"), None).expect("Valid dev key")
+}
+
+/// A chain on top of the genesis, its pool only keeps the valid transactions and ignores the
+/// `requires` and `provides` tags, the tests submit the transactions in order.
+pub struct Chain {
+	pool: Vec<(TransactionPriority, UncheckedExtrinsic)>,
+	parent_hash: Hash,
+}
+
+impl Default for Chain {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Chain {
+	/// The chain at the genesis, must be called within the externalities.
+	pub fn new() -> Self {
+		Self { pool: Vec::new(), parent_hash: System::block_hash(0) }
+	}
+
+	/// Submit an unsigned call to the pool, as a relayer of meta-transactions does.
+	pub fn submit_unsigned(&mut self, call: impl Into<RuntimeCall>) -> TransactionValidity {
+		self.submit(UncheckedExtrinsic::new_unsigned(call.into()))
+	}
+
+	/// Submit a call signed by `signer` to the pool, the nonce accounts for the transactions of
+	/// `signer` already in the pool.
+	pub fn submit_signed(
+		&mut self,
+		signer: &sr25519::Pair,
+		call: impl Into<RuntimeCall>,
+	) -> TransactionValidity {
+		let call = call.into();
+		let who = AccountId::from(signer.public());
+		let pending = self
+			.pool
+			.iter()
+			.filter(
+				|(_, xt)| matches!(&xt.signature, Some((Address::Id(sender), ..)) if *sender == who),
+			)
+			.count() as Nonce;
+		let extra: SignedExtra = (
+			frame_system::CheckNonZeroSender::new(),
+			frame_system::CheckSpecVersion::new(),
+			frame_system::CheckTxVersion::new(),
+			frame_system::CheckGenesis::new(),
+			frame_system::CheckEra::from(Era::Immortal),
+			frame_system::CheckNonce::from(System::account_nonce(&who) + pending),
+			frame_system::CheckWeight::new(),
+			pallet_transaction_payment::ChargeTransactionPayment::from(0),
+		);
+		let payload = SignedPayload::new(call.clone(), extra.clone()).expect("Valid payload");
+		let signature = payload.using_encoded(|payload| signer.sign(payload));
+		self.submit(UncheckedExtrinsic::new_signed(call, Address::Id(who), signature.into(), extra))
+	}
+
+	/// Validate `xt` like the pool of a node, in a state discarded afterwards, and keep it for the
+	/// next block if it's valid.
+	fn submit(&mut self, xt: UncheckedExtrinsic) -> TransactionValidity {
+		sp_io::storage::start_transaction();
+		let validity = Executive::validate_transaction(
+			TransactionSource::External,
+			xt.clone(),
+			self.parent_hash,
+		);
+		sp_io::storage::rollback_transaction();
+
+		if let Ok(valid) = &validity {
+			self.pool.push((valid.priority, xt));
+		}
+		validity
+	}
+
+	/// The transactions waiting for the next block.
+	pub fn pending(&self) -> usize {
+		self.pool.len()
+	}
+
+	/// Build the next block with the transactions of the pool by decreasing priority, the
+	/// results of their application are returned in the order of the block.
+	pub fn build_block(&mut self) -> Vec<ApplyExtrinsicResult> {
+		let header = Header::new(
+			System::block_number() + 1,
+			Default::default(),
+			Default::default(),
+			self.parent_hash,
+			Default::default(),
+		);
+		Executive::initialize_block(&header);
+
+		let mut pool = core::mem::take(&mut self.pool);
+		pool.sort_by_key(|(priority, _)| core::cmp::Reverse(*priority));
+		let results = pool
+			.into_iter()
+			.map(|(_, xt)| {
+				// Like the block builder, drop the changes of the extrinsics which can't be
+				// included
+				sp_io::storage::start_transaction();
+				let result = Executive::apply_extrinsic(xt);
+				match result {
+					Ok(_) => sp_io::storage::commit_transaction(),
+					Err(_) => sp_io::storage::rollback_transaction(),
+				}
+				result
+			})
+			.collect();
+
+		self.parent_hash = Executive::finalize_block().hash();
+		results
+	}
+
+	/// The events of the last block.
+	pub fn events(&self) -> Vec<RuntimeEvent> {
+		System::events().into_iter().map(|record| record.event).collect()
+	}
+}
+
+/// The EIP-712 message hash of a `meta_call` of `call`, of the current payload version.
+pub fn meta_call_message_hash(
+	who: &AccountId,
+	call: &RuntimeCall,
+	nonce: pallet_evm_account_mapping::Nonce,
+	tip: Option<Balance>,
+) -> [u8; 32] {
+	EvmAccountMapping::meta_call_message_hash(
+		pallet_evm_account_mapping::PayloadVersion::V1,
+		who,
+		&call.encode(),
+		nonce,
+		tip,
+	)
+}
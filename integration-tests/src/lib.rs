@@ -0,0 +1,29 @@
+// This file is part of EVM Account Mapping Pallet.
+
+// Copyright (C) HashForest Technology Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! End-to-end tests of the EVM account mapping pallet.
+//!
+//! Unlike the unit tests of the pallet, the meta-transactions are signed by real secp256k1 keys,
+//! validated as unsigned transactions of the pool and applied in blocks by `frame_executive`, in a
+//! runtime integrating the pallets the meta-transactions usually call.
+
+pub mod runtime;
+pub mod wallet;
+
+mod harness;
+
+pub use harness::*;
@@ -0,0 +1,338 @@
+// This file is part of EVM Account Mapping Pallet.
+
+// Copyright (C) HashForest Technology Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A runtime integrating the pallet with the pallets it's usually deployed with, configured like
+//! a production runtime rather than a unit test mock.
+
+use frame_support::{
+	construct_runtime, derive_impl, parameter_types,
+	traits::{
+		tokens::{
+			fungible::{self, Credit},
+			fungibles, Fortitude, Preservation,
+		},
+		AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, Contains,
+		Currency, ExistenceRequirement, OnUnbalanced,
+	},
+	weights::{constants::RocksDbWeight, IdentityFee, Weight},
+};
+use frame_system::{EnsureRoot, EnsureSigned};
+use pallet_transaction_payment::{ConstFeeMultiplier, CurrencyAdapter, Multiplier};
+use sp_runtime::{
+	generic,
+	traits::{AccountIdLookup, BlakeTwo256, Convert, IdentifyAccount, Verify},
+	DispatchResult, MultiSignature, Perbill,
+};
+
+pub type BlockNumber = u32;
+pub type Signature = MultiSignature;
+pub type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::AccountId;
+pub type Balance = u128;
+pub type AssetId = u32;
+pub type Nonce = u32;
+pub type Hash = sp_core::H256;
+
+pub const MILLI_CENTS: Balance = 1_000_000;
+pub const CENTS: Balance = 1_000 * MILLI_CENTS;
+pub const DOLLARS: Balance = 100 * CENTS;
+
+pub const EXISTENTIAL_DEPOSIT: Balance = 500;
+pub const SERVICE_FEE: Balance = 10 * MILLI_CENTS;
+
+/// The account collecting the tips paid in assets.
+pub const TIP_COLLECTOR: AccountId = AccountId::new([0xffu8; 32]);
+/// The account collecting the transaction fees.
+pub const FEE_COLLECTOR: AccountId = AccountId::new([0xeeu8; 32]);
+
+pub type Address = sp_runtime::MultiAddress<AccountId, ()>;
+pub type Header = generic::Header<BlockNumber, BlakeTwo256>;
+pub type Block = generic::Block<Header, UncheckedExtrinsic>;
+pub type SignedExtra = (
+	frame_system::CheckNonZeroSender<Runtime>,
+	frame_system::CheckSpecVersion<Runtime>,
+	frame_system::CheckTxVersion<Runtime>,
+	frame_system::CheckGenesis<Runtime>,
+	frame_system::CheckEra<Runtime>,
+	frame_system::CheckNonce<Runtime>,
+	frame_system::CheckWeight<Runtime>,
+	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+);
+pub type UncheckedExtrinsic =
+	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
+pub type Executive = frame_executive::Executive<
+	Runtime,
+	Block,
+	frame_system::ChainContext<Runtime>,
+	Runtime,
+	AllPalletsWithSystem,
+>;
+
+construct_runtime!(
+	pub struct Runtime {
+		System: frame_system,
+		Balances: pallet_balances,
+		TransactionPayment: pallet_transaction_payment,
+		Utility: pallet_utility,
+		Assets: pallet_assets,
+		EvmAccountMapping: pallet_evm_account_mapping,
+	}
+);
+
+const NORMAL_DISPATCH_RATIO: Perbill = Perbill::from_percent(75);
+
+parameter_types! {
+	pub BlockWeights: frame_system::limits::BlockWeights =
+		frame_system::limits::BlockWeights::with_sensible_defaults(
+			Weight::from_parts(
+				2 * frame_support::weights::constants::WEIGHT_REF_TIME_PER_SECOND,
+				u64::MAX,
+			),
+			NORMAL_DISPATCH_RATIO,
+		);
+	pub BlockLength: frame_system::limits::BlockLength =
+		frame_system::limits::BlockLength::max_with_normal_ratio(5 * 1024 * 1024, NORMAL_DISPATCH_RATIO);
+}
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig)]
+impl frame_system::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = BlockWeights;
+	type BlockLength = BlockLength;
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = Nonce;
+	type Hash = Hash;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = AccountIdLookup<AccountId, ()>;
+	type Block = Block;
+	type BlockHashCount = ConstU32<2400>;
+	type DbWeight = RocksDbWeight;
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<Balance>;
+	type OnKilledAccount = EvmAccountMapping;
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type WeightInfo = ();
+	type Balance = Balance;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU128<EXISTENTIAL_DEPOSIT>;
+	type AccountStore = System;
+	type ReserveIdentifier = [u8; 8];
+	type FreezeIdentifier = ();
+	type MaxLocks = ConstU32<50>;
+	type MaxReserves = ConstU32<50>;
+	type MaxHolds = ConstU32<4>;
+	type MaxFreezes = ();
+}
+
+parameter_types! {
+	pub FeeMultiplier: Multiplier = Multiplier::from_u32(1);
+}
+
+/// Deposit the transaction fees, of the signed transactions and of the meta-transactions alike,
+/// into `FEE_COLLECTOR`.
+pub struct DealWithFees;
+impl OnUnbalanced<pallet_balances::NegativeImbalance<Runtime>> for DealWithFees {
+	fn on_nonzero_unbalanced(amount: pallet_balances::NegativeImbalance<Runtime>) {
+		Balances::resolve_creating(&FEE_COLLECTOR, amount);
+	}
+}
+impl OnUnbalanced<Credit<AccountId, Balances>> for DealWithFees {
+	fn on_nonzero_unbalanced(amount: Credit<AccountId, Balances>) {
+		let _ = <Balances as fungible::Balanced<AccountId>>::resolve(&FEE_COLLECTOR, amount);
+	}
+}
+
+impl pallet_transaction_payment::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type OnChargeTransaction = CurrencyAdapter<Balances, DealWithFees>;
+	type OperationalFeeMultiplier = ConstU8<5>;
+	type WeightToFee = IdentityFee<Balance>;
+	type LengthToFee = IdentityFee<Balance>;
+	type FeeMultiplierUpdate = ConstFeeMultiplier<FeeMultiplier>;
+}
+
+impl pallet_utility::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type PalletsOrigin = OriginCaller;
+	type WeightInfo = ();
+}
+
+impl pallet_assets::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = Balance;
+	type RemoveItemsLimit = ConstU32<1000>;
+	type AssetId = AssetId;
+	type AssetIdParameter = codec::Compact<AssetId>;
+	type Currency = Balances;
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<AccountId>>;
+	type ForceOrigin = EnsureRoot<AccountId>;
+	type AssetDeposit = ConstU128<DOLLARS>;
+	type AssetAccountDeposit = ConstU128<CENTS>;
+	type MetadataDepositBase = ConstU128<CENTS>;
+	type MetadataDepositPerByte = ConstU128<MILLI_CENTS>;
+	type ApprovalDeposit = ConstU128<MILLI_CENTS>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type CallbackHandle = ();
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
+parameter_types! {
+	pub EIP712Name: Vec<u8> = b"Substrate".to_vec();
+	pub EIP712Version: Vec<u8> = b"1".to_vec();
+	pub EIP712ChainID: pallet_evm_account_mapping::EIP712ChainID = sp_core::U256::from(0);
+	pub EIP712VerifyingContractAddress: pallet_evm_account_mapping::EIP712VerifyingContractAddress =
+		sp_core::H160::from([0u8; 20]);
+	pub const UnsignedTagPrefix: &'static str = "EVMAccountMapping";
+	pub const SpendExistenceRequirement: ExistenceRequirement = ExistenceRequirement::AllowDeath;
+	pub ApprovalWeight: Weight = Weight::from_parts(50_000_000, 3_675);
+}
+
+pub struct ConvertTypedCall;
+impl Convert<pallet_evm_account_mapping::TypedCallOf<Runtime>, Option<RuntimeCall>>
+	for ConvertTypedCall
+{
+	fn convert(
+		typed_call: pallet_evm_account_mapping::TypedCallOf<Runtime>,
+	) -> Option<RuntimeCall> {
+		use pallet_evm_account_mapping::TypedCall;
+
+		Some(match typed_call {
+			TypedCall::BalanceTransfer { to, amount } =>
+				RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive {
+					dest: to.into(),
+					value: amount,
+				}),
+			TypedCall::Remark { remark } =>
+				RuntimeCall::System(frame_system::Call::remark { remark }),
+		})
+	}
+}
+
+/// Forbid `balances.transferAll`, also within batches.
+pub struct NoTransferAll;
+impl Contains<RuntimeCall> for NoTransferAll {
+	fn contains(call: &RuntimeCall) -> bool {
+		!matches!(call, RuntimeCall::Balances(pallet_balances::Call::transfer_all { .. }))
+	}
+}
+
+/// The calls wrapped by the batches of `pallet_utility`.
+pub struct UtilityCalls;
+impl pallet_evm_account_mapping::InspectInnerCalls<RuntimeCall> for UtilityCalls {
+	fn inner_calls(call: &RuntimeCall) -> Vec<&RuntimeCall> {
+		match call {
+			RuntimeCall::Utility(
+				pallet_utility::Call::batch { calls } |
+				pallet_utility::Call::batch_all { calls } |
+				pallet_utility::Call::force_batch { calls },
+			) => calls.iter().collect(),
+			_ => Vec::new(),
+		}
+	}
+}
+
+/// Pay the asset tips to [`TIP_COLLECTOR`] in any asset of `pallet_assets`.
+pub struct AssetTips;
+impl pallet_evm_account_mapping::TipHandler<AccountId, AssetId> for AssetTips {
+	fn can_pay(who: &AccountId, asset_id: &AssetId, amount: u128) -> bool {
+		use fungibles::Inspect;
+
+		Assets::reducible_balance(*asset_id, who, Preservation::Preserve, Fortitude::Polite) >=
+			amount
+	}
+
+	fn pay(who: &AccountId, asset_id: &AssetId, amount: u128) -> DispatchResult {
+		use fungibles::Mutate;
+
+		Assets::transfer(*asset_id, who, &TIP_COLLECTOR, amount, Preservation::Preserve).map(|_| ())
+	}
+
+	fn weight() -> Weight {
+		Weight::from_parts(50_000_000, 6_208)
+	}
+}
+
+impl pallet_evm_account_mapping::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type Currency = Balances;
+	type LinkDeposit = ConstU128<CENTS>;
+	type GrantDeposit = ConstU128<CENTS>;
+	type SponsorshipDeposit = ConstU128<CENTS>;
+	type AddressConverter = pallet_evm_account_mapping::SubstrateAddressConverter;
+	type ServiceFee = ConstU128<SERVICE_FEE>;
+	type TypedCallConverter = ConvertTypedCall;
+	type FeeAdjustment = ();
+	type OnUnbalancedForServiceFee = ();
+	type OnChargeMetaTransaction =
+		pallet_evm_account_mapping::HoldingFeeAdapter<Balances, DealWithFees>;
+	type SpendExistenceRequirement = SpendExistenceRequirement;
+	type CallFilter = NoTransferAll;
+	type CallFilterOrigin = EnsureRoot<AccountId>;
+	type ServiceFeeWaiver = frame_support::traits::Nothing;
+	type InspectInnerCalls = UtilityCalls;
+	type ReceiptRetention = ConstU32<100>;
+	type IndexMetaTransactions = ConstBool<true>;
+	type FreeCallsPerAccount = ConstU32<0>;
+	type RateLimitWindow = ConstU32<10>;
+	type MaxCallsPerWindow = ConstU32<10>;
+	type MaxCallDepth = ConstU32<8>;
+	type AllowOperationalCalls = ConstBool<false>;
+	type DispatchAsEvmMapped = ConstBool<false>;
+	type TipAssetId = AssetId;
+	type TipHandler = AssetTips;
+	type PermitAssetId = AssetId;
+	type AssetApprovals = pallet_evm_account_mapping::FungiblesApprovals<Assets, ApprovalWeight>;
+	type ProxyType = ();
+	type PriorityPolicy = pallet_evm_account_mapping::DefaultPriority<Runtime>;
+	type UnsignedLongevity = ConstU64<5>;
+	type MaxNonceGap = ConstU64<16>;
+	type ReplayProtection = pallet_evm_account_mapping::SequentialNonces<Runtime>;
+	type MaxMetaTxPerBlock = ConstU32<256>;
+	type PurgeNonceOnReap = ConstBool<false>;
+	type MetaMortality = ConstBool<false>;
+	type UnsignedTagPrefix = UnsignedTagPrefix;
+	type BlockAuthor = ();
+	type TipToBlockAuthor = ConstBool<false>;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type RecoveryOrigin = EnsureRoot<AccountId>;
+	type RecoveryDelay = ConstU32<100>;
+	type ControllerRotationDelay = ConstU32<100>;
+	type EIP712Name = EIP712Name;
+	type EIP712Version = EIP712Version;
+	type EIP712ChainID = EIP712ChainID;
+	type EIP712VerifyingContractAddress = EIP712VerifyingContractAddress;
+	type EIP712ExtraVerifyingContracts = ();
+	type EIP712Salt = ();
+	type EIP712ParaId = ();
+	type WeightInfo = pallet_evm_account_mapping::weights::SubstrateWeight<Runtime>;
+}
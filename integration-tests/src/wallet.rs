@@ -0,0 +1,58 @@
+// This file is part of EVM Account Mapping Pallet.
+
+// Copyright (C) HashForest Technology Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The EVM wallets signing the meta-transactions of the tests.
+
+use crate::runtime::AccountId;
+use k256::ecdsa::SigningKey;
+use pallet_evm_account_mapping::{AddressConversion, EvmAddress, MetaSignature};
+
+/// A secp256k1 key signing EIP-712 hashes like an EVM wallet does.
+pub struct Wallet {
+	key: SigningKey,
+}
+
+impl Wallet {
+	/// The wallet of the secret key made of `seed` repeated, `seed` must not be zero.
+	pub fn from_seed(seed: u8) -> Self {
+		Self { key: SigningKey::from_slice(&[seed; 32]).expect("Valid secret key") }
+	}
+
+	/// The EVM address of the wallet.
+	pub fn evm_address(&self) -> EvmAddress {
+		let point = self.key.verifying_key().to_encoded_point(false);
+		let public_key: [u8; 64] = point.as_bytes()[1..].try_into().expect("Uncompressed point");
+		pallet_evm_account_mapping::evm_address_of(&public_key)
+	}
+
+	/// The account the wallet is mapped to.
+	pub fn account(&self) -> AccountId {
+		let point = self.key.verifying_key().to_encoded_point(true);
+		pallet_evm_account_mapping::SubstrateAddressConverter::try_convert(point.as_bytes())
+			.expect("Convertable")
+	}
+
+	/// Sign the EIP-712 `message_hash` of a payload.
+	pub fn sign(&self, message_hash: &[u8; 32]) -> MetaSignature {
+		let (signature, recovery_id) =
+			self.key.sign_prehash_recoverable(message_hash).expect("Signable");
+		let mut bytes = [0u8; 65];
+		bytes[..64].copy_from_slice(&signature.to_bytes());
+		bytes[64] = 27 + recovery_id.to_byte();
+		bytes.into()
+	}
+}
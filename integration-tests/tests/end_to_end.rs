@@ -0,0 +1,399 @@
+// This file is part of EVM Account Mapping Pallet.
+
+// Copyright (C) HashForest Technology Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use codec::Encode;
+use evm_account_mapping_integration_tests::{
+	dev_key, meta_call_message_hash, runtime::*, wallet::Wallet, Chain, ExtBuilder,
+};
+use pallet_evm_account_mapping::{
+	AssetTip, BatchMode, Event as MappingEvent, InvalidMetaTransaction, PayloadVersion,
+	SponsorshipTarget,
+};
+use sp_runtime::transaction_validity::InvalidTransaction;
+
+type MappingCall = pallet_evm_account_mapping::Call<Runtime>;
+
+/// The fees settled for the meta-transactions of the last block, as `(who, payer, total)`.
+fn settled_fees(chain: &Chain) -> Vec<(AccountId, AccountId, Balance)> {
+	chain
+		.events()
+		.into_iter()
+		.filter_map(|event| match event {
+			RuntimeEvent::EvmAccountMapping(MappingEvent::MetaTransactionFeeSettled {
+				who,
+				payer,
+				service_fee,
+				inclusion_fee,
+				tip,
+				..
+			}) => Some((who, payer, service_fee + inclusion_fee + tip)),
+			_ => None,
+		})
+		.collect()
+}
+
+/// The inclusion fees and the tips of the signed transactions and of the meta-transactions of the
+/// last block.
+fn collected_fees(chain: &Chain) -> Vec<Balance> {
+	chain
+		.events()
+		.into_iter()
+		.filter_map(|event| match event {
+			RuntimeEvent::TransactionPayment(
+				pallet_transaction_payment::Event::TransactionFeePaid { actual_fee, tip, .. },
+			) => Some(actual_fee + tip),
+			RuntimeEvent::EvmAccountMapping(MappingEvent::MetaTransactionFeeSettled {
+				inclusion_fee,
+				tip,
+				..
+			}) => Some(inclusion_fee + tip),
+			_ => None,
+		})
+		.collect()
+}
+
+fn transfer(dest: &AccountId, value: Balance) -> RuntimeCall {
+	RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive {
+		dest: dest.clone().into(),
+		value,
+	})
+}
+
+#[test]
+fn meta_call_goes_through_the_pool_and_a_block() {
+	let wallet = Wallet::from_seed(1);
+	let account = wallet.account();
+	let dest = AccountId::new([2u8; 32]);
+
+	ExtBuilder::default()
+		.balance(account.clone(), 10 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			let mut chain = Chain::new();
+
+			let call = transfer(&dest, DOLLARS);
+			let signature = wallet.sign(&meta_call_message_hash(&account, &call, 0, None));
+			let meta_call = MappingCall::meta_call {
+				who: account.clone(),
+				call: Box::new(call),
+				nonce: 0,
+				signature,
+				tip: None,
+				version: PayloadVersion::V1,
+			};
+			chain.submit_unsigned(meta_call.clone()).expect("Valid meta-transaction");
+
+			let results = chain.build_block();
+			assert_eq!(results.len(), 1);
+			assert!(matches!(results[0], Ok(Ok(()))));
+
+			assert_eq!(Balances::free_balance(&dest), DOLLARS);
+			let fees = settled_fees(&chain);
+			assert_eq!(fees.len(), 1);
+			let (who, payer, fee) = fees[0].clone();
+			assert_eq!(who, account);
+			assert_eq!(payer, account);
+			assert!(fee > SERVICE_FEE);
+			assert_eq!(Balances::free_balance(&account), 9 * DOLLARS - fee);
+			assert_eq!(
+				EvmAccountMapping::evm_address_of_account(&account),
+				Some(wallet.evm_address())
+			);
+
+			let receipt = EvmAccountMapping::receipt(&account, 0).expect("Executed");
+			assert_eq!(receipt.block_number, 1);
+			assert!(receipt.success);
+
+			// The nonce is used, the relayed meta-transaction can't be replayed in the next block
+			assert_eq!(chain.submit_unsigned(meta_call), Err(InvalidTransaction::Stale.into()));
+			assert_eq!(chain.pending(), 0);
+		});
+}
+
+#[test]
+fn utility_batches_are_filtered_per_inner_call() {
+	let wallet = Wallet::from_seed(1);
+	let account = wallet.account();
+	let dest = AccountId::new([2u8; 32]);
+
+	ExtBuilder::default()
+		.balance(account.clone(), 10 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			let mut chain = Chain::new();
+
+			let batch = |calls: Vec<RuntimeCall>| {
+				RuntimeCall::Utility(pallet_utility::Call::batch_all { calls })
+			};
+			let meta_call = |call: RuntimeCall, nonce| MappingCall::meta_call {
+				who: account.clone(),
+				signature: wallet.sign(&meta_call_message_hash(&account, &call, nonce, None)),
+				call: Box::new(call),
+				nonce,
+				tip: None,
+				version: PayloadVersion::V1,
+			};
+
+			// `balances.transferAll` is forbidden, also when wrapped in a batch
+			let forbidden = batch(vec![
+				transfer(&dest, DOLLARS),
+				RuntimeCall::Balances(pallet_balances::Call::transfer_all {
+					dest: dest.clone().into(),
+					keep_alive: true,
+				}),
+			]);
+			assert_eq!(
+				chain.submit_unsigned(meta_call(forbidden, 0)),
+				Err(InvalidMetaTransaction::CallFiltered.into())
+			);
+			assert_eq!(chain.pending(), 0);
+
+			let allowed = batch(vec![
+				transfer(&dest, DOLLARS),
+				RuntimeCall::System(frame_system::Call::remark_with_event {
+					remark: b"hi".to_vec(),
+				}),
+			]);
+			chain.submit_unsigned(meta_call(allowed, 0)).expect("Valid meta-transaction");
+			assert!(chain.build_block().iter().all(|result| matches!(result, Ok(Ok(())))));
+
+			assert_eq!(Balances::free_balance(&dest), DOLLARS);
+			let events = chain.events();
+			assert!(events.contains(&RuntimeEvent::Utility(pallet_utility::Event::BatchCompleted)));
+			assert!(events.iter().any(|event| matches!(
+				event,
+				RuntimeEvent::System(frame_system::Event::Remarked { sender, .. }) if *sender == account
+			)));
+		});
+}
+
+#[test]
+fn meta_batch_is_applied_in_a_block() {
+	let wallet = Wallet::from_seed(1);
+	let account = wallet.account();
+	let (alice, bob) = (AccountId::new([2u8; 32]), AccountId::new([3u8; 32]));
+
+	ExtBuilder::default()
+		.balance(account.clone(), 10 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			let mut chain = Chain::new();
+
+			let calls = vec![transfer(&alice, DOLLARS), transfer(&bob, 2 * DOLLARS)];
+			let message_hash = EvmAccountMapping::batch_message_hash(
+				&account,
+				&calls,
+				0,
+				None,
+				BatchMode::AllOrNothing,
+			);
+			chain
+				.submit_unsigned(MappingCall::meta_batch {
+					who: account.clone(),
+					calls,
+					nonce: 0,
+					signature: wallet.sign(&message_hash),
+					tip: None,
+					mode: BatchMode::AllOrNothing,
+				})
+				.expect("Valid meta-transaction");
+			assert!(matches!(chain.build_block()[..], [Ok(Ok(()))]));
+
+			assert_eq!(Balances::free_balance(&alice), DOLLARS);
+			assert_eq!(Balances::free_balance(&bob), 2 * DOLLARS);
+			let (_, _, fee) = settled_fees(&chain).pop().expect("Settled");
+			assert_eq!(Balances::free_balance(&account), 7 * DOLLARS - fee);
+			assert!(EvmAccountMapping::receipt(&account, 0).expect("Executed").success);
+		});
+}
+
+#[test]
+fn sponsorship_pays_for_an_unfunded_wallet() {
+	let wallet = Wallet::from_seed(1);
+	let account = wallet.account();
+	let sponsor_key = dev_key("Alice");
+	let sponsor = AccountId::from(sp_core::Pair::public(&sponsor_key));
+
+	ExtBuilder::default()
+		.balance(sponsor.clone(), 10 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			let mut chain = Chain::new();
+
+			let remark = RuntimeCall::System(frame_system::Call::remark_with_event {
+				remark: b"hi".to_vec(),
+			});
+			let meta_call = MappingCall::meta_call {
+				who: account.clone(),
+				signature: wallet.sign(&meta_call_message_hash(&account, &remark, 0, None)),
+				call: Box::new(remark),
+				nonce: 0,
+				tip: None,
+				version: PayloadVersion::V1,
+			};
+
+			// The wallet can't pay its fees
+			assert_eq!(
+				chain.submit_unsigned(meta_call.clone()),
+				Err(InvalidTransaction::Payment.into())
+			);
+
+			let target = SponsorshipTarget::Signer(wallet.evm_address());
+			chain
+				.submit_signed(
+					&sponsor_key,
+					MappingCall::sponsor { target, budget: DOLLARS, expires_at: 100 },
+				)
+				.expect("Valid transaction");
+			assert!(matches!(chain.build_block()[..], [Ok(Ok(()))]));
+			let sponsor_balance = Balances::free_balance(&sponsor);
+
+			chain.submit_unsigned(meta_call).expect("Sponsored meta-transaction");
+			assert!(matches!(chain.build_block()[..], [Ok(Ok(()))]));
+
+			let (who, payer, fee) = settled_fees(&chain).pop().expect("Settled");
+			assert_eq!((who, payer), (account.clone(), sponsor.clone()));
+			assert_eq!(Balances::free_balance(&sponsor), sponsor_balance - fee);
+			assert_eq!(Balances::free_balance(&account), 0);
+			assert_eq!(
+				pallet_evm_account_mapping::Sponsorships::<Runtime>::get(&target)
+					.expect("Budget left")
+					.budget,
+				DOLLARS - fee
+			);
+		});
+}
+
+#[test]
+fn asset_tips_are_paid_with_pallet_assets() {
+	const ASSET: AssetId = 1;
+
+	let wallet = Wallet::from_seed(1);
+	let account = wallet.account();
+
+	ExtBuilder::default()
+		.balance(account.clone(), 10 * DOLLARS)
+		.asset_balance(ASSET, account.clone(), 1_000)
+		.build()
+		.execute_with(|| {
+			let mut chain = Chain::new();
+
+			let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![1] });
+			let tip = AssetTip { asset_id: ASSET, amount: 100 };
+			let message_hash =
+				EvmAccountMapping::asset_tip_message_hash(&account, &remark.encode(), 0, &tip);
+			chain
+				.submit_unsigned(MappingCall::meta_call_with_asset_tip {
+					who: account.clone(),
+					call: Box::new(remark),
+					nonce: 0,
+					signature: wallet.sign(&message_hash),
+					tip: tip.clone(),
+				})
+				.expect("Valid meta-transaction");
+			assert!(matches!(chain.build_block()[..], [Ok(Ok(()))]));
+
+			assert_eq!(Assets::balance(ASSET, &account), 900);
+			assert_eq!(Assets::balance(ASSET, TIP_COLLECTOR), 100);
+			assert!(chain.events().iter().any(|event| matches!(
+				event,
+				RuntimeEvent::EvmAccountMapping(MappingEvent::MetaTransactionFeeSettled {
+					asset_tip: Some(asset_tip),
+					..
+				}) if *asset_tip == tip
+			)));
+		});
+}
+
+#[test]
+fn meta_transaction_fees_land_with_the_transaction_fees() {
+	let wallet = Wallet::from_seed(1);
+	let account = wallet.account();
+	let signer_key = dev_key("Alice");
+	let signer = AccountId::from(sp_core::Pair::public(&signer_key));
+	let dest = AccountId::new([2u8; 32]);
+
+	ExtBuilder::default()
+		.balance(account.clone(), 10 * DOLLARS)
+		.balance(signer.clone(), 10 * DOLLARS)
+		.balance(FEE_COLLECTOR, DOLLARS)
+		.build()
+		.execute_with(|| {
+			let mut chain = Chain::new();
+
+			chain
+				.submit_signed(&signer_key, transfer(&dest, DOLLARS))
+				.expect("Valid transaction");
+			let call = transfer(&dest, DOLLARS);
+			let signature = wallet.sign(&meta_call_message_hash(&account, &call, 0, Some(CENTS)));
+			chain
+				.submit_unsigned(MappingCall::meta_call {
+					who: account.clone(),
+					call: Box::new(call),
+					nonce: 0,
+					signature,
+					tip: Some(CENTS),
+					version: PayloadVersion::V1,
+				})
+				.expect("Valid meta-transaction");
+			assert!(matches!(chain.build_block()[..], [Ok(Ok(())), Ok(Ok(()))]));
+
+			// The inclusion fees and the tips of both go to the fee handler of the runtime, only
+			// the service fee is left to `OnUnbalancedForServiceFee`
+			let collected = collected_fees(&chain);
+			assert_eq!(collected.len(), 2);
+			assert!(collected.iter().all(|fee| *fee > 0));
+			assert_eq!(
+				Balances::free_balance(&FEE_COLLECTOR),
+				DOLLARS + collected.iter().sum::<Balance>()
+			);
+		});
+}
+
+#[test]
+fn failed_inner_calls_are_counted_in_the_block() {
+	let wallet = Wallet::from_seed(1);
+	let account = wallet.account();
+	let dest = AccountId::new([2u8; 32]);
+
+	ExtBuilder::default()
+		.balance(account.clone(), 10 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			let mut chain = Chain::new();
+
+			// More than the balance of the account
+			let call = transfer(&dest, 20 * DOLLARS);
+			let signature = wallet.sign(&meta_call_message_hash(&account, &call, 0, None));
+			chain
+				.submit_unsigned(MappingCall::meta_call {
+					who: account.clone(),
+					call: Box::new(call),
+					nonce: 0,
+					signature,
+					tip: None,
+					version: PayloadVersion::V1,
+				})
+				.expect("Valid meta-transaction");
+			assert!(matches!(chain.build_block()[..], [Ok(Ok(()))]));
+
+			assert_eq!(
+				EvmAccountMapping::rejection_stats(),
+				pallet_evm_account_mapping::RejectionStats { call_failed: 1, ..Default::default() }
+			);
+		});
+}
@@ -0,0 +1,104 @@
+// This file is part of EVM Account Mapping Pallet.
+
+// Copyright (C) HashForest Technology Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the EVM Account Mapping pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::{traits::NumberFor, RuntimeDebug};
+use sp_std::vec::Vec;
+
+pub use pallet_evm_account_mapping::{EvmAddress, MetaFeeEstimate, Nonce, Receipt, RejectionStats};
+
+/// The fees of a meta-transaction broken down for a confirmation screen.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct FeeQuote<AccountId, Balance> {
+	/// The service fee of the pallet, zero if the account has a free call left.
+	pub service_fee: Balance,
+	/// The fee of the inner call, excluding the tip.
+	pub inclusion_fee: Balance,
+	/// The tip for the block author.
+	pub tip: Balance,
+	/// The sum of all the fees above.
+	pub total: Balance,
+	/// The account the fees are charged from.
+	pub payer: AccountId,
+}
+
+impl<AccountId, Balance> FeeQuote<AccountId, Balance> {
+	/// Quote `estimate` as paid by `payer`.
+	pub fn new(estimate: MetaFeeEstimate<Balance>, payer: AccountId) -> Self {
+		let MetaFeeEstimate { inclusion_fee, tip, service_fee, total } = estimate;
+		Self { service_fee, inclusion_fee, tip, total, payer }
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	#[api_version(5)]
+	pub trait EvmAccountMappingApi<Balance, AccountId>
+	where
+		Balance: Codec,
+		AccountId: Codec,
+	{
+		/// Estimate the fees of a meta-transaction, `call_data` is the SCALE encoded call.
+		///
+		/// Returns `None` if `call_data` can't be decoded.
+		fn estimate_meta_fee(call_data: Vec<u8>, tip: Balance) -> Option<MetaFeeEstimate<Balance>>;
+
+		/// Quote the fees of a meta-transaction of `who`, paid by the sponsor of the active
+		/// sponsorship covering them or by `who`.
+		///
+		/// `sponsor` is ignored, the sponsorships are registered on chain.
+		///
+		/// Returns `None` if `call_data` can't be decoded.
+		#[api_version(2)]
+		fn quote_meta_transaction(
+			who: AccountId,
+			call_data: Vec<u8>,
+			tip: Balance,
+			sponsor: Option<AccountId>,
+		) -> Option<FeeQuote<AccountId, Balance>>;
+
+		/// The account `evm_address` is mapped to.
+		///
+		/// Returns `None` if the account can't be derived from the address and the address has
+		/// never signed a meta-transaction.
+		#[api_version(3)]
+		fn map_evm_address(evm_address: EvmAddress) -> Option<AccountId>;
+
+		/// The EVM address `who` is mapped from.
+		///
+		/// Returns `None` if `who` has never sent a meta-transaction.
+		#[api_version(3)]
+		fn evm_address_of(who: AccountId) -> Option<EvmAddress>;
+
+		/// The receipt of the meta-transaction of `who` with `nonce`, for the light clients which
+		/// can't scan the events.
+		///
+		/// Returns `None` if the meta-transaction hasn't been executed, or its receipt was pruned
+		/// after `ReceiptRetention` blocks.
+		#[api_version(4)]
+		fn receipt(who: AccountId, nonce: Nonce) -> Option<Receipt<NumberFor<Block>, Balance>>;
+
+		/// The meta-transactions rejected at their dispatch or failed in the current block, by
+		/// category.
+		#[api_version(5)]
+		fn rejection_stats() -> RejectionStats;
+	}
+}
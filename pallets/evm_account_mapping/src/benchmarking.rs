@@ -22,6 +22,7 @@ use super::*;
 #[allow(unused)]
 use crate::Pallet as ThisPallet;
 use frame_benchmarking::v2::*;
+use frame_support::traits::EnsureOrigin;
 use frame_system::RawOrigin;
 
 use codec::Decode;
@@ -34,6 +35,29 @@ fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
 	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
 }
 
+/// The key type of the signing keys generated by the benchmarks.
+const KEY_TYPE: sp_core::crypto::KeyTypeId = sp_core::crypto::KeyTypeId(*b"evmm");
+
+/// Generate a signing key in the keystore, returning it together with the account it maps to.
+fn mapped_signer<T: Config>() -> (sp_core::ecdsa::Public, T::AccountId)
+where
+	T: frame_system::Config<AccountId = sp_runtime::AccountId32>,
+{
+	let public = sp_io::crypto::ecdsa_generate(KEY_TYPE, None);
+	let message_hash = [0u8; 32];
+	let signature = sp_io::crypto::ecdsa_sign_prehashed(KEY_TYPE, &public, &message_hash)
+		.expect("The key is in the keystore");
+	let public_key = Pallet::<T>::ecdsa_recover_public_key(&signature.0, &message_hash)
+		.expect("The signature is valid");
+	let account = T::AddressConverter::try_convert(&encode_public_key(
+		&public_key,
+		&T::AddressConverter::SECP256K1_PUBLIC_KEY_FORM,
+	))
+	.expect("The public key is valid");
+
+	(public, account)
+}
+
 #[benchmarks( where PaymentBalanceOf<T>: FixedPointOperand, <T as frame_system::Config>::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>, T: frame_system::Config<AccountId = sp_runtime::AccountId32>,)]
 mod benchmarks {
 	use super::*;
@@ -54,10 +78,493 @@ mod benchmarks {
 		T::Currency::make_free_balance_be(&account, BalanceOf::<T>::max_value() / 2u32.into());
 
 		#[extrinsic_call]
-		_(RawOrigin::None, account, Box::new(call.into()), nonce, signature, None);
+		_(
+			RawOrigin::None,
+			account,
+			Box::new(call.into()),
+			nonce,
+			signature.into(),
+			None,
+			PayloadVersion::V0,
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn block_address() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let address = EvmAddress::repeat_byte(1);
+
+		#[extrinsic_call]
+		_(origin as <T as frame_system::Config>::RuntimeOrigin, address);
 
+		assert!(Blocked::<T>::contains_key(address));
 		Ok(())
 	}
 
+	#[benchmark]
+	fn unblock_address() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let address = EvmAddress::repeat_byte(1);
+		Blocked::<T>::insert(address, ());
+
+		#[extrinsic_call]
+		_(origin as <T as frame_system::Config>::RuntimeOrigin, address);
+
+		assert!(!Blocked::<T>::contains_key(address));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn meta_transfer() -> Result<(), BenchmarkError> {
+		let (public, who) = mapped_signer::<T>();
+		let dest: T::AccountId = account("dest", 0, 0);
+		let amount = <T::Currency as Currency<_>>::minimum_balance();
+		let nonce: u64 = 0;
+		let message_hash = Pallet::<T>::meta_transfer_message_hash(&who, &dest, amount, nonce);
+		let signature = sp_io::crypto::ecdsa_sign_prehashed(KEY_TYPE, &public, &message_hash)
+			.expect("The key is in the keystore")
+			.0;
+
+		T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 2u32.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::None, who.clone(), dest.clone(), amount, nonce, signature.into());
+
+		assert_eq!(AccountNonce::<T>::get(&who), 1);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn initiate_recovery() -> Result<(), BenchmarkError> {
+		let origin =
+			T::RecoveryOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let who: T::AccountId = account("who", 0, 0);
+		let new_controller: T::AccountId = account("new_controller", 0, 0);
+		EvmAddresses::<T>::insert(&who, EvmAddress::repeat_byte(1));
+
+		#[extrinsic_call]
+		_(origin as <T as frame_system::Config>::RuntimeOrigin, who.clone(), new_controller);
+
+		assert!(Recoveries::<T>::contains_key(&who));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn veto_recovery() -> Result<(), BenchmarkError> {
+		let who: T::AccountId = account("who", 0, 0);
+		Recoveries::<T>::insert(
+			&who,
+			RecoveryRequest {
+				new_controller: account("new_controller", 0, 0),
+				executable_at: frame_system::Pallet::<T>::block_number(),
+			},
+		);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(who.clone()));
+
+		assert!(!Recoveries::<T>::contains_key(&who));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn execute_recovery() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let who: T::AccountId = account("who", 0, 0);
+		let new_controller: T::AccountId = account("new_controller", 0, 0);
+		T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 2u32.into());
+		EvmAddresses::<T>::insert(&who, EvmAddress::repeat_byte(1));
+		MappedAccounts::<T>::insert(EvmAddress::repeat_byte(1), &who);
+		Recoveries::<T>::insert(
+			&who,
+			RecoveryRequest {
+				new_controller: new_controller.clone(),
+				executable_at: frame_system::Pallet::<T>::block_number(),
+			},
+		);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), who.clone());
+
+		assert!(!Recoveries::<T>::contains_key(&who));
+		assert!(!<T::Currency as Currency<_>>::free_balance(&new_controller).is_zero());
+		assert_eq!(MappedAccounts::<T>::get(EvmAddress::repeat_byte(1)), Some(new_controller));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn link_accounts() -> Result<(), BenchmarkError> {
+		let (public, who) = mapped_signer::<T>();
+		let account: T::AccountId = whitelisted_caller();
+		let nonce: u64 = 0;
+		let message_hash = Pallet::<T>::link_accounts_message_hash(&who, &account, nonce);
+		let signature = sp_io::crypto::ecdsa_sign_prehashed(KEY_TYPE, &public, &message_hash)
+			.expect("The key is in the keystore")
+			.0;
+
+		T::Currency::make_free_balance_be(&account, BalanceOf::<T>::max_value() / 2u32.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(account.clone()), who.clone(), nonce, signature.into());
+
+		assert_eq!(Links::<T>::get(&who), Some(account));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn unlink_accounts() -> Result<(), BenchmarkError> {
+		let who: T::AccountId = account("who", 0, 0);
+		let account: T::AccountId = whitelisted_caller();
+		Links::<T>::insert(&who, &account);
+		ReverseLinks::<T>::insert(&account, &who);
+		T::Currency::make_free_balance_be(&account, BalanceOf::<T>::max_value() / 2u32.into());
+		T::Currency::hold(
+			&HoldReason::LinkDeposit.into(),
+			&account,
+			Pallet::<T>::deposit_of(T::LinkDeposit::get()),
+		)?;
+
+		// The native side of the link unlinks, which is the heavier path
+		#[extrinsic_call]
+		_(RawOrigin::Signed(account.clone()));
+
+		assert!(!Links::<T>::contains_key(&who));
+		assert!(!ReverseLinks::<T>::contains_key(&account));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn meta_call_as_linked() -> Result<(), BenchmarkError> {
+		let (public, who) = mapped_signer::<T>();
+		let account: T::AccountId = whitelisted_caller();
+		Links::<T>::insert(&who, &account);
+		ReverseLinks::<T>::insert(&account, &who);
+		let call: <T as frame_system::Config>::RuntimeCall =
+			frame_system::Call::<T>::remark { remark: vec![] }.into();
+		let nonce: u64 = 0;
+		let message_hash =
+			Pallet::<T>::call_as_linked_message_hash(&who, &account, &call.encode(), nonce, None);
+		let signature = sp_io::crypto::ecdsa_sign_prehashed(KEY_TYPE, &public, &message_hash)
+			.expect("The key is in the keystore")
+			.0;
+
+		T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 2u32.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::None, who.clone(), Box::new(call.into()), nonce, signature.into(), None);
+
+		assert_eq!(AccountNonce::<T>::get(&who), 1);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn grant() {
+		let granter: T::AccountId = whitelisted_caller();
+		let grantee: T::AccountId = account("grantee", 0, 0);
+		// A new grant holds a deposit, which is the heavier path
+		T::Currency::make_free_balance_be(&granter, BalanceOf::<T>::max_value() / 2u32.into());
+		let expires_at = frame_system::Pallet::<T>::block_number() + 2u32.into();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(granter.clone()), grantee.clone(), GrantKind::Delegation, expires_at);
+
+		assert_eq!(
+			Grants::<T>::get((&granter, &grantee, GrantKind::Delegation)),
+			Some(Grant { expires_at })
+		);
+	}
+
+	#[benchmark]
+	fn revoke() {
+		let granter: T::AccountId = whitelisted_caller();
+		let grantee: T::AccountId = account("grantee", 0, 0);
+		T::Currency::make_free_balance_be(&granter, BalanceOf::<T>::max_value() / 2u32.into());
+		Pallet::<T>::grant(
+			RawOrigin::Signed(granter.clone()).into(),
+			grantee.clone(),
+			GrantKind::Delegation,
+			frame_system::Pallet::<T>::block_number() + 1u32.into(),
+		)
+		.expect("The grant expires in the future");
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(granter.clone()), grantee.clone(), GrantKind::Delegation);
+
+		assert!(!Grants::<T>::contains_key((&granter, &grantee, GrantKind::Delegation)));
+	}
+
+	#[benchmark]
+	fn set_call_filter_rule() -> Result<(), BenchmarkError> {
+		let origin =
+			T::CallFilterOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let selector = CallSelector::Call(0, 0);
+
+		#[extrinsic_call]
+		_(
+			origin as <T as frame_system::Config>::RuntimeOrigin,
+			selector,
+			Some(CallFilterRule::Deny),
+		);
+
+		assert_eq!(CallFilterOverrides::<T>::get(selector), Some(CallFilterRule::Deny));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn fund_mapped_account() -> Result<(), BenchmarkError> {
+		let public = sp_io::crypto::ecdsa_generate(KEY_TYPE, None);
+		let message_hash = [0u8; 32];
+		let signature = sp_io::crypto::ecdsa_sign_prehashed(KEY_TYPE, &public, &message_hash)
+			.expect("The key is in the keystore");
+		let public_key = Pallet::<T>::ecdsa_recover_public_key(&signature.0, &message_hash)
+			.expect("The signature is valid");
+		let caller: T::AccountId = whitelisted_caller();
+		let amount = <T::Currency as Currency<_>>::minimum_balance();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+
+		// Funding by the public key records the mapping, which is the heavier path
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), EvmIdentity::PublicKey(public_key), amount);
+
+		assert!(MappedAccounts::<T>::contains_key(evm_address_of(&public_key)));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn purge_nonce() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		// The nonces aren't purged unless the meta-transactions are mortal
+		if !T::MetaMortality::get() {
+			return Err(BenchmarkError::Skip)
+		}
+		let who: T::AccountId = account("who", 0, 0);
+		AccountNonce::<T>::insert(&who, 1);
+
+		#[extrinsic_call]
+		_(origin as <T as frame_system::Config>::RuntimeOrigin, who.clone());
+
+		assert!(!AccountNonce::<T>::contains_key(&who));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn permit() -> Result<(), BenchmarkError> {
+		let (public, owner) = mapped_signer::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		let spender: T::AccountId = account("spender", 0, 0);
+		let asset_id = T::PermitAssetId::decode(&mut TrailingZeroInput::zeroes())
+			.map_err(|_| BenchmarkError::Weightless)?;
+		let deadline = frame_system::Pallet::<T>::block_number() + 1u32.into();
+		let message_hash =
+			Pallet::<T>::permit_message_hash(&owner, &asset_id, &spender, 1, 0, deadline);
+		let signature = sp_io::crypto::ecdsa_sign_prehashed(KEY_TYPE, &public, &message_hash)
+			.expect("The key is in the keystore")
+			.0;
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller),
+			owner.clone(),
+			asset_id,
+			spender,
+			1,
+			0,
+			deadline,
+			signature.into(),
+		);
+
+		assert_eq!(PermitNonces::<T>::get(&owner), 1);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn request_controller_rotation() -> Result<(), BenchmarkError> {
+		let (public, who) = mapped_signer::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		let new_controller = EvmAddress::repeat_byte(1);
+		let nonce: u64 = 0;
+		let message_hash =
+			Pallet::<T>::rotate_controller_message_hash(&who, &new_controller, nonce);
+		let signature = sp_io::crypto::ecdsa_sign_prehashed(KEY_TYPE, &public, &message_hash)
+			.expect("The key is in the keystore")
+			.0;
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller),
+			who.clone(),
+			EvmIdentity::Address(new_controller),
+			nonce,
+			signature.into(),
+		);
+
+		assert!(ControllerRotations::<T>::contains_key(&who));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cancel_controller_rotation() {
+		let who: T::AccountId = whitelisted_caller();
+		ControllerRotations::<T>::insert(
+			&who,
+			ControllerRotation {
+				new_controller: EvmAddress::repeat_byte(1),
+				executable_at: frame_system::Pallet::<T>::block_number(),
+			},
+		);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(who.clone()));
+
+		assert!(!ControllerRotations::<T>::contains_key(&who));
+	}
+
+	#[benchmark]
+	fn execute_controller_rotation() {
+		let caller: T::AccountId = whitelisted_caller();
+		let who: T::AccountId = account("who", 0, 0);
+		let new_controller = EvmAddress::repeat_byte(1);
+		// Replacing a recorded mapping is the heavier path
+		EvmAddresses::<T>::insert(&who, EvmAddress::repeat_byte(2));
+		MappedAccounts::<T>::insert(EvmAddress::repeat_byte(2), &who);
+		ControllerRotations::<T>::insert(
+			&who,
+			ControllerRotation {
+				new_controller,
+				executable_at: frame_system::Pallet::<T>::block_number(),
+			},
+		);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), who.clone());
+
+		assert_eq!(Controllers::<T>::get(&who), Some(new_controller));
+	}
+
+	#[benchmark]
+	fn set_operational_call() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let selector = CallSelector::Call(0, 0);
+
+		#[extrinsic_call]
+		_(origin as <T as frame_system::Config>::RuntimeOrigin, selector, true);
+
+		assert!(OperationalCalls::<T>::contains_key(selector));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn meta_call_operational() -> Result<(), BenchmarkError> {
+		let origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let (public, who) = mapped_signer::<T>();
+		let call: <T as frame_system::Config>::RuntimeCall =
+			frame_system::Call::<T>::remark { remark: vec![] }.into();
+		let call_data = call.encode();
+		OperationalCalls::<T>::insert(CallSelector::Call(call_data[0], call_data[1]), ());
+		let nonce: u64 = 0;
+		let message_hash =
+			Pallet::<T>::meta_call_message_hash(PayloadVersion::V0, &who, &call_data, nonce, None);
+		let signature = sp_io::crypto::ecdsa_sign_prehashed(KEY_TYPE, &public, &message_hash)
+			.expect("The key is in the keystore")
+			.0;
+
+		T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 2u32.into());
+
+		#[extrinsic_call]
+		_(
+			origin as <T as frame_system::Config>::RuntimeOrigin,
+			who.clone(),
+			Box::new(call.into()),
+			nonce,
+			signature.into(),
+			None,
+			PayloadVersion::V0,
+		);
+
+		assert_eq!(AccountNonce::<T>::get(&who), 1);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn sponsor() -> Result<(), BenchmarkError> {
+		let sponsor: T::AccountId = whitelisted_caller();
+		let target = SponsorshipTarget::Call(CallSelector::Call(0, 0));
+		let budget = BalanceOf::<T>::max_value() / 2u32.into();
+		let expires_at = frame_system::Pallet::<T>::block_number() + 2u32.into();
+		// Replacing the expired sponsorship of another sponsor releases its deposit and holds a
+		// new one, which is the heavier path
+		let previous: T::AccountId = account("previous", 0, 0);
+		T::Currency::make_free_balance_be(&previous, BalanceOf::<T>::max_value() / 4u32.into());
+		T::Currency::make_free_balance_be(&sponsor, BalanceOf::<T>::max_value() / 4u32.into());
+		T::Currency::hold(
+			&HoldReason::SponsorshipDeposit.into(),
+			&previous,
+			Pallet::<T>::deposit_of(T::SponsorshipDeposit::get()),
+		)?;
+		Sponsorships::<T>::insert(
+			target,
+			Sponsorship {
+				sponsor: previous,
+				budget,
+				expires_at: frame_system::Pallet::<T>::block_number(),
+			},
+		);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(sponsor.clone()), target, budget, expires_at);
+
+		assert_eq!(
+			Sponsorships::<T>::get(target),
+			Some(Sponsorship { sponsor, budget, expires_at })
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn end_sponsorship() -> Result<(), BenchmarkError> {
+		let sponsor: T::AccountId = whitelisted_caller();
+		let target = SponsorshipTarget::Call(CallSelector::Call(0, 0));
+		T::Currency::make_free_balance_be(&sponsor, BalanceOf::<T>::max_value() / 2u32.into());
+		T::Currency::hold(
+			&HoldReason::SponsorshipDeposit.into(),
+			&sponsor,
+			Pallet::<T>::deposit_of(T::SponsorshipDeposit::get()),
+		)?;
+		Sponsorships::<T>::insert(
+			target,
+			Sponsorship {
+				sponsor: sponsor.clone(),
+				budget: BalanceOf::<T>::max_value() / 2u32.into(),
+				expires_at: frame_system::Pallet::<T>::block_number() + 2u32.into(),
+			},
+		);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(sponsor), target);
+
+		assert!(!Sponsorships::<T>::contains_key(target));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn meta_typed_call_unconvertible() {
+		let account: T::AccountId = whitelisted_caller();
+		let typed_call = TypedCall::Remark { remark: b"Hello".to_vec() };
+
+		// The work of `meta_typed_call` before the typed call fails to convert
+		#[block]
+		{
+			Pallet::<T>::typed_call_message_hash(&account, &typed_call, 0);
+			T::TypedCallConverter::convert(typed_call);
+		}
+	}
+
 	impl_benchmark_test_suite!(ThisPallet, crate::mock::new_test_ext(), crate::mock::Test);
 }
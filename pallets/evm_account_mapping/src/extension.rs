@@ -0,0 +1,143 @@
+// This file is part of EVM Account Mapping Pallet.
+
+// Copyright (C) HashForest Technology Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The `TransactionExtension` validating meta-transactions, for the polkadot-sdk releases which
+//! replace `SignedExtension` and deprecate `ValidateUnsigned`.
+
+use super::*;
+use frame_support::{
+	traits::IsSubType, CloneNoBound, DefaultNoBound, EqNoBound, PartialEqNoBound,
+	RuntimeDebugNoBound,
+};
+use sp_runtime::{
+	traits::{DispatchOriginOf, Implication, TransactionExtension, ValidateResult},
+	transaction_validity::{TransactionSource, ValidTransaction},
+};
+
+/// Validate the meta-transactions of the pallet sent as general transactions, the same way
+/// `validate_unsigned` and `pre_dispatch` do for the unsigned ones.
+///
+/// Unlike `pre_dispatch`, the validation doesn't write the storage: in a block the signer
+/// recovered by [`TransactionExtension::validate`] is passed to
+/// [`TransactionExtension::prepare`], which records it for the dispatch and counts it in the
+/// block. Other calls and the calls with another origin than `None` are left to the other
+/// extensions.
+///
+/// The dispatch errors of the meta-transactions are counted in `RejectionCounts` after the
+/// dispatch, which the failed dispatch itself can't do as its storage is reverted.
+#[derive(
+	Encode,
+	Decode,
+	CloneNoBound,
+	EqNoBound,
+	PartialEqNoBound,
+	DefaultNoBound,
+	RuntimeDebugNoBound,
+	TypeInfo,
+)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckMetaTransaction<T>(PhantomData<T>);
+
+impl<T> CheckMetaTransaction<T> {
+	/// Create a new extension, it has no state.
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T> TransactionExtension<<T as frame_system::Config>::RuntimeCall> for CheckMetaTransaction<T>
+where
+	T: Config + frame_system::Config<AccountId = AccountId32> + Send + Sync,
+	PaymentBalanceOf<T>: FixedPointOperand,
+	BalanceOf<T>: FixedPointOperand,
+	<T as frame_system::Config>::RuntimeCall:
+		Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo> + IsSubType<Call<T>>,
+{
+	const IDENTIFIER: &'static str = "CheckMetaTransaction";
+	type Implicit = ();
+	type Val =
+		Option<(Keccak256Signature, EIP712Signature, EvmAddress, EIP712VerifyingContractAddress)>;
+	/// Whether the transaction is a meta-transaction.
+	type Pre = bool;
+
+	fn weight(&self, call: &<T as frame_system::Config>::RuntimeCall) -> Weight {
+		// The signature recovery is accounted in the weight of the meta-transaction calls, only
+		// removing the signer and counting a dispatch error are left
+		if call.is_sub_type().is_some() {
+			T::DbWeight::get().reads_writes(1, 2)
+		} else {
+			Weight::zero()
+		}
+	}
+
+	fn validate(
+		&self,
+		origin: DispatchOriginOf<<T as frame_system::Config>::RuntimeCall>,
+		call: &<T as frame_system::Config>::RuntimeCall,
+		_info: &DispatchInfoOf<<T as frame_system::Config>::RuntimeCall>,
+		_len: usize,
+		_self_implicit: Self::Implicit,
+		_inherited_implication: &impl Implication,
+		source: TransactionSource,
+	) -> ValidateResult<Self::Val, <T as frame_system::Config>::RuntimeCall> {
+		let Some(meta_call) = call.is_sub_type() else {
+			return Ok((ValidTransaction::default(), None, origin))
+		};
+		// The meta-transaction calls are dispatched with the `None` origin
+		if frame_system::ensure_none(origin.clone()).is_err() {
+			return Ok((ValidTransaction::default(), None, origin))
+		}
+
+		let checked = match source {
+			TransactionSource::InBlock => Pallet::<T>::check_inclusion(meta_call)?,
+			_ => Pallet::<T>::check_unsigned(meta_call)?,
+		};
+		let signer = checked.pre_dispatched_signer();
+		Ok((Pallet::<T>::valid_transaction(checked), Some(signer), origin))
+	}
+
+	fn prepare(
+		self,
+		val: Self::Val,
+		_origin: &DispatchOriginOf<<T as frame_system::Config>::RuntimeCall>,
+		_call: &<T as frame_system::Config>::RuntimeCall,
+		_info: &DispatchInfoOf<<T as frame_system::Config>::RuntimeCall>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		let Some(signer) = val else { return Ok(false) };
+		Pallet::<T>::note_pre_dispatched(signer);
+		Ok(true)
+	}
+
+	fn post_dispatch_details(
+		pre: Self::Pre,
+		_info: &DispatchInfoOf<<T as frame_system::Config>::RuntimeCall>,
+		_post_info: &PostDispatchInfoOf<<T as frame_system::Config>::RuntimeCall>,
+		_len: usize,
+		result: &DispatchResult,
+	) -> Result<Weight, TransactionValidityError> {
+		if !pre {
+			return Ok(Weight::zero())
+		}
+		// A failed dispatch reverts the take of the signer
+		PreDispatchedSigner::<T>::kill();
+		if let Err(error) = result {
+			Pallet::<T>::note_dispatch_error(error);
+		}
+		Ok(Weight::zero())
+	}
+}
@@ -19,8 +19,12 @@
 
 pub use pallet::*;
 
-mod eip712;
-mod encode;
+pub use substrate_eip712 as eip712;
+pub mod migrations;
+#[cfg(feature = "std")]
+pub mod test_utils;
+#[cfg(feature = "std")]
+pub mod test_vectors;
 
 #[cfg(test)]
 mod mock;
@@ -30,6 +34,10 @@ mod tests;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+#[cfg(feature = "tx-extension")]
+pub mod extension;
+#[cfg(feature = "tx-extension")]
+pub use extension::CheckMetaTransaction;
 pub mod weights;
 pub use weights::WeightInfo;
 
@@ -47,32 +55,84 @@ macro_rules! log {
 	};
 }
 
-use codec::Decode;
-use frame_support::{dispatch::{DispatchInfo, GetDispatchInfo, PostDispatchInfo, RawOrigin}, Parameter, traits::{
+use codec::{Decode, DecodeLimit, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use frame_support::{dispatch::{DispatchClass, DispatchInfo, GetDispatchInfo, PostDispatchInfo, RawOrigin}, Parameter, traits::{
 	tokens::{Fortitude, Preservation},
-	fungible::Inspect as InspectFungible,
-	Contains, Imbalance, OriginTrait,
+	fungible::{Inspect as InspectFungible, MutateHold},
+	Contains, EnsureOrigin, ExistenceRequirement, Imbalance, InstanceFilter, OriginTrait,
 	Currency,
 }, weights::Weight};
 use pallet_transaction_payment::OnChargeTransaction;
 use sp_core::crypto::AccountId32;
 use sp_io::hashing::blake2_256;
-use sp_runtime::{traits::Dispatchable, FixedPointOperand};
+use sp_runtime::{
+	traits::{
+		Convert, DispatchInfoOf, Dispatchable, PostDispatchInfoOf, Saturating, TrailingZeroInput,
+		Zero,
+	},
+	transaction_validity::{TransactionLongevity, TransactionPriority, TransactionValidityError},
+	DispatchError, DispatchResult, FixedPointOperand, RuntimeDebug, SaturatedConversion,
+};
+use sp_std::{marker::PhantomData, vec::Vec};
+use substrate_eip712::encode::abi::{self, Token};
 
 type PaymentOnChargeTransaction<T> = <T as pallet_transaction_payment::Config>::OnChargeTransaction;
 
 type PaymentBalanceOf<T> = <<T as pallet_transaction_payment::Config>::OnChargeTransaction as OnChargeTransaction<T>>::Balance;
 
 type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type TypedCallOf<T> = TypedCall<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+pub type AssetTipOf<T> = AssetTip<<T as Config>::TipAssetId>;
 type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
 pub type EIP712ChainID = sp_core::U256;
 pub type EIP712VerifyingContractAddress = sp_core::H160;
 pub type EIP712Signature = [u8; 65];
+pub type EvmAddress = sp_core::H160;
 
 pub type Nonce = u64;
 pub type AccountId32Bytes = [u8; 32];
 pub type Keccak256Signature = [u8; 32];
 
+// The verifying contract of the EIP-712 domain the payloads are hashed within, when it's not
+// `EIP712VerifyingContractAddress`, see `Pallet::with_verifying_contract`.
+environmental::environmental!(verifying_contract: EIP712VerifyingContractAddress);
+
+/// Custom error codes carried by `InvalidTransaction::Custom` when `validate_unsigned` rejects a
+/// meta-transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum InvalidMetaTransaction {
+	/// The signer's EVM address is in the denylist.
+	AddressBlocked = 1,
+	/// The account has used up its meta-transactions for the current rate limit window.
+	RateLimited = 2,
+	/// The dispatch class of the call is not allowed for meta-transactions.
+	DispatchClassNotAllowed = 3,
+	/// The call is nested deeper than `MaxCallDepth`.
+	CallTooDeep = 4,
+	/// The call, or a call it wraps, is not allowed by `CallFilter`.
+	CallFiltered = 5,
+	/// The account isn't linked to a native account.
+	NotLinked = 6,
+	/// The signature isn't in the canonical form, see [`is_canonical_signature`].
+	MalleableSignature = 7,
+	/// The meta-transaction designates a relayer other than the author of the block.
+	NotDesignatedRelayer = 8,
+	/// The validity window of the meta-transaction has passed.
+	Expired = 9,
+	/// The weight of the call exceeds the weight limit signed by the signer.
+	WeightLimitExceeded = 10,
+	/// The fee of the meta-transaction exceeds the maximum fee signed by the signer.
+	FeeLimitExceeded = 11,
+}
+
+impl From<InvalidMetaTransaction> for sp_runtime::transaction_validity::TransactionValidityError {
+	fn from(error: InvalidMetaTransaction) -> Self {
+		sp_runtime::transaction_validity::InvalidTransaction::Custom(error as u8).into()
+	}
+}
+
 pub enum Secp256K1PublicKeyForm {
 	Compressed,
 	Uncompressed,
@@ -82,6 +142,1056 @@ pub trait AddressConversion<AccountId>: Sized {
 	const SECP256K1_PUBLIC_KEY_FORM: Secp256K1PublicKeyForm;
 
 	fn try_convert(evm_public_key: &[u8]) -> Option<AccountId>;
+
+	/// Convert an EVM address without its public key, which is only possible if the account is
+	/// derived from the address itself.
+	fn try_convert_evm_address(_evm_address: &EvmAddress) -> Option<AccountId> {
+		None
+	}
+}
+
+/// Derive the EVM address from an uncompressed secp256k1 public key (without the `0x04` prefix).
+pub fn evm_address_of(public_key: &[u8; 64]) -> EvmAddress {
+	sp_core::H160::from(sp_core::H256(sp_io::hashing::keccak_256(public_key)))
+}
+
+/// Half of the order of the secp256k1 curve, the `s` value of a signature must not exceed it.
+pub const SECP256K1_HALF_ORDER: [u8; 32] = [
+	0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+	0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
+
+/// Whether `signature` is in the canonical form, i.e. its `s` value is in the lower half of the
+/// curve order and its recovery id is one of `0`, `1`, `27` and `28`.
+///
+/// Every signature has a second valid encoding with `s` negated, accepting only one of them
+/// prevents a third party from re-submitting a modified but valid signature.
+pub fn is_canonical_signature(signature: &EIP712Signature) -> bool {
+	signature[32..64] <= SECP256K1_HALF_ORDER[..] && matches!(signature[64], 0 | 1 | 27 | 28)
+}
+
+/// A secp256k1 signature of a meta-transaction, in either of the encodings wallets produce.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum Secp256K1Signature {
+	/// `r ‖ s ‖ v`, 65 bytes.
+	#[codec(index = 0)]
+	Standard(EIP712Signature),
+	/// The ERC-2098 compact `r ‖ yParityAndS`, 64 bytes, the recovery id is folded into the
+	/// highest bit of `s`.
+	#[codec(index = 1)]
+	Compact([u8; 64]),
+}
+
+impl Secp256K1Signature {
+	/// The 65-byte encoding of the signature, which is recovered from.
+	pub fn normalize(&self) -> EIP712Signature {
+		match self {
+			Self::Standard(signature) => *signature,
+			Self::Compact(compact) => {
+				let mut signature = [0u8; 65];
+				signature[..64].copy_from_slice(compact);
+				signature[32] &= 0x7f;
+				signature[64] = compact[32] >> 7;
+				signature
+			},
+		}
+	}
+}
+
+impl From<EIP712Signature> for Secp256K1Signature {
+	fn from(signature: EIP712Signature) -> Self {
+		Self::Standard(signature)
+	}
+}
+
+impl TryFrom<&[u8]> for Secp256K1Signature {
+	type Error = ();
+
+	/// Either encoding by its length, 65 or 64 bytes.
+	fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+		match bytes.len() {
+			65 => bytes.try_into().map(Self::Standard).map_err(|_| ()),
+			64 => bytes.try_into().map(Self::Compact).map_err(|_| ()),
+			_ => Err(()),
+		}
+	}
+}
+
+/// The signature of a meta-transaction, its scheme determines what the wallet signed.
+///
+/// Every scheme signs the EIP-712 hash of the payload, see [`MetaSignature::signed_digest`], so
+/// that a new scheme only needs a variant here and its verifier.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum MetaSignature {
+	/// An EIP-712 `eth_signTypedData_v4` signature.
+	#[codec(index = 0)]
+	Eip712Secp256k1(Secp256K1Signature),
+	/// An EIP-191 `personal_sign` signature of the 32-byte EIP-712 hash, for the wallets which
+	/// can't sign typed data.
+	#[codec(index = 1)]
+	Eip191Secp256k1(Secp256K1Signature),
+}
+
+impl MetaSignature {
+	/// The digest the signature is recovered from, for the EIP-712 `message_hash` of a payload.
+	pub fn signed_digest(&self, message_hash: &Keccak256Signature) -> Keccak256Signature {
+		match self {
+			Self::Eip712Secp256k1(_) => *message_hash,
+			Self::Eip191Secp256k1(_) => {
+				let mut message = b"\x19Ethereum Signed Message:\n32".to_vec();
+				message.extend_from_slice(message_hash);
+				sp_io::hashing::keccak_256(&message)
+			},
+		}
+	}
+
+	/// The 65-byte secp256k1 signature, which is recovered from.
+	pub fn normalize(&self) -> EIP712Signature {
+		match self {
+			Self::Eip712Secp256k1(signature) | Self::Eip191Secp256k1(signature) =>
+				signature.normalize(),
+		}
+	}
+}
+
+impl From<Secp256K1Signature> for MetaSignature {
+	fn from(signature: Secp256K1Signature) -> Self {
+		Self::Eip712Secp256k1(signature)
+	}
+}
+
+impl From<EIP712Signature> for MetaSignature {
+	fn from(signature: EIP712Signature) -> Self {
+		Self::Eip712Secp256k1(signature.into())
+	}
+}
+
+/// Serialize an uncompressed secp256k1 public key (without the `0x04` prefix) into `form`.
+pub fn encode_public_key(public_key: &[u8; 64], form: &Secp256K1PublicKeyForm) -> Vec<u8> {
+	match form {
+		Secp256K1PublicKeyForm::Compressed => {
+			let prefix = if public_key[63] & 1 == 0 { 0x02 } else { 0x03 };
+			let mut encoded = Vec::with_capacity(33);
+			encoded.push(prefix);
+			encoded.extend_from_slice(&public_key[..32]);
+			encoded
+		},
+		Secp256K1PublicKeyForm::Uncompressed => {
+			let mut encoded = Vec::with_capacity(65);
+			encoded.push(0x04);
+			encoded.extend_from_slice(public_key);
+			encoded
+		},
+	}
+}
+
+/// Adjust the transaction fee charged for a meta-transaction.
+pub trait AdjustFee<Call, Balance> {
+	/// Return the fee to charge for `call`, `fee` is the computed fee including the tip.
+	fn adjust_fee(call: &Call, fee: Balance) -> Balance;
+}
+
+impl<Call, Balance> AdjustFee<Call, Balance> for () {
+	fn adjust_fee(_call: &Call, fee: Balance) -> Balance {
+		fee
+	}
+}
+
+/// Expose the calls a call dispatches on behalf of its origin, e.g. the calls of a
+/// `utility::batch` or a `proxy::proxy`.
+pub trait InspectInnerCalls<Call> {
+	/// The calls wrapped by `call`, empty if it doesn't wrap any.
+	fn inner_calls(call: &Call) -> Vec<&Call>;
+}
+
+impl<Call> InspectInnerCalls<Call> for () {
+	fn inner_calls(_call: &Call) -> Vec<&Call> {
+		Vec::new()
+	}
+}
+
+/// Settle the tips of meta-transactions which are paid in an asset instead of the native token.
+pub trait TipHandler<AccountId, AssetId> {
+	/// Whether `who` is able to pay a tip of `amount` in `asset_id`.
+	fn can_pay(who: &AccountId, asset_id: &AssetId, amount: u128) -> bool;
+
+	/// Pay a tip of `amount` in `asset_id` from `who`, e.g. transfer it to the block author or
+	/// convert it to the native token.
+	fn pay(who: &AccountId, asset_id: &AssetId, amount: u128) -> DispatchResult;
+
+	/// The weight of [`TipHandler::pay`].
+	fn weight() -> Weight;
+}
+
+/// Asset tips are not supported.
+impl<AccountId, AssetId> TipHandler<AccountId, AssetId> for () {
+	fn can_pay(_who: &AccountId, _asset_id: &AssetId, _amount: u128) -> bool {
+		false
+	}
+
+	fn pay(_who: &AccountId, _asset_id: &AssetId, _amount: u128) -> DispatchResult {
+		Err(DispatchError::Other("Asset tips are not supported"))
+	}
+
+	fn weight() -> Weight {
+		Weight::zero()
+	}
+}
+
+/// Approve a spender of the assets of an account for the permits, e.g. with `pallet_assets`.
+pub trait AssetApprovals<AccountId, AssetId> {
+	/// Approve `spender` to transfer `amount` of `asset_id` from `owner`.
+	fn approve(
+		asset_id: &AssetId,
+		owner: &AccountId,
+		spender: &AccountId,
+		amount: u128,
+	) -> DispatchResult;
+
+	/// The weight of [`AssetApprovals::approve`].
+	fn weight() -> Weight;
+}
+
+/// Permits are not supported.
+impl<AccountId, AssetId> AssetApprovals<AccountId, AssetId> for () {
+	fn approve(
+		_asset_id: &AssetId,
+		_owner: &AccountId,
+		_spender: &AccountId,
+		_amount: u128,
+	) -> DispatchResult {
+		Err(DispatchError::Other("Permits are not supported"))
+	}
+
+	fn weight() -> Weight {
+		Weight::zero()
+	}
+}
+
+/// Approve through the `fungibles` approvals of `F`, which `pallet_assets` implements, `W` is
+/// the weight of an approval.
+pub struct FungiblesApprovals<F, W>(PhantomData<(F, W)>);
+
+impl<AccountId, F, W> AssetApprovals<AccountId, F::AssetId> for FungiblesApprovals<F, W>
+where
+	F: frame_support::traits::fungibles::approvals::Mutate<AccountId>,
+	W: frame_support::traits::Get<Weight>,
+{
+	fn approve(
+		asset_id: &F::AssetId,
+		owner: &AccountId,
+		spender: &AccountId,
+		amount: u128,
+	) -> DispatchResult {
+		F::approve(asset_id.clone(), owner, spender, amount.saturated_into())
+	}
+
+	fn weight() -> Weight {
+		W::get()
+	}
+}
+
+/// A tip of `amount` in the asset `asset_id`, the amount is in the smallest unit of the asset.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct AssetTip<AssetId> {
+	pub asset_id: AssetId,
+	pub amount: u128,
+}
+
+/// Withdraw the transaction fee of a meta-transaction before its execution and settle it after,
+/// the meta-transaction counterpart of `pallet_transaction_payment::OnChargeTransaction`.
+///
+/// The fee is computed by `pallet_transaction_payment` and adjusted by `FeeAdjustment`, the
+/// implementation decides where it is taken from and where it goes.
+pub trait OnChargeMetaTransaction<T: pallet_transaction_payment::Config> {
+	/// What is withdrawn before the execution and settled after it.
+	type LiquidityInfo;
+
+	/// Withdraw the estimated `fee`, including the `tip`, of a meta-transaction of `who`.
+	fn withdraw_fee(
+		who: &T::AccountId,
+		call: &<T as frame_system::Config>::RuntimeCall,
+		info: &DispatchInfoOf<<T as frame_system::Config>::RuntimeCall>,
+		fee: PaymentBalanceOf<T>,
+		tip: PaymentBalanceOf<T>,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError>;
+
+	/// Refund the difference between the withdrawn fee and the `corrected_fee`, then deposit the
+	/// fee and the tip.
+	fn correct_and_deposit_fee(
+		who: &T::AccountId,
+		info: &DispatchInfoOf<<T as frame_system::Config>::RuntimeCall>,
+		post_info: &PostDispatchInfoOf<<T as frame_system::Config>::RuntimeCall>,
+		corrected_fee: PaymentBalanceOf<T>,
+		tip: PaymentBalanceOf<T>,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError>;
+}
+
+/// Charge meta-transactions the same way as native transactions, through the
+/// `OnChargeTransaction` of `pallet_transaction_payment`.
+pub struct TransactionPaymentAdapter;
+
+impl<T: pallet_transaction_payment::Config> OnChargeMetaTransaction<T>
+	for TransactionPaymentAdapter
+{
+	type LiquidityInfo = <PaymentOnChargeTransaction<T> as OnChargeTransaction<T>>::LiquidityInfo;
+
+	fn withdraw_fee(
+		who: &T::AccountId,
+		call: &<T as frame_system::Config>::RuntimeCall,
+		info: &DispatchInfoOf<<T as frame_system::Config>::RuntimeCall>,
+		fee: PaymentBalanceOf<T>,
+		tip: PaymentBalanceOf<T>,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		<PaymentOnChargeTransaction<T> as OnChargeTransaction<T>>::withdraw_fee(
+			who, call, info, fee, tip,
+		)
+	}
+
+	fn correct_and_deposit_fee(
+		who: &T::AccountId,
+		info: &DispatchInfoOf<<T as frame_system::Config>::RuntimeCall>,
+		post_info: &PostDispatchInfoOf<<T as frame_system::Config>::RuntimeCall>,
+		corrected_fee: PaymentBalanceOf<T>,
+		tip: PaymentBalanceOf<T>,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		<PaymentOnChargeTransaction<T> as OnChargeTransaction<T>>::correct_and_deposit_fee(
+			who,
+			info,
+			post_info,
+			corrected_fee,
+			tip,
+			already_withdrawn,
+		)
+	}
+}
+
+/// Charge meta-transactions by holding the estimated fee of `F` for
+/// [`HoldReason::TransactionFee`] during the execution, then taking the actual fee from the hold
+/// and releasing the rest. `OU` receives the fee and then the tip.
+///
+/// The fee stays with the account until it is settled, but the call can't spend it nor reap the
+/// account.
+pub struct HoldingFeeAdapter<F, OU>(PhantomData<(F, OU)>);
+
+impl<T, F, OU> OnChargeMetaTransaction<T> for HoldingFeeAdapter<F, OU>
+where
+	T: Config,
+	F: MutateHold<T::AccountId, Balance = PaymentBalanceOf<T>>
+		+ frame_support::traits::fungible::BalancedHold<T::AccountId>,
+	F::Reason: From<HoldReason>,
+	OU: frame_support::traits::OnUnbalanced<
+		frame_support::traits::fungible::Credit<T::AccountId, F>,
+	>,
+{
+	type LiquidityInfo = PaymentBalanceOf<T>;
+
+	fn withdraw_fee(
+		who: &T::AccountId,
+		_call: &<T as frame_system::Config>::RuntimeCall,
+		_info: &DispatchInfoOf<<T as frame_system::Config>::RuntimeCall>,
+		fee: PaymentBalanceOf<T>,
+		_tip: PaymentBalanceOf<T>,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		if !fee.is_zero() {
+			F::hold(&HoldReason::TransactionFee.into(), who, fee)
+				.map_err(|_| sp_runtime::transaction_validity::InvalidTransaction::Payment)?;
+		}
+		Ok(fee)
+	}
+
+	fn correct_and_deposit_fee(
+		who: &T::AccountId,
+		_info: &DispatchInfoOf<<T as frame_system::Config>::RuntimeCall>,
+		_post_info: &PostDispatchInfoOf<<T as frame_system::Config>::RuntimeCall>,
+		corrected_fee: PaymentBalanceOf<T>,
+		tip: PaymentBalanceOf<T>,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		use frame_support::traits::tokens::Precision;
+
+		let reason = HoldReason::TransactionFee.into();
+		let (credit, _) = F::slash(&reason, who, corrected_fee.min(already_withdrawn));
+		let refund = already_withdrawn.saturating_sub(credit.peek());
+		if !refund.is_zero() {
+			F::release(&reason, who, refund, Precision::BestEffort)
+				.map_err(|_| sp_runtime::transaction_validity::InvalidTransaction::Payment)?;
+		}
+		let (tip, fee) = credit.split(tip);
+		OU::on_unbalanceds(Some(fee).into_iter().chain(Some(tip)));
+		Ok(())
+	}
+}
+
+/// Calculate the priority of a meta-transaction in the transaction pool.
+pub trait PriorityPolicy<Balance> {
+	/// The priority of a meta-transaction dispatching a call of `info` and encoded length `len`,
+	/// paying `tip`.
+	fn priority(info: &DispatchInfo, len: usize, tip: Balance) -> TransactionPriority;
+}
+
+/// The priority `pallet-transaction-payment` gives to a signed transaction of the same call and
+/// tip.
+pub struct DefaultPriority<T>(PhantomData<T>);
+
+impl<T: Config> PriorityPolicy<PaymentBalanceOf<T>> for DefaultPriority<T> {
+	fn priority(info: &DispatchInfo, len: usize, tip: PaymentBalanceOf<T>) -> TransactionPriority {
+		// Cheat from `get_priority` in frame/transaction-payment/src/lib.rs
+		use frame_support::traits::{Defensive, Get};
+		use sp_runtime::traits::One;
+		// Calculate how many such extrinsics we could fit into an empty block and take the
+		// limiting factor.
+		let max_block_weight = <T as frame_system::Config>::BlockWeights::get().max_block;
+		let max_block_length =
+			*<T as frame_system::Config>::BlockLength::get().max.get(info.class) as u64;
+
+		// bounded_weight is used as a divisor later so we keep it non-zero.
+		let bounded_weight = info.weight.max(Weight::from_parts(1, 1)).min(max_block_weight);
+		let bounded_length = (len as u64).clamp(1, max_block_length);
+
+		// returns the scarce resource, i.e. the one that is limiting the number of
+		// transactions.
+		let max_tx_per_block_weight = max_block_weight
+			.checked_div_per_component(&bounded_weight)
+			.defensive_proof("bounded_weight is non-zero; qed")
+			.unwrap_or(1);
+		let max_tx_per_block_length = max_block_length / bounded_length;
+		// Given our current knowledge this value is going to be in a reasonable range - i.e.
+		// less than 10^9 (2^30), so multiplying by the `tip` value is unlikely to overflow the
+		// balance type. We still use saturating ops obviously, but the point is to end up with
+		// some `priority` distribution instead of having all transactions saturate the
+		// priority.
+		let max_tx_per_block = max_tx_per_block_length
+			.min(max_tx_per_block_weight)
+			.saturated_into::<PaymentBalanceOf<T>>();
+		let max_reward = |val: PaymentBalanceOf<T>| val.saturating_mul(max_tx_per_block);
+
+		// To distribute no-tip transactions a little bit, we increase the tip value by one.
+		// This means that given two transactions without a tip, smaller one will be preferred.
+		let tip = tip.saturating_add(One::one());
+		let scaled_tip = max_reward(tip);
+
+		scaled_tip.saturated_into::<TransactionPriority>()
+	}
+}
+
+/// The transaction pool entry of a meta-transaction as decided by [`ReplayProtection::check`].
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ReplayTags {
+	/// The tag the meta-transaction provides.
+	pub provides: Vec<u8>,
+	/// The tag the meta-transaction requires, if any.
+	pub requires: Option<Vec<u8>>,
+	/// The number of blocks the meta-transaction stays valid in the transaction pool.
+	pub longevity: TransactionLongevity,
+}
+
+/// Protect the meta-transactions against replays with the `nonce` of their signed payloads.
+///
+/// Only the meta-transactions are protected by it, the links and the controller rotations keep
+/// the sequential nonce of the account.
+pub trait ReplayProtection<AccountId, BlockNumber> {
+	/// Check the meta-transaction of `who` signed with `nonce` may be executed, `message_hash`
+	/// computes the hash of its signed payload.
+	///
+	/// The check runs before the signature recovery, the strategies which don't need the hash
+	/// shouldn't compute it.
+	fn check(
+		who: &AccountId,
+		nonce: Nonce,
+		message_hash: impl Fn() -> Keccak256Signature,
+	) -> Result<ReplayTags, sp_runtime::transaction_validity::InvalidTransaction>;
+
+	/// Use up `nonce` of `who` at the dispatch, `false` if the meta-transaction may not be
+	/// executed.
+	fn consume(who: &AccountId, nonce: Nonce, message_hash: &Keccak256Signature) -> bool;
+
+	/// Forget the meta-transactions which can't be executed anymore from block `n` on, called at
+	/// the start of the block.
+	fn prune(n: BlockNumber) -> Weight;
+}
+
+/// The sequential nonces of the accounts, like the nonces of the signed transactions: a
+/// meta-transaction uses the next nonce of its account. The ones up to `MaxNonceGap` ahead wait
+/// in the transaction pool for the ones before them.
+pub struct SequentialNonces<T>(PhantomData<T>);
+
+impl<T: Config> ReplayProtection<T::AccountId, frame_system::pallet_prelude::BlockNumberFor<T>>
+	for SequentialNonces<T>
+{
+	fn check(
+		who: &T::AccountId,
+		nonce: Nonce,
+		_message_hash: impl Fn() -> Keccak256Signature,
+	) -> Result<ReplayTags, sp_runtime::transaction_validity::InvalidTransaction> {
+		use frame_support::traits::Get;
+		use sp_runtime::transaction_validity::InvalidTransaction;
+
+		// The future nonces within the gap wait in the pool for the tag of the previous one
+		let account_nonce = AccountNonce::<T>::get(who);
+		if nonce < account_nonce {
+			return Err(InvalidTransaction::Stale)
+		}
+		if nonce > account_nonce.saturating_add(T::MaxNonceGap::get()) {
+			return Err(InvalidTransaction::Future)
+		}
+		Ok(ReplayTags {
+			provides: (who, nonce).encode(),
+			requires: if account_nonce < nonce { Some((who, nonce - 1).encode()) } else { None },
+			longevity: T::UnsignedLongevity::get(),
+		})
+	}
+
+	fn consume(who: &T::AccountId, nonce: Nonce, _message_hash: &Keccak256Signature) -> bool {
+		AccountNonce::<T>::try_mutate(who, |value| {
+			if *value != nonce {
+				return Err(())
+			}
+			*value += 1;
+			Ok(())
+		})
+		.is_ok()
+	}
+
+	fn prune(_n: frame_system::pallet_prelude::BlockNumberFor<T>) -> Weight {
+		Weight::zero()
+	}
+}
+
+/// Remember the hashes of the executed payloads instead of ordering them, so that the
+/// meta-transactions of an account are executed in any order.
+///
+/// The `nonce` of a payload is the last block it may be executed in, at most `Window` blocks
+/// ahead, and its hash is kept until then. The payloads of an account only need distinct
+/// nonces if they're otherwise identical, but the receipts of the meta-transactions sharing a
+/// nonce overwrite each other.
+pub struct WindowedPayloadHashes<T, Window>(PhantomData<(T, Window)>);
+
+impl<T, Window> ReplayProtection<T::AccountId, frame_system::pallet_prelude::BlockNumberFor<T>>
+	for WindowedPayloadHashes<T, Window>
+where
+	T: Config,
+	Window: frame_support::traits::Get<frame_system::pallet_prelude::BlockNumberFor<T>>,
+{
+	fn check(
+		_who: &T::AccountId,
+		nonce: Nonce,
+		message_hash: impl Fn() -> Keccak256Signature,
+	) -> Result<ReplayTags, sp_runtime::transaction_validity::InvalidTransaction> {
+		use frame_support::traits::Get;
+		use sp_runtime::transaction_validity::InvalidTransaction;
+
+		let now = frame_system::Pallet::<T>::block_number().saturated_into::<Nonce>();
+		if nonce < now {
+			return Err(InvalidTransaction::Stale)
+		}
+		if nonce > now.saturating_add(Window::get().saturated_into()) {
+			return Err(InvalidTransaction::Future)
+		}
+		let message_hash = message_hash();
+		if ExecutedPayloads::<T>::contains_key(Self::expiry(nonce), message_hash) {
+			return Err(InvalidTransaction::Stale)
+		}
+		Ok(ReplayTags {
+			provides: message_hash.encode(),
+			requires: None,
+			// Leave the transaction pool once the payload has expired
+			longevity: T::UnsignedLongevity::get().min(nonce - now + 1),
+		})
+	}
+
+	fn consume(_who: &T::AccountId, nonce: Nonce, message_hash: &Keccak256Signature) -> bool {
+		let now = frame_system::Pallet::<T>::block_number().saturated_into::<Nonce>();
+		let expiry = Self::expiry(nonce);
+		if nonce < now || ExecutedPayloads::<T>::contains_key(expiry, message_hash) {
+			return false
+		}
+		ExecutedPayloads::<T>::insert(expiry, message_hash, ());
+		true
+	}
+
+	fn prune(n: frame_system::pallet_prelude::BlockNumberFor<T>) -> Weight {
+		use frame_support::traits::Get;
+
+		let pruned = ExecutedPayloads::<T>::drain_prefix(n).count() as u64;
+		T::DbWeight::get().reads_writes(pruned.saturating_add(1), pruned)
+	}
+}
+
+impl<T: Config, Window> WindowedPayloadHashes<T, Window> {
+	/// The block the hash of a payload signed with `nonce` is forgotten at.
+	fn expiry(nonce: Nonce) -> frame_system::pallet_prelude::BlockNumberFor<T> {
+		nonce.saturating_add(1).saturated_into()
+	}
+}
+
+/// The outcome of an executed meta-transaction.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Receipt<BlockNumber, Balance> {
+	/// The block the meta-transaction was executed in.
+	pub block_number: BlockNumber,
+	/// Whether the inner call succeeded.
+	pub success: bool,
+	/// The error of the inner call if it failed.
+	pub error: Option<sp_runtime::DispatchError>,
+	/// The transaction fee actually paid, including the tip and excluding the service fee.
+	pub actual_fee: Balance,
+}
+
+pub type ReceiptOf<T> =
+	Receipt<frame_system::pallet_prelude::BlockNumberFor<T>, PaymentBalanceOf<T>>;
+
+/// The prefix of the off-chain index keys of executed meta-transactions.
+pub const OFFCHAIN_INDEX_PREFIX: &[u8] = b"evm_account_mapping::meta_tx";
+
+/// The off-chain index key of the meta-transaction signed by `evm_address` with `nonce`.
+pub fn offchain_index_key(evm_address: &EvmAddress, nonce: Nonce) -> Vec<u8> {
+	(OFFCHAIN_INDEX_PREFIX, evm_address, nonce).encode()
+}
+
+/// The off-chain index entry of an executed meta-transaction.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct IndexedMetaTransaction<AccountId, BlockNumber> {
+	/// The account the meta-transaction was executed for.
+	pub who: AccountId,
+	/// The block the meta-transaction was executed in.
+	pub block_number: BlockNumber,
+	/// The index of the extrinsic within the block.
+	pub extrinsic_index: u32,
+	/// Whether the inner call succeeded.
+	pub success: bool,
+	/// The error of the inner call if it failed.
+	pub error: Option<sp_runtime::DispatchError>,
+}
+
+/// The EIP-712 type of the `meta_call` payload of [`PayloadVersion::V0`].
+pub const SUBSTRATE_CALL_TYPE: &[u8] = b"SubstrateCall(string who,bytes callData,uint64 nonce)";
+
+/// The EIP-712 type of the `meta_call` payload of [`PayloadVersion::V1`].
+pub const SUBSTRATE_CALL_V1_TYPE: &[u8] =
+	b"SubstrateCall(uint8 version,string who,bytes callData,uint64 nonce,uint128 tip)";
+
+/// The version of the signed `meta_call` payload.
+///
+/// The version is an argument of `meta_call` and a member of the signed struct since
+/// [`PayloadVersion::V1`], so that wallets signing different versions of the payload are served
+/// at the same time.
+#[derive(
+	Clone, Copy, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub enum PayloadVersion {
+	/// [`SUBSTRATE_CALL_TYPE`], the tip is not signed.
+	#[default]
+	#[codec(index = 0)]
+	V0,
+	/// [`SUBSTRATE_CALL_V1_TYPE`], the tip is signed, `None` is signed as zero.
+	#[codec(index = 1)]
+	V1,
+}
+
+/// The EIP-712 type of the `meta_call_with_asset_tip` payload, `tipAssetId` is the SCALE encoded
+/// asset id.
+pub const SUBSTRATE_CALL_WITH_ASSET_TIP_TYPE: &[u8] =
+	b"SubstrateCallWithAssetTip(string who,bytes callData,uint64 nonce,bytes tipAssetId,uint128 tipAmount)";
+
+/// The EIP-712 type of the `meta_call_with_proxy_type` payload, `proxyType` is the SCALE encoded
+/// proxy type.
+pub const SUBSTRATE_CALL_WITH_PROXY_TYPE_TYPE: &[u8] =
+	b"SubstrateCallWithProxyType(string who,bytes callData,uint64 nonce,uint128 tip,bytes proxyType)";
+
+/// The EIP-712 type of the `meta_call_as_sub_account` payload.
+pub const SUBSTRATE_CALL_AS_SUB_ACCOUNT_TYPE: &[u8] =
+	b"SubstrateCallAsSubAccount(string who,bytes callData,uint64 nonce,uint128 tip,uint16 index)";
+
+/// The EIP-712 type of the `meta_call_with_relayer` payload, `relayer` is the account which may
+/// include the meta-transaction.
+pub const SUBSTRATE_CALL_WITH_RELAYER_TYPE: &[u8] =
+	b"SubstrateCallWithRelayer(string who,bytes callData,uint64 nonce,uint128 tip,string relayer)";
+
+/// The EIP-712 type of the `meta_call_with_mortality` payload, the meta-transaction is valid from
+/// the block `validFrom` to the block `validUntil` inclusive.
+pub const SUBSTRATE_CALL_WITH_MORTALITY_TYPE: &[u8] =
+	b"SubstrateCallWithMortality(string who,bytes callData,uint64 nonce,uint128 tip,uint64 validFrom,uint64 validUntil)";
+
+/// The EIP-712 type of the `meta_call_with_weight_limit` payload, the weight of the call may not
+/// exceed `maxRefTime` and `maxProofSize`.
+pub const SUBSTRATE_CALL_WITH_WEIGHT_LIMIT_TYPE: &[u8] =
+	b"SubstrateCallWithWeightLimit(string who,bytes callData,uint64 nonce,uint128 tip,uint64 maxRefTime,uint64 maxProofSize)";
+
+/// The EIP-712 type of the `meta_call_with_tip_bps` payload, the tip is `tipBps` basis points of
+/// the inclusion fee and the inclusion fee plus the tip may not exceed `maxFee`.
+pub const SUBSTRATE_CALL_WITH_TIP_BPS_TYPE: &[u8] =
+	b"SubstrateCallWithTipBps(string who,bytes callData,uint64 nonce,uint16 tipBps,uint128 maxFee)";
+
+/// The EIP-712 type of the `meta_batch` payload, `callHashes` are the keccak-256 hashes of the
+/// SCALE encoded calls in order, so that wallets show each of them, and `mode` is the
+/// [`BatchMode`].
+pub const SUBSTRATE_CALLS_TYPE: &[u8] =
+	b"SubstrateCalls(string who,bytes32[] callHashes,uint64 nonce,uint128 tip,uint8 mode)";
+
+/// How a `meta_batch` handles a failing call, as `utility::batch`, `utility::batch_all` and
+/// `utility::force_batch` do.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum BatchMode {
+	/// Stop at the first failing call, the calls before it are kept.
+	#[codec(index = 0)]
+	StopOnError,
+	/// Revert every call if one of them fails.
+	#[codec(index = 1)]
+	AllOrNothing,
+	/// Revert the failing calls only and go on with the others.
+	#[codec(index = 2)]
+	ContinueOnError,
+}
+
+/// The EIP-712 struct hash of a `meta_call` payload of `version`, `who` is the SS58 address of the
+/// account as shown in the payload.
+pub fn meta_call_struct_hash(
+	version: PayloadVersion,
+	who: &[u8],
+	call_data: &[u8],
+	nonce: Nonce,
+	tip: u128,
+) -> Keccak256Signature {
+	use sp_io::hashing::keccak_256;
+
+	let encoded = match version {
+		PayloadVersion::V0 => abi::encode(&[
+			Token::FixedBytes(&keccak_256(SUBSTRATE_CALL_TYPE)),
+			Token::FixedBytes(&keccak_256(who)),
+			Token::FixedBytes(&keccak_256(call_data)),
+			Token::Uint(nonce.into()),
+		]),
+		PayloadVersion::V1 => abi::encode(&[
+			Token::FixedBytes(&keccak_256(SUBSTRATE_CALL_V1_TYPE)),
+			Token::Uint((version as u8).into()),
+			Token::FixedBytes(&keccak_256(who)),
+			Token::FixedBytes(&keccak_256(call_data)),
+			Token::Uint(nonce.into()),
+			Token::Uint(tip.into()),
+		]),
+	};
+	keccak_256(&encoded)
+}
+
+/// The EIP-712 type of [`TypedCall::BalanceTransfer`].
+pub const BALANCE_TRANSFER_TYPE: &[u8] =
+	b"BalanceTransfer(string who,string to,uint128 amount,uint64 nonce)";
+
+/// The EIP-712 type of [`TypedCall::Remark`].
+pub const REMARK_TYPE: &[u8] = b"Remark(string who,string remark,uint64 nonce)";
+
+/// The EIP-712 type of the `meta_transfer` payload.
+pub const META_TRANSFER_TYPE: &[u8] =
+	b"MetaTransfer(string who,string dest,uint128 amount,uint64 nonce)";
+
+/// The EIP-712 type of the `link_accounts` payload, `account` is the native account to link.
+pub const LINK_ACCOUNTS_TYPE: &[u8] = b"LinkAccounts(string who,string account,uint64 nonce)";
+
+/// The EIP-712 type of the `meta_call_as_linked` payload, `account` is the linked native account
+/// the call is dispatched as.
+pub const SUBSTRATE_CALL_AS_LINKED_TYPE: &[u8] =
+	b"SubstrateCallAsLinked(string who,string account,bytes callData,uint64 nonce,uint128 tip)";
+
+/// The EIP-712 type of the EIP-2612 style `permit` payload, `asset` is the SCALE encoded asset id
+/// and `deadline` the last block number the permit is valid.
+pub const PERMIT_TYPE: &[u8] =
+	b"Permit(string owner,bytes asset,string spender,uint128 amount,uint64 nonce,uint64 deadline)";
+
+/// The EIP-712 type of the `request_controller_rotation` payload, `newController` is the EVM
+/// address of the key taking over the account.
+pub const ROTATE_CONTROLLER_TYPE: &[u8] =
+	b"RotateController(string who,address newController,uint64 nonce)";
+
+/// Common calls which can be signed as human-readable EIP-712 structs instead of opaque call data,
+/// so that wallets are able to show what is being signed.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum TypedCall<AccountId, Balance> {
+	/// Transfer `amount` of the native token to `to`.
+	BalanceTransfer { to: AccountId, amount: Balance },
+	/// Make an on-chain remark, shown as a string in the wallet.
+	Remark { remark: Vec<u8> },
+}
+
+/// The fees a meta-transaction is expected to pay.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct MetaFeeEstimate<Balance> {
+	/// The fee of the inner call, excluding the tip.
+	pub inclusion_fee: Balance,
+	/// The tip for the block author.
+	pub tip: Balance,
+	/// The service fee of the pallet.
+	pub service_fee: Balance,
+	/// The sum of all the fees above.
+	pub total: Balance,
+}
+
+/// Why a meta-transaction was rejected at its dispatch, or failed.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum RejectionCategory {
+	/// The signature is invalid, malleable or by another key.
+	BadSignature,
+	/// The nonce was already used or is ahead.
+	StaleNonce,
+	/// The fees couldn't be paid.
+	Payment,
+	/// The signer's EVM address is in the denylist.
+	Blocked,
+	/// The account has used up its meta-transactions for the rate limit window.
+	RateLimited,
+	/// The call isn't allowed by `CallFilter`, or is nested too deep.
+	Filtered,
+	/// The meta-transaction was executed but its inner call failed.
+	CallFailed,
+	/// Any other reason.
+	Other,
+}
+
+/// The number of meta-transactions rejected or failed in a block, by [`RejectionCategory`].
+#[derive(Clone, Default, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct RejectionStats {
+	/// [`RejectionCategory::BadSignature`]
+	pub bad_signature: u32,
+	/// [`RejectionCategory::StaleNonce`]
+	pub stale_nonce: u32,
+	/// [`RejectionCategory::Payment`]
+	pub payment: u32,
+	/// [`RejectionCategory::Blocked`]
+	pub blocked: u32,
+	/// [`RejectionCategory::RateLimited`]
+	pub rate_limited: u32,
+	/// [`RejectionCategory::Filtered`]
+	pub filtered: u32,
+	/// [`RejectionCategory::CallFailed`]
+	pub call_failed: u32,
+	/// [`RejectionCategory::Other`]
+	pub other: u32,
+}
+
+impl RejectionStats {
+	/// Count a rejection of `category`.
+	pub fn note(&mut self, category: RejectionCategory) {
+		let counter = match category {
+			RejectionCategory::BadSignature => &mut self.bad_signature,
+			RejectionCategory::StaleNonce => &mut self.stale_nonce,
+			RejectionCategory::Payment => &mut self.payment,
+			RejectionCategory::Blocked => &mut self.blocked,
+			RejectionCategory::RateLimited => &mut self.rate_limited,
+			RejectionCategory::Filtered => &mut self.filtered,
+			RejectionCategory::CallFailed => &mut self.call_failed,
+			RejectionCategory::Other => &mut self.other,
+		};
+		*counter = counter.saturating_add(1);
+	}
+}
+
+/// Meta-transactions an account has sent within the current rate limit window.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct RateLimitState<BlockNumber> {
+	/// The block the current window started at.
+	pub window_start: BlockNumber,
+	/// The number of meta-transactions dispatched within the window.
+	pub calls: u32,
+}
+
+/// A pending recovery of a mapped account whose EVM key is lost.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct RecoveryRequest<AccountId, BlockNumber> {
+	/// The account which receives the balance of the recovered account.
+	pub new_controller: AccountId,
+	/// The first block the recovery may be executed in, the original key may veto it until then.
+	pub executable_at: BlockNumber,
+}
+
+/// A pending rotation of the EVM key controlling a mapped account.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ControllerRotation<BlockNumber> {
+	/// The EVM address of the key which controls the account after the rotation.
+	pub new_controller: EvmAddress,
+	/// The first block the rotation may be executed in, the current key may cancel it until it's
+	/// executed.
+	pub executable_at: BlockNumber,
+}
+
+/// What a [`Grant`] permits the grantee to do for the granter.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum GrantKind {
+	/// The grantee acts on behalf of the granter.
+	#[codec(index = 0)]
+	Delegation,
+}
+
+/// A permission of a grantee, valid until its expiry block.
+///
+/// Expired grants are treated as absent as soon as they expire, see [`Pallet::active_grant`], and
+/// are removed from the storage by `on_idle` later.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Grant<BlockNumber> {
+	/// The first block the grant is no longer valid in.
+	pub expires_at: BlockNumber,
+}
+
+/// How an EVM account is identified, e.g. by an exchange funding it.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum EvmIdentity {
+	/// The EVM address, its account is only known if it's derived from the address or the
+	/// account has sent a meta-transaction.
+	#[codec(index = 0)]
+	Address(EvmAddress),
+	/// The uncompressed secp256k1 public key (without the `0x04` prefix).
+	#[codec(index = 1)]
+	PublicKey([u8; 64]),
+}
+
+impl EvmIdentity {
+	/// The EVM address of the identity.
+	pub fn evm_address(&self) -> EvmAddress {
+		match self {
+			Self::Address(evm_address) => *evm_address,
+			Self::PublicKey(public_key) => evm_address_of(public_key),
+		}
+	}
+}
+
+/// The calls a [`CallFilterRule`] applies to, by their indices in the encoded call.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum CallSelector {
+	/// Every call of the pallet at the index.
+	#[codec(index = 0)]
+	Pallet(u8),
+	/// The call at the call index of the pallet at the pallet index.
+	#[codec(index = 1)]
+	Call(u8, u8),
+}
+
+/// Whether the calls of a [`CallSelector`] are reachable through meta-transactions, overriding
+/// `CallFilter`.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum CallFilterRule {
+	#[codec(index = 0)]
+	Allow,
+	#[codec(index = 1)]
+	Deny,
+}
+
+/// The meta-transactions a [`Sponsorship`] pays the fees of.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum SponsorshipTarget {
+	/// The meta-transactions signed by the EVM address.
+	#[codec(index = 0)]
+	Signer(EvmAddress),
+	/// The meta-transactions whose call, the one their fees are computed for, matches the
+	/// selector.
+	#[codec(index = 1)]
+	Call(CallSelector),
+}
+
+/// An agreement of `sponsor` to pay the fees of the meta-transactions of a [`SponsorshipTarget`]
+/// until the block `expires_at`, up to `budget` in total.
+///
+/// Expired sponsorships are treated as absent, see [`Pallet::active_sponsorship`].
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Sponsorship<AccountId, Balance, BlockNumber> {
+	/// The account paying the fees.
+	pub sponsor: AccountId,
+	/// What is left of the budget.
+	pub budget: Balance,
+	/// The first block the sponsorship is no longer valid in.
+	pub expires_at: BlockNumber,
+}
+
+pub type SponsorshipOf<T> = Sponsorship<
+	<T as frame_system::Config>::AccountId,
+	BalanceOf<T>,
+	frame_system::pallet_prelude::BlockNumberFor<T>,
+>;
+
+/// A meta-transaction which passed the checks of the transaction pool, see
+/// [`Pallet::check_meta_transaction`].
+pub(crate) struct CheckedMetaTransaction<T: Config> {
+	/// The EVM address of the signer.
+	pub evm_address: EvmAddress,
+	/// The EIP-712 hash of the signed payload, within the domain of
+	/// `EIP712VerifyingContractAddress` even if it's signed for another verifying contract.
+	pub message_hash: Keccak256Signature,
+	/// The signature of the payload.
+	pub signature: EIP712Signature,
+	/// The verifying contract of the domain the payload is signed within.
+	pub verifying_contract: EIP712VerifyingContractAddress,
+	/// The dispatch info of the call.
+	pub info: DispatchInfo,
+	/// The encoded length of the call.
+	pub len: usize,
+	/// The tip paid in the native token.
+	pub tip: PaymentBalanceOf<T>,
+	/// The tag the meta-transaction provides.
+	pub provides: Vec<u8>,
+	/// The tag the meta-transaction requires, if any.
+	pub requires: Option<Vec<u8>>,
+	/// The number of blocks the meta-transaction stays valid in the transaction pool.
+	pub longevity: TransactionLongevity,
+	/// Whether the meta-transaction is gossiped to the other nodes.
+	pub propagate: bool,
+}
+
+impl<T: Config> CheckedMetaTransaction<T> {
+	/// The signer recorded in [`PreDispatchedSigner`] for the dispatch.
+	pub fn pre_dispatched_signer(
+		&self,
+	) -> (Keccak256Signature, EIP712Signature, EvmAddress, EIP712VerifyingContractAddress) {
+		(self.message_hash, self.signature, self.evm_address, self.verifying_contract)
+	}
+}
+
+/// A meta-transaction whose signer is checked and whose service fee is charged, see
+/// [`Pallet::prepare_meta_transaction`].
+pub(crate) struct PreparedMetaTransaction<T: Config> {
+	/// The EVM address of the signer.
+	pub evm_address: EvmAddress,
+	/// The verifying contract of the domain the payload is signed within.
+	pub verifying_contract: EIP712VerifyingContractAddress,
+	/// The account paying the fees, the signer unless a sponsorship pays them.
+	pub payer: T::AccountId,
+	/// The sponsorship paying the fees, if any.
+	pub sponsorship: Option<SponsorshipTarget>,
+	/// The service fee charged, zero if it is waived.
+	pub service_fee: BalanceOf<T>,
+	/// The tip paid in another asset, if any.
+	pub asset_tip: Option<AssetTipOf<T>>,
+}
+
+/// The origin of the calls dispatched by meta-transactions if `DispatchAsEvmMapped` is enabled, see
+/// [`Origin`].
+#[derive(Clone, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub enum MappedOrigin<AccountId> {
+	/// A call of the mapped account `account_id` dispatched by a meta-transaction which is signed
+	/// by `evm_address`.
+	EvmMapped { evm_address: EvmAddress, account_id: AccountId },
+}
+
+/// Ensure the origin is [`MappedOrigin::EvmMapped`], i.e. the call is routed through the EVM
+/// mapping, returning the EVM address and the mapped account.
+pub struct EnsureEvmMapped<AccountId>(PhantomData<AccountId>);
+
+impl<O, AccountId> EnsureOrigin<O> for EnsureEvmMapped<AccountId>
+where
+	O: Into<Result<MappedOrigin<AccountId>, O>> + From<MappedOrigin<AccountId>>,
+	AccountId: Decode,
+{
+	type Success = (EvmAddress, AccountId);
+
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		o.into()
+			.map(|MappedOrigin::EvmMapped { evm_address, account_id }| (evm_address, account_id))
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<O, ()> {
+		let account_id = AccountId::decode(&mut TrailingZeroInput::zeroes()).map_err(|_| ())?;
+		Ok(O::from(MappedOrigin::EvmMapped { evm_address: EvmAddress::zero(), account_id }))
+	}
 }
 
 pub struct SubstrateAddressConverter;
@@ -100,16 +1210,70 @@ impl AddressConversion<AccountId32> for EvmTransparentConverter {
 	fn try_convert(evm_public_key: &[u8]) -> Option<AccountId32> {
 		let h32 = sp_core::H256(sp_io::hashing::keccak_256(&evm_public_key[1..]));
 		let h20 = sp_core::H160::from(h32);
-		let postfix = b"@evm_address";
 
+		Self::try_convert_evm_address(&h20)
+	}
+
+	fn try_convert_evm_address(evm_address: &EvmAddress) -> Option<AccountId32> {
 		let mut raw_account: AccountId32Bytes = [0; 32];
-		raw_account[..20].copy_from_slice(h20.as_bytes());
-		raw_account[20..].copy_from_slice(postfix);
+		raw_account[..20].copy_from_slice(evm_address.as_bytes());
+		raw_account[20..].copy_from_slice(b"@evm_address");
 
 		Some(AccountId32::from(raw_account))
 	}
 }
 
+/// Resolve an EVM address to the account this pallet maps it to, so that other pallets handling
+/// EVM addresses (e.g. Frontier's `pallet_evm`) agree with the meta-transactions on the account.
+///
+/// `Fallback` converts the addresses whose account isn't known yet, see
+/// [`Pallet::map_evm_address`]. Note that the account of such an address changes once it's
+/// recorded, with [`EvmTransparentConverter`] every address is known.
+pub struct EvmAddressMapping<T, Fallback>(PhantomData<(T, Fallback)>);
+
+impl<T, Fallback> Convert<EvmAddress, AccountId32> for EvmAddressMapping<T, Fallback>
+where
+	T: Config + frame_system::Config<AccountId = AccountId32>,
+	Fallback: Convert<EvmAddress, AccountId32>,
+{
+	fn convert(evm_address: EvmAddress) -> AccountId32 {
+		Pallet::<T>::map_evm_address(&evm_address).unwrap_or_else(|| Fallback::convert(evm_address))
+	}
+}
+
+#[cfg(feature = "frontier")]
+impl<T, Fallback> pallet_evm::AddressMapping<AccountId32> for EvmAddressMapping<T, Fallback>
+where
+	T: Config + frame_system::Config<AccountId = AccountId32>,
+	Fallback: Convert<EvmAddress, AccountId32>,
+{
+	fn into_account_id(address: sp_core::H160) -> AccountId32 {
+		<Self as Convert<EvmAddress, AccountId32>>::convert(address)
+	}
+}
+
+/// The parachain id of `P`, e.g. `ParachainInfo`, for [`Config::EIP712ParaId`].
+pub struct ParaIdOf<P>(PhantomData<P>);
+
+impl<P, I> frame_support::traits::Get<Option<u32>> for ParaIdOf<P>
+where
+	P: frame_support::traits::Get<I>,
+	I: Into<u32>,
+{
+	fn get() -> Option<u32> {
+		Some(P::get().into())
+	}
+}
+
+/// The salt of the EIP-712 domain of the parachain `para_id`, `keccak256(abi.encode(uint32
+/// para_id, bytes32 salt))` where `salt` is the configured salt or zero.
+pub fn parachain_eip712_salt(para_id: u32, salt: Option<[u8; 32]>) -> [u8; 32] {
+	sp_io::hashing::keccak_256(&abi::encode(&[
+		Token::Uint(para_id.into()),
+		Token::FixedBytes(&salt.unwrap_or_default()),
+	]))
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -117,7 +1281,11 @@ pub mod pallet {
 	use frame_system::pallet_prelude::*;
 	use sp_std::prelude::*;
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
@@ -126,30 +1294,207 @@ pub mod pallet {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
+		/// The overarching origin type.
+		type RuntimeOrigin: From<Origin<Self>>
+			+ Into<Result<Origin<Self>, <Self as Config>::RuntimeOrigin>>
+			+ IsType<<Self as frame_system::Config>::RuntimeOrigin>;
+
 		/// The overarching call type.
 		type RuntimeCall: Parameter
 			+ Dispatchable<
-				RuntimeOrigin = Self::RuntimeOrigin,
+				RuntimeOrigin = <Self as frame_system::Config>::RuntimeOrigin,
 				Info = DispatchInfo,
 				PostInfo = PostDispatchInfo,
 			> + GetDispatchInfo
 			+ codec::Decode
 			+ codec::Encode
 			+ scale_info::TypeInfo
+			+ From<Call<Self>>
 			+ IsType<<Self as frame_system::Config>::RuntimeCall>;
 
+		/// The overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason>;
+
 		/// The system's currency for payment.
-		type Currency: InspectFungible<Self::AccountId> + Currency<Self::AccountId>;
+		type Currency: InspectFungible<Self::AccountId>
+			+ MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>
+			+ Currency<Self::AccountId>;
+
+		/// The deposit held from the native account of a link, see [`Pallet::link_accounts`].
+		#[pallet::constant]
+		type LinkDeposit: Get<BalanceOf<Self>>;
+
+		/// The deposit held from the granter of a grant, see [`Pallet::grant`].
+		#[pallet::constant]
+		type GrantDeposit: Get<BalanceOf<Self>>;
+
+		/// The deposit held from the sponsor of a sponsorship, see [`Pallet::sponsor`].
+		#[pallet::constant]
+		type SponsorshipDeposit: Get<BalanceOf<Self>>;
 
 		type AddressConverter: AddressConversion<Self::AccountId>;
 
 		#[pallet::constant]
 		type ServiceFee: Get<BalanceOf<Self>>;
 
+		/// Re-encode a [`TypedCall`] into the runtime call it describes, `None` if it isn't
+		/// supported.
+		type TypedCallConverter: Convert<TypedCallOf<Self>, Option<<Self as Config>::RuntimeCall>>;
+
+		/// Adjust the transaction fee of a meta-transaction based on its decoded call, e.g. to
+		/// discount calls into a dApp pallet.
+		type FeeAdjustment: AdjustFee<<Self as Config>::RuntimeCall, PaymentBalanceOf<Self>>;
+
 		type OnUnbalancedForServiceFee: OnUnbalanced<NegativeImbalanceOf<Self>>;
 
+		/// Withdraw and settle the transaction fees of meta-transactions, use
+		/// [`TransactionPaymentAdapter`] to charge them as native transactions.
+		type OnChargeMetaTransaction: OnChargeMetaTransaction<Self>;
+
+		/// Whether the amounts spent by meta-transactions, e.g. by `meta_transfer`, may reap the
+		/// account. The fees are always withdrawn keeping the account alive, as the transaction
+		/// fee is withdrawn after the service fee.
+		type SpendExistenceRequirement: Get<ExistenceRequirement>;
+
+		/// The calls reachable through meta-transactions, unless overridden by
+		/// [`CallFilterOverrides`].
 		type CallFilter: Contains<<Self as frame_system::Config>::RuntimeCall>;
 
+		/// The origin which may override `CallFilter` for a pallet or a call.
+		type CallFilterOrigin: EnsureOrigin<<Self as frame_system::Config>::RuntimeOrigin>;
+
+		/// The calls whose meta-transactions don't pay the service fee, e.g. the calls into the
+		/// pallets of a project which covers the fee for its users.
+		type ServiceFeeWaiver: Contains<<Self as frame_system::Config>::RuntimeCall>;
+
+		/// Unwrap batch or proxy calls so that `CallFilter` applies to the calls they wrap too.
+		type InspectInnerCalls: InspectInnerCalls<<Self as frame_system::Config>::RuntimeCall>;
+
+		/// How many blocks the receipt of an executed meta-transaction is kept, zero disables
+		/// receipts.
+		#[pallet::constant]
+		type ReceiptRetention: Get<BlockNumberFor<Self>>;
+
+		/// Whether to write every executed meta-transaction into the off-chain index, see
+		/// [`offchain_index_key`].
+		#[pallet::constant]
+		type IndexMetaTransactions: Get<bool>;
+
+		/// The number of meta-transactions of an account which don't pay the service fee.
+		#[pallet::constant]
+		type FreeCallsPerAccount: Get<u32>;
+
+		/// The length of the rate limit window in blocks, zero disables rate limiting.
+		#[pallet::constant]
+		type RateLimitWindow: Get<BlockNumberFor<Self>>;
+
+		/// The maximum number of meta-transactions an account may send within a rate limit
+		/// window.
+		#[pallet::constant]
+		type MaxCallsPerWindow: Get<u32>;
+
+		/// The maximum nesting depth of the call of a meta-transaction, as counted by
+		/// [`codec::DecodeLimit`], e.g. calls wrapped in batches or proxies.
+		#[pallet::constant]
+		type MaxCallDepth: Get<u32>;
+
+		/// Whether meta-transactions may dispatch `Operational` calls, `Mandatory` calls are
+		/// never allowed.
+		#[pallet::constant]
+		type AllowOperationalCalls: Get<bool>;
+
+		/// Whether meta-transactions dispatch their calls with [`MappedOrigin::EvmMapped`] instead
+		/// of a signed origin, so that other pallets can tell them apart with [`EnsureEvmMapped`].
+		///
+		/// Calls which require a signed origin fail if this is enabled.
+		#[pallet::constant]
+		type DispatchAsEvmMapped: Get<bool>;
+
+		/// The id of the assets meta-transactions may be tipped in.
+		type TipAssetId: Parameter + MaxEncodedLen;
+
+		/// Settle the tips paid in an asset, `()` rejects asset tips.
+		type TipHandler: TipHandler<Self::AccountId, Self::TipAssetId>;
+
+		/// The id of the assets which may be approved by a permit.
+		type PermitAssetId: Parameter + MaxEncodedLen;
+
+		/// Approve the spenders of the permits, `()` rejects permits.
+		type AssetApprovals: AssetApprovals<Self::AccountId, Self::PermitAssetId>;
+
+		/// The proxy-like classes a meta-transaction may be restricted to, e.g. the `ProxyType` of
+		/// the runtime, see `meta_call_with_proxy_type`.
+		type ProxyType: Parameter
+			+ Member
+			+ MaxEncodedLen
+			+ InstanceFilter<<Self as frame_system::Config>::RuntimeCall>;
+
+		/// Calculate the priority of a meta-transaction in the transaction pool.
+		type PriorityPolicy: PriorityPolicy<PaymentBalanceOf<Self>>;
+
+		/// The number of blocks a validated meta-transaction stays valid in the transaction pool.
+		#[pallet::constant]
+		type UnsignedLongevity: Get<TransactionLongevity>;
+
+		/// How far ahead of the account nonce the nonce of a meta-transaction may be, so that it
+		/// waits in the transaction pool for the ones before it. Zero accepts only the next nonce.
+		#[pallet::constant]
+		type MaxNonceGap: Get<Nonce>;
+
+		/// How the meta-transactions are protected against replays, [`SequentialNonces`] for the
+		/// nonces of the accounts or [`WindowedPayloadHashes`] to execute them in any order.
+		type ReplayProtection: ReplayProtection<Self::AccountId, BlockNumberFor<Self>>;
+
+		/// The maximum number of meta-transactions included in a block, so that they can't starve
+		/// the other transactions.
+		#[pallet::constant]
+		type MaxMetaTxPerBlock: Get<u32>;
+
+		/// Whether the nonce of an account is removed when the account is reaped, the pallet must
+		/// be the `OnKilledAccount` handler of `frame_system`.
+		///
+		/// The nonce restarts from zero afterwards, so it only takes effect with `MetaMortality`.
+		#[pallet::constant]
+		type PurgeNonceOnReap: Get<bool>;
+
+		/// Whether the nonces may be purged, see `PurgeNonceOnReap` and `purge_nonce`.
+		///
+		/// Only enable it if the signers are only asked for mortal payloads, of
+		/// `meta_call_with_mortality`: an immortal payload signed before could be replayed once the
+		/// nonce of its account restarts from zero. The other meta-transactions are still accepted.
+		#[pallet::constant]
+		type MetaMortality: Get<bool>;
+
+		/// The prefix of the tags a meta-transaction provides and requires, it must not be shared
+		/// with another pallet.
+		#[pallet::constant]
+		type UnsignedTagPrefix: Get<&'static str>;
+
+		/// The account of the author of the current block, the meta-transactions designating a
+		/// relayer are only included in the blocks it authors. `()` disables them.
+		type BlockAuthor: Get<Option<Self::AccountId>>;
+
+		/// Whether the tips of meta-transactions are paid to the [`Config::BlockAuthor`] instead
+		/// of `OnChargeMetaTransaction`, e.g. if the latter burns the fees.
+		#[pallet::constant]
+		type TipToBlockAuthor: Get<bool>;
+
+		/// The origin which may manage the pallet, e.g. block or unblock EVM addresses.
+		type AdminOrigin: EnsureOrigin<<Self as frame_system::Config>::RuntimeOrigin>;
+
+		/// The origin which may initiate the recovery of a mapped account.
+		type RecoveryOrigin: EnsureOrigin<<Self as frame_system::Config>::RuntimeOrigin>;
+
+		/// The number of blocks between initiating and executing a recovery, during which the
+		/// original EVM key may veto it.
+		#[pallet::constant]
+		type RecoveryDelay: Get<BlockNumberFor<Self>>;
+
+		/// The number of blocks between requesting and executing the rotation of the EVM key
+		/// controlling an account, during which the current key may cancel it.
+		#[pallet::constant]
+		type ControllerRotationDelay: Get<BlockNumberFor<Self>>;
+
 		#[pallet::constant]
 		type EIP712Name: Get<Vec<u8>>;
 
@@ -162,6 +1507,26 @@ pub mod pallet {
 		#[pallet::constant]
 		type EIP712VerifyingContractAddress: Get<EIP712VerifyingContractAddress>;
 
+		/// Other verifying contracts whose EIP-712 domains the meta-transactions may be signed
+		/// within, e.g. of a staging dApp, or `()` for none. Each of them costs the validation, and
+		/// the dispatch unless `pre_dispatch` recovered the signer, one more signature recovery for
+		/// the meta-transactions not signed within the domain of `EIP712VerifyingContractAddress`.
+		/// The other payloads, like links and permits, are only accepted within that domain.
+		#[pallet::constant]
+		type EIP712ExtraVerifyingContracts: Get<Vec<EIP712VerifyingContractAddress>>;
+
+		/// The optional salt of the EIP-712 domain, it disambiguates deployments which share the
+		/// name, version and chain id.
+		#[pallet::constant]
+		type EIP712Salt: Get<Option<[u8; 32]>>;
+
+		/// The parachain id mixed into the salt of the EIP-712 domain, see
+		/// [`parachain_eip712_salt`], so that parachains deploying the pallet with the same name,
+		/// version and chain id don't accept each other's signatures. Use [`ParaIdOf`] to take it
+		/// from `ParachainInfo`, or `()` on a solo chain.
+		#[pallet::constant]
+		type EIP712ParaId: Get<Option<u32>>;
+
 		/// Type representing the weight of this pallet
 		type WeightInfo: WeightInfo;
 	}
@@ -176,14 +1541,163 @@ pub mod pallet {
 			actual_fee: BalanceOf<T>,
 			expected_fee: BalanceOf<T>,
 		},
+		ServiceFeeWaived {
+			who: T::AccountId,
+			waived_fee: BalanceOf<T>,
+		},
+		/// The service fee is waived because the call matches `ServiceFeeWaiver`.
+		ServiceFeeWaivedForCall {
+			who: T::AccountId,
+			waived_fee: BalanceOf<T>,
+		},
 		TransactionFeePaid {
 			who: T::AccountId,
 			actual_fee: PaymentBalanceOf<T>,
 			tip: PaymentBalanceOf<T>,
 		},
+		/// The `tip` of a meta-transaction of `who` is paid to the block `author`, see
+		/// [`Config::TipToBlockAuthor`].
+		TipPaidToAuthor {
+			who: T::AccountId,
+			author: T::AccountId,
+			tip: PaymentBalanceOf<T>,
+		},
+		AssetTipPaid {
+			who: T::AccountId,
+			asset_id: T::TipAssetId,
+			amount: u128,
+		},
+		/// All the fees of an executed meta-transaction of `who`, charged from `payer`.
+		///
+		/// `inclusion_fee` excludes the `tip`, `asset_tip` is the tip paid in another asset, if
+		/// any.
+		MetaTransactionFeeSettled {
+			who: T::AccountId,
+			payer: T::AccountId,
+			service_fee: BalanceOf<T>,
+			inclusion_fee: PaymentBalanceOf<T>,
+			tip: PaymentBalanceOf<T>,
+			asset_tip: Option<AssetTipOf<T>>,
+		},
 		CallDone {
 			who: T::AccountId,
 			call_result: DispatchResultWithPostInfo,
+			/// The verifying contract of the EIP-712 domain the call was signed within.
+			verifying_contract: EIP712VerifyingContractAddress,
+		},
+		AddressBlocked {
+			address: EvmAddress,
+		},
+		AddressUnblocked {
+			address: EvmAddress,
+		},
+		RecoveryInitiated {
+			who: T::AccountId,
+			new_controller: T::AccountId,
+			executable_at: BlockNumberFor<T>,
+		},
+		RecoveryVetoed {
+			who: T::AccountId,
+		},
+		AccountRecovered {
+			who: T::AccountId,
+			new_controller: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		AccountsLinked {
+			who: T::AccountId,
+			account: T::AccountId,
+		},
+		AccountsUnlinked {
+			who: T::AccountId,
+			account: T::AccountId,
+		},
+		Granted {
+			granter: T::AccountId,
+			grantee: T::AccountId,
+			kind: GrantKind,
+			expires_at: BlockNumberFor<T>,
+		},
+		Revoked {
+			granter: T::AccountId,
+			grantee: T::AccountId,
+			kind: GrantKind,
+		},
+		/// A `meta_batch` stopped at the call `index`, the calls before it are kept.
+		BatchInterrupted {
+			who: T::AccountId,
+			index: u32,
+			error: DispatchError,
+		},
+		/// The call `index` of a `meta_batch` failed and was reverted, the batch went on.
+		BatchCallFailed {
+			who: T::AccountId,
+			index: u32,
+			error: DispatchError,
+		},
+		/// The rule of `selector` was set, or removed if `None`.
+		CallFilterUpdated {
+			selector: CallSelector,
+			rule: Option<CallFilterRule>,
+		},
+		/// `amount` was transferred from `from` to `account`, the account mapped from
+		/// `evm_address`.
+		MappedAccountFunded {
+			from: T::AccountId,
+			evm_address: EvmAddress,
+			account: T::AccountId,
+			amount: BalanceOf<T>,
+		},
+		/// The `nonce` of the reaped account `who` was removed.
+		NoncePurged {
+			who: T::AccountId,
+			nonce: Nonce,
+		},
+		/// `spender` was approved to transfer `amount` of `asset_id` from `owner` by a permit.
+		PermitApproved {
+			owner: T::AccountId,
+			asset_id: T::PermitAssetId,
+			spender: T::AccountId,
+			amount: u128,
+		},
+		ControllerRotationRequested {
+			who: T::AccountId,
+			new_controller: EvmAddress,
+			executable_at: BlockNumberFor<T>,
+		},
+		ControllerRotationCancelled {
+			who: T::AccountId,
+		},
+		/// The EVM key of `new_controller` controls `who` instead of its previous key.
+		ControllerRotated {
+			who: T::AccountId,
+			new_controller: EvmAddress,
+		},
+		/// Whether the calls of `selector` may be dispatched by `meta_call_operational` was set.
+		OperationalCallUpdated {
+			selector: CallSelector,
+			allowed: bool,
+		},
+		/// `sponsor` pays the fees of the meta-transactions of `target`, up to `budget` until
+		/// the block `expires_at`.
+		SponsorshipRegistered {
+			sponsor: T::AccountId,
+			target: SponsorshipTarget,
+			budget: BalanceOf<T>,
+			expires_at: BlockNumberFor<T>,
+		},
+		/// The sponsorship of `target` by `sponsor` was ended before its budget was spent.
+		SponsorshipEnded {
+			sponsor: T::AccountId,
+			target: SponsorshipTarget,
+		},
+		/// The budget of the sponsorship of `target` by `sponsor` is spent, or what is left of it
+		/// no longer covers the fees of a meta-transaction, which its signer paid. The
+		/// sponsorship is removed.
+		SponsorshipDepleted {
+			sponsor: T::AccountId,
+			target: SponsorshipTarget,
+			budget: BalanceOf<T>,
 		},
 	}
 
@@ -193,12 +1707,273 @@ pub mod pallet {
 		Unexpected,
 		NonceError,
 		PaymentError,
+		InvalidSignature,
+		AddressBlocked,
+		AlreadyBlocked,
+		NotBlocked,
+		RateLimited,
+		CallTooDeep,
+		CallFiltered,
+		/// The account already has a pending recovery.
+		RecoveryAlreadyInitiated,
+		/// The account has no pending recovery.
+		NoRecovery,
+		/// The recovery is still within its veto period.
+		RecoveryNotExecutable,
+		/// An account can't be recovered to itself.
+		RecoveryToSelf,
+		/// One of the accounts is already linked.
+		AlreadyLinked,
+		/// The account isn't linked.
+		NotLinked,
+		/// The expiry block of a grant must be in the future.
+		InvalidExpiry,
+		/// The grant doesn't exist.
+		NoGrant,
+		/// The signature isn't in the canonical form, see [`is_canonical_signature`].
+		MalleableSignature,
+		/// The meta-transaction designates a relayer other than the author of the block.
+		NotDesignatedRelayer,
+		/// The current block is outside the validity window of the meta-transaction.
+		OutsideValidityWindow,
+		/// The weight of the call exceeds the weight limit signed by the signer.
+		WeightLimitExceeded,
+		/// The account of the EVM address is unknown, it must be identified by its public key.
+		UnknownEvmAddress,
+		/// The account has no nonce to purge.
+		NoNonce,
+		/// Only the nonce of a reaped account which isn't linked may be purged.
+		AccountInUse,
+		/// The deadline of the permit has passed.
+		PermitExpired,
+		/// The account already has a pending rotation of its controller.
+		RotationAlreadyRequested,
+		/// The account has no pending rotation of its controller.
+		NoRotation,
+		/// The rotation is still within its cancellation period.
+		RotationNotExecutable,
+		/// The new controller is already mapped to an account, or the account derived from it
+		/// exists.
+		ControllerInUse,
+		/// The call, or a call it wraps, isn't allowed to be dispatched as operational.
+		OperationalCallNotAllowed,
+		/// The fee of the meta-transaction exceeds the maximum fee signed by the signer.
+		FeeLimitExceeded,
+		/// The target is sponsored by another account.
+		SponsorshipTaken,
+		/// There is no sponsorship of the target.
+		NoSponsorship,
+		/// Only the sponsor may end a sponsorship before it expires.
+		NotSponsor,
+		/// `TypedCallConverter` can't convert the typed call into a runtime call.
+		UnconvertibleCall,
+		/// The account isn't known to be mapped from an EVM address.
+		NotMapped,
+		/// The nonces aren't purged unless the meta-transactions are mortal, see
+		/// `Config::MetaMortality`.
+		ImmortalPayloads,
 	}
 
 	#[pallet::storage]
 	pub(crate) type AccountNonce<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AccountId, u64, ValueQuery>;
 
+	/// The nonces of the permits, apart from the ones of the meta-transactions so that a permit
+	/// signed ahead doesn't block them.
+	#[pallet::storage]
+	pub type PermitNonces<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Nonce, ValueQuery>;
+
+	/// EVM addresses which are not allowed to use the meta-transaction gateway.
+	#[pallet::storage]
+	pub type Blocked<T: Config> = StorageMap<_, Blake2_128Concat, EvmAddress, ()>;
+
+	/// Rules overriding `CallFilter`, a call's own rule takes precedence over its pallet's.
+	#[pallet::storage]
+	pub type CallFilterOverrides<T: Config> =
+		StorageMap<_, Blake2_128Concat, CallSelector, CallFilterRule>;
+
+	/// The calls `AdminOrigin` may push through with `meta_call_operational`, a call is allowed if
+	/// either it or its pallet is listed.
+	#[pallet::storage]
+	pub type OperationalCalls<T: Config> = StorageMap<_, Blake2_128Concat, CallSelector, ()>;
+
+	/// Receipts of executed meta-transactions, keyed by the account and the nonce of the signed
+	/// payload.
+	#[pallet::storage]
+	pub type Receipts<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, Nonce, ReceiptOf<T>>;
+
+	/// The hashes of the payloads executed with [`WindowedPayloadHashes`], by the block they are
+	/// forgotten at.
+	#[pallet::storage]
+	pub(crate) type ExecutedPayloads<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, BlockNumberFor<T>, Identity, Keccak256Signature, ()>;
+
+	/// Receipts to remove at the start of a block.
+	#[pallet::storage]
+	pub(crate) type ReceiptExpiries<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		Blake2_128Concat,
+		(T::AccountId, Nonce),
+		(),
+	>;
+
+	/// The number of free meta-transactions an account has used.
+	#[pallet::storage]
+	pub type FreeCallsUsed<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Meta-transactions sent by an account within its current rate limit window.
+	#[pallet::storage]
+	pub type CallRateLimits<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, RateLimitState<BlockNumberFor<T>>>;
+
+	/// Native accounts linked to mapped accounts, keyed by the mapped account.
+	#[pallet::storage]
+	pub type Links<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId>;
+
+	/// Mapped accounts linked to native accounts, keyed by the native account.
+	#[pallet::storage]
+	pub type ReverseLinks<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId>;
+
+	/// The EVM addresses of the mapped accounts, recorded by their first meta-transaction.
+	#[pallet::storage]
+	pub type EvmAddresses<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, EvmAddress>;
+
+	/// The mapped accounts of the EVM addresses, the reverse of [`EvmAddresses`].
+	#[pallet::storage]
+	pub type MappedAccounts<T: Config> = StorageMap<_, Blake2_128Concat, EvmAddress, T::AccountId>;
+
+	/// The number of meta-transactions included in the current block, see
+	/// [`Config::MaxMetaTxPerBlock`].
+	#[pallet::storage]
+	#[pallet::whitelist_storage]
+	pub type MetaTxCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The meta-transactions of the current block which failed, by category, for the relayers'
+	/// dashboards.
+	///
+	/// The failed inner calls are counted as [`RejectionCategory::CallFailed`]. The errors of the
+	/// dispatch are only counted by the `CheckMetaTransaction` extension after the dispatch, as
+	/// the storage of a failed dispatch is reverted. The meta-transactions rejected by the
+	/// transaction pool or `pre_dispatch` aren't in the block, they are reported to the relayer by
+	/// the `InvalidMetaTransaction` codes.
+	#[pallet::storage]
+	#[pallet::whitelist_storage]
+	pub type RejectionCounts<T: Config> = StorageValue<_, RejectionStats, ValueQuery>;
+
+	/// The signer recovered by `pre_dispatch` for the meta-transaction being applied, with the
+	/// EIP-712 hash and the signature of its payload and the verifying contract of the domain it
+	/// was signed within. Taken by the dispatch.
+	///
+	/// A failed dispatch reverts the take, the entry is removed at the end of the block.
+	#[pallet::storage]
+	pub(crate) type PreDispatchedSigner<T: Config> = StorageValue<
+		_,
+		(Keccak256Signature, EIP712Signature, EvmAddress, EIP712VerifyingContractAddress),
+	>;
+
+	/// The sponsorships paying the fees of meta-transactions, by their target, see
+	/// [`Pallet::active_sponsorship`].
+	#[pallet::storage]
+	pub type Sponsorships<T: Config> =
+		StorageMap<_, Blake2_128Concat, SponsorshipTarget, SponsorshipOf<T>>;
+
+	/// The number of [`SponsorshipTarget::Signer`] entries in [`Sponsorships`], the signer of a
+	/// meta-transaction of an account whose EVM address isn't recorded is only known after the
+	/// signature recovery.
+	#[pallet::storage]
+	pub(crate) type SignerSponsorships<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Grants of granters to grantees, see [`Pallet::active_grant`].
+	#[pallet::storage]
+	pub type Grants<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, T::AccountId>,
+			NMapKey<Blake2_128Concat, T::AccountId>,
+			NMapKey<Twox64Concat, GrantKind>,
+		),
+		Grant<BlockNumberFor<T>>,
+	>;
+
+	/// Grants to remove once they have expired, keyed by their expiry block.
+	#[pallet::storage]
+	pub(crate) type GrantExpiries<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		Blake2_128Concat,
+		(T::AccountId, T::AccountId, GrantKind),
+		(),
+	>;
+
+	/// The first block whose expired grants haven't been removed yet.
+	#[pallet::storage]
+	pub(crate) type NextGrantSweep<T: Config> = StorageValue<_, BlockNumberFor<T>>;
+
+	/// Pending recoveries of mapped accounts.
+	#[pallet::storage]
+	pub type Recoveries<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		RecoveryRequest<T::AccountId, BlockNumberFor<T>>,
+	>;
+
+	/// The EVM addresses controlling mapped accounts instead of the keys the accounts are derived
+	/// from, after their controllers were rotated.
+	#[pallet::storage]
+	pub type Controllers<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, EvmAddress>;
+
+	/// Pending rotations of the controllers of mapped accounts.
+	#[pallet::storage]
+	pub type ControllerRotations<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, ControllerRotation<BlockNumberFor<T>>>;
+
+	/// The origin of the calls dispatched by meta-transactions if `DispatchAsEvmMapped` is
+	/// enabled.
+	#[pallet::origin]
+	pub type Origin<T> = MappedOrigin<<T as frame_system::Config>::AccountId>;
+
+	/// A reason for the pallet placing a hold on funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// The deposit of a link between a mapped and a native account.
+		LinkDeposit,
+		/// The deposit of a grant.
+		GrantDeposit,
+		/// The estimated fee of a meta-transaction being executed, see [`HoldingFeeAdapter`].
+		TransactionFee,
+		/// The deposit of a sponsorship.
+		SponsorshipDeposit,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+			MetaTxCount::<T>::kill();
+			RejectionCounts::<T>::kill();
+			PreDispatchedSigner::<T>::kill();
+			// Including the removal of `on_finalize`
+			Self::prune_receipts(n)
+				.saturating_add(T::ReplayProtection::prune(n))
+				.saturating_add(T::DbWeight::get().writes(4))
+		}
+
+		fn on_finalize(_n: BlockNumberFor<T>) {
+			// Left by a failed dispatch, it isn't kept in the state
+			PreDispatchedSigner::<T>::kill();
+		}
+
+		fn on_idle(n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::sweep_expired_grants(n, remaining_weight)
+		}
+	}
+
 	#[pallet::validate_unsigned]
 	impl<T: Config> ValidateUnsigned for Pallet<T>
 	where
@@ -215,142 +1990,17 @@ pub mod pallet {
 			_source: TransactionSource,
 			unsigned_call: &Self::Call,
 		) -> TransactionValidity {
-			// Only allow `meta_call`
-			let Call::meta_call {
-				ref who,
-				ref call,
-				ref nonce,
-				ref signature,
-				ref tip,
-			} = unsigned_call
-			else {
-				return Err(InvalidTransaction::Call.into())
-			};
-
-			// Check the signature and get the public key
-			let call_data = <T as Config>::RuntimeCall::encode(call);
-			let message_hash = Self::eip712_message_hash(who.clone(), &call_data, *nonce);
-
-			let Ok(recovered_public_key) = (match <T as Config>::AddressConverter::SECP256K1_PUBLIC_KEY_FORM {
-				Secp256K1PublicKeyForm::Compressed => {
-					sp_io::crypto::secp256k1_ecdsa_recover_compressed(signature, &message_hash)
-						.map(|i| i.to_vec())
-				},
-				Secp256K1PublicKeyForm::Uncompressed => {
-					sp_io::crypto::secp256k1_ecdsa_recover(signature, &message_hash)
-						.map(|i| i.to_vec())
-				}
-			}) else {
-				return Err(InvalidTransaction::Call.into())
-			};
-
-			// Deserialize the actual caller
-			let Some(decoded_account) =
-				<T as Config>::AddressConverter::try_convert(&recovered_public_key) else {
-				return Err(InvalidTransaction::Call.into())
-			};
-			if who != &decoded_account {
-				return Err(InvalidTransaction::BadSigner.into())
-			}
-
-			// Skip frame_system::CheckNonZeroSender
-			// Skip frame_system::CheckSpecVersion<Runtime>
-			// Skip frame_system::CheckTxVersion<Runtime>
-			// Skip frame_system::CheckGenesis<Runtime>
-			// Skip frame_system::CheckEra<Runtime>
-
-			// frame_system::CheckNonce<Runtime>
-			let account_nonce = AccountNonce::<T>::get(who);
-			if nonce < &account_nonce {
-				return Err(InvalidTransaction::Stale.into())
-			}
-			let provides = (who, nonce).encode();
-			let requires = if &account_nonce < nonce && nonce > &0u64 {
-				Some((who, nonce - 1).encode())
-			} else {
-				None
-			};
-			if nonce != &account_nonce {
-				return Err(if nonce < &account_nonce {
-					InvalidTransaction::Stale
-				} else {
-					InvalidTransaction::Future
-				}
-				.into())
-			}
-
-			// Skip frame_system::CheckWeight<Runtime>
-			// it has implemented `validate_unsigned` and `pre_dispatch_unsigned`, we don't need to
-			// do the validate here.
-
-			// pallet_transaction_payment::ChargeTransactionPayment<Runtime>
-			let tip = tip.unwrap_or(0u32.into());
-			let len = call.encoded_size();
-			let info = call.get_dispatch_info();
-			// We shall get the same `fee` later
-			let est_fee =
-				pallet_transaction_payment::Pallet::<T>::compute_fee(len as u32, &info, tip);
-			// TODO: Need check this work with assets-payment
-			// We don't withdraw the fee here, because we can't cache the imbalance
-			// Instead, we check the account has enough fee
-			// I think this is a hack, or the type can't match
-			let est_fee = est_fee.saturated_into::<u128>();
-			// We can't get the actual size of the meta-tx itself,
-			// so we have to introducing service fee.
-			let service_fee = T::ServiceFee::get().saturated_into::<u128>();
-			let usable_balance_for_fees =
-				T::Currency::reducible_balance(who, Preservation::Preserve, Fortitude::Polite)
-					.saturated_into::<u128>();
-			if est_fee.saturating_add(service_fee) > usable_balance_for_fees {
-				return Err(InvalidTransaction::Payment.into())
-			}
+			let checked = Self::check_unsigned(unsigned_call)?;
+			Ok(Self::valid_transaction(checked))
+		}
 
-			// Calculate priority
-			// Cheat from `get_priority` in frame/transaction-payment/src/lib.rs
-			use frame_support::traits::Defensive;
-			use sp_runtime::{traits::One, SaturatedConversion, Saturating};
-			// Calculate how many such extrinsics we could fit into an empty block and take the
-			// limiting factor.
-			let max_block_weight = <T as frame_system::Config>::BlockWeights::get().max_block;
-			let max_block_length =
-				*<T as frame_system::Config>::BlockLength::get().max.get(info.class) as u64;
-
-			// bounded_weight is used as a divisor later so we keep it non-zero.
-			let bounded_weight = info.weight.max(Weight::from_parts(1, 1)).min(max_block_weight);
-			let bounded_length = (len as u64).clamp(1, max_block_length);
-
-			// returns the scarce resource, i.e. the one that is limiting the number of
-			// transactions.
-			let max_tx_per_block_weight = max_block_weight
-				.checked_div_per_component(&bounded_weight)
-				.defensive_proof("bounded_weight is non-zero; qed")
-				.unwrap_or(1);
-			let max_tx_per_block_length = max_block_length / bounded_length;
-			// Given our current knowledge this value is going to be in a reasonable range - i.e.
-			// less than 10^9 (2^30), so multiplying by the `tip` value is unlikely to overflow the
-			// balance type. We still use saturating ops obviously, but the point is to end up with
-			// some `priority` distribution instead of having all transactions saturate the
-			// priority.
-			let max_tx_per_block = max_tx_per_block_length
-				.min(max_tx_per_block_weight)
-				.saturated_into::<PaymentBalanceOf<T>>();
-			let max_reward = |val: PaymentBalanceOf<T>| val.saturating_mul(max_tx_per_block);
-
-			// To distribute no-tip transactions a little bit, we increase the tip value by one.
-			// This means that given two transactions without a tip, smaller one will be preferred.
-			let tip = tip.saturating_add(One::one());
-			let scaled_tip = max_reward(tip);
-
-			let priority = scaled_tip.saturated_into::<TransactionPriority>();
-
-			// Finish the validation
-			let valid_transaction_builder = ValidTransaction::with_tag_prefix("EVMAccountMapping")
-				.priority(priority)
-				.and_provides(provides)
-				.longevity(5)
-				.propagate(true);
-			let Some(requires) = requires else { return valid_transaction_builder.build() };
-			valid_transaction_builder.and_requires(requires).build()
+		/// Check the meta-transaction again at the inclusion, without computing the priority and
+		/// the tags which are irrelevant there. The recovered signer is recorded so that the
+		/// dispatch doesn't recover it again.
+		fn pre_dispatch(unsigned_call: &Self::Call) -> Result<(), TransactionValidityError> {
+			let checked = Self::check_inclusion(unsigned_call)?;
+			Self::note_pre_dispatched(checked.pre_dispatched_signer());
+			Ok(())
 		}
 	}
 
@@ -377,132 +2027,2928 @@ pub mod pallet {
 			who: T::AccountId,
 			call: Box<<T as Config>::RuntimeCall>,
 			nonce: Nonce,
-			#[allow(unused_variables)] signature: EIP712Signature,
+			signature: MetaSignature,
 			tip: Option<PaymentBalanceOf<T>>,
+			version: PayloadVersion,
 		) -> DispatchResult {
 			// This is an unsigned transaction
 			ensure_none(origin)?;
 
-			// We don't need to re-validate the signature here,
-			// because it already validated in `validate_unsigned` stage,
-			// and it should no way to skip.
-			// TODO: Confirm this.
+			let call_data = <T as Config>::RuntimeCall::encode(&call);
+			let message_hash =
+				|| Self::meta_call_message_hash(version, &who, &call_data, nonce, tip);
+			Self::execute_meta_transaction(&who, *call, nonce, message_hash, &signature, tip, None)
+		}
 
-			// It is possible that an account passed `validate_unsigned` check,
-			// but for some reason, its balance isn't enough for the service fee.
-			use frame_support::traits::tokens::{WithdrawReasons, ExistenceRequirement};
-			// NOTE: it is possible that the account doesn't have enough fee, which is a vulnerable.
-			let withdrawn = T::Currency::withdraw(
-				&who,
-				T::ServiceFee::get(),
-				WithdrawReasons::FEE,
-				ExistenceRequirement::KeepAlive
-			).map_err(|_err| Error::<T>::PaymentError)?;
-			let withdrawn_fee = withdrawn.peek();
-			T::OnUnbalancedForServiceFee::on_unbalanced(withdrawn);
-			Self::deposit_event(Event::ServiceFeePaid {
-				who: who.clone(),
-				actual_fee: withdrawn_fee,
-				expected_fee: T::ServiceFee::get(),
-			});
+		/// Forbid an EVM address from using the meta-transaction gateway.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::block_address())]
+		pub fn block_address(origin: OriginFor<T>, address: EvmAddress) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
 
-			// Bump the nonce
-			AccountNonce::<T>::try_mutate(&who, |value| {
-				if *value != nonce {
-					return Err(Error::<T>::NonceError)
-				}
-				*value += 1;
-				Ok(())
-			})?;
+			ensure!(!Blocked::<T>::contains_key(address), Error::<T>::AlreadyBlocked);
+			Blocked::<T>::insert(address, ());
+			Self::deposit_event(Event::AddressBlocked { address });
 
-			// Call
-			let mut origin: T::RuntimeOrigin = RawOrigin::Signed(who.clone()).into();
-			origin.add_filter(T::CallFilter::contains);
-			let len = call.encoded_size();
-			let info = call.get_dispatch_info();
-			let tip = tip.unwrap_or(0u32.into());
-			let est_fee =
-				pallet_transaction_payment::Pallet::<T>::compute_fee(len as u32, &info, tip);
-			// Add the service fee
-			let already_withdrawn =
-				<PaymentOnChargeTransaction<T> as OnChargeTransaction<T>>::withdraw_fee(
-					&who,
-					&(*call).clone().into(),
-					&info,
-					est_fee,
-					tip,
-				)
-				.map_err(|_err| Error::<T>::PaymentError)?;
+			Ok(())
+		}
 
-			let call_result = call.dispatch(origin);
-			let post_info = match call_result {
-				Ok(post_info) => post_info,
-				Err(error_and_info) => error_and_info.post_info,
-			};
-			// Deposit the call's result
-			Self::deposit_event(Event::CallDone { who: who.clone(), call_result });
+		/// Allow a blocked EVM address to use the meta-transaction gateway again.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::unblock_address())]
+		pub fn unblock_address(origin: OriginFor<T>, address: EvmAddress) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
 
-			let actual_fee = pallet_transaction_payment::Pallet::<T>::compute_actual_fee(
-				len as u32, &info, &post_info, tip,
-			);
-			// frame/transaction-payment/src/payment.rs
-			<PaymentOnChargeTransaction<T> as OnChargeTransaction<T>>::correct_and_deposit_fee(
-				&who,
-				&info,
-				&post_info,
-				actual_fee,
-				tip,
-				already_withdrawn,
-			)
-			.map_err(|_err| Error::<T>::PaymentError)?;
-			Self::deposit_event(Event::TransactionFeePaid { who: who.clone(), actual_fee, tip });
+			ensure!(Blocked::<T>::contains_key(address), Error::<T>::NotBlocked);
+			Blocked::<T>::remove(address);
+			Self::deposit_event(Event::AddressUnblocked { address });
 
 			Ok(())
 		}
-	}
 
-	impl<T: Config> Pallet<T>
-	where
-		T: frame_system::Config<AccountId = sp_runtime::AccountId32>,
-	{
-		pub(crate) fn eip712_message_hash(
-			who: T::AccountId,
-			call_data: &[u8],
-			nonce: Nonce,
-		) -> Keccak256Signature {
-			use sp_std::vec;
+		/// Meta-transaction from EVM compatible chains, signed as a human-readable EIP-712 struct
+		/// instead of opaque call data.
+		#[pallet::call_index(3)]
+		#[pallet::weight({
+			// An unconvertible typed call is rejected by `validate_unsigned`, it still declares the
+			// weight of its conversion failure if a block includes it
+			match T::TypedCallConverter::convert(typed_call.clone()) {
+				Some(call) => {
+					let di = call.get_dispatch_info();
+					(T::WeightInfo::meta_call().saturating_add(di.weight), di.class)
+				},
+				None => (T::WeightInfo::meta_typed_call_unconvertible(), DispatchClass::Normal),
+			}
+		})]
+		pub fn meta_typed_call(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			typed_call: TypedCallOf<T>,
+			nonce: Nonce,
+			signature: MetaSignature,
+			tip: Option<PaymentBalanceOf<T>>,
+		) -> DispatchResult {
+			// This is an unsigned transaction
+			ensure_none(origin)?;
+
+			let message_hash = || Self::typed_call_message_hash(&who, &typed_call, nonce);
+			let call = T::TypedCallConverter::convert(typed_call.clone())
+				.ok_or(Error::<T>::UnconvertibleCall)?;
+			Self::execute_meta_transaction(&who, call, nonce, message_hash, &signature, tip, None)
+		}
+
+		/// Transfer `amount` of the native token from `who` to `dest`, signed as an EIP-712 struct
+		/// which spells out the destination and the amount.
+		///
+		/// The transfer is executed directly instead of dispatching a decoded runtime call.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::meta_transfer())]
+		pub fn meta_transfer(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			dest: T::AccountId,
+			amount: BalanceOf<T>,
+			nonce: Nonce,
+			signature: MetaSignature,
+		) -> DispatchResult {
+			// This is an unsigned transaction
+			ensure_none(origin)?;
+
+			let message_hash = || Self::meta_transfer_message_hash(&who, &dest, amount, nonce);
+			// The transfer pays the fee of the `meta_transfer` call itself
+			let call = <T as Config>::RuntimeCall::from(Call::<T>::meta_transfer {
+				who: who.clone(),
+				dest: dest.clone(),
+				amount,
+				nonce,
+				signature,
+			});
+			let prepared =
+				Self::prepare_meta_transaction(&who, &call, nonce, message_hash, &signature, None)?;
+
+			Self::charge_and_execute(&who, nonce, &call, None, prepared, || {
+				T::Currency::transfer(&who, &dest, amount, T::SpendExistenceRequirement::get())
+					.map(Into::into)
+					.map_err(Into::into)
+			})
+		}
+
+		/// Start recovering `who` to `new_controller`, the recovery is executable after
+		/// `RecoveryDelay` blocks unless the EVM key of `who` vetoes it.
+		///
+		/// Only the mapped accounts whose EVM address is recorded, i.e. which have sent a
+		/// meta-transaction, can be recovered.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::initiate_recovery())]
+		pub fn initiate_recovery(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			new_controller: T::AccountId,
+		) -> DispatchResult {
+			T::RecoveryOrigin::ensure_origin(origin)?;
+
+			ensure!(who != new_controller, Error::<T>::RecoveryToSelf);
+			ensure!(EvmAddresses::<T>::contains_key(&who), Error::<T>::NotMapped);
+			ensure!(!Recoveries::<T>::contains_key(&who), Error::<T>::RecoveryAlreadyInitiated);
+			let executable_at =
+				frame_system::Pallet::<T>::block_number().saturating_add(T::RecoveryDelay::get());
+			Recoveries::<T>::insert(
+				&who,
+				RecoveryRequest { new_controller: new_controller.clone(), executable_at },
+			);
+			Self::deposit_event(Event::RecoveryInitiated { who, new_controller, executable_at });
+
+			Ok(())
+		}
+
+		/// Veto the pending recovery of the origin, the EVM key dispatches this with a
+		/// `meta_call`.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::veto_recovery())]
+		pub fn veto_recovery(origin: OriginFor<T>) -> DispatchResult {
+			let who = Self::ensure_account(origin)?;
+
+			ensure!(Recoveries::<T>::take(&who).is_some(), Error::<T>::NoRecovery);
+			Self::deposit_event(Event::RecoveryVetoed { who });
+
+			Ok(())
+		}
+
+		/// Execute the recovery of `who` once its veto period has passed, transferring the whole
+		/// transferable balance of `who` to the new controller.
+		///
+		/// The EVM address of the lost key is then mapped to the new controller instead of `who`,
+		/// so that the funds sent to the address reach the new controller, and the controller of
+		/// `who` set by a rotation is removed.
+		///
+		/// Anyone may execute a due recovery.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::execute_recovery())]
+		pub fn execute_recovery(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			Self::ensure_account(origin)?;
+
+			let request = Recoveries::<T>::get(&who).ok_or(Error::<T>::NoRecovery)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= request.executable_at,
+				Error::<T>::RecoveryNotExecutable
+			);
+			Recoveries::<T>::remove(&who);
+
+			let amount: BalanceOf<T> =
+				T::Currency::reducible_balance(&who, Preservation::Expendable, Fortitude::Polite)
+					.saturated_into::<u128>()
+					.saturated_into();
+			T::Currency::transfer(
+				&who,
+				&request.new_controller,
+				amount,
+				ExistenceRequirement::AllowDeath,
+			)?;
+			if let Some(lost_key) = EvmAddresses::<T>::take(&who) {
+				MappedAccounts::<T>::insert(lost_key, &request.new_controller);
+			}
+			Controllers::<T>::remove(&who);
+			ControllerRotations::<T>::remove(&who);
+			Self::deposit_event(Event::AccountRecovered {
+				who,
+				new_controller: request.new_controller,
+				amount,
+			});
+
+			Ok(())
+		}
+
+		/// Link the signed origin, a native account, to the mapped account `who`, so that both
+		/// control the same identity.
+		///
+		/// The EVM key of `who` signs the native account with the nonce of `who` as a
+		/// [`LINK_ACCOUNTS_TYPE`] struct.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::link_accounts())]
+		pub fn link_accounts(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			nonce: Nonce,
+			signature: MetaSignature,
+		) -> DispatchResult {
+			let account = Self::ensure_account(origin)?;
+
+			ensure!(AccountNonce::<T>::get(&who) == nonce, Error::<T>::NonceError);
+			let message_hash =
+				signature.signed_digest(&Self::link_accounts_message_hash(&who, &account, nonce));
+			let signature = signature.normalize();
+			ensure!(is_canonical_signature(&signature), Error::<T>::MalleableSignature);
+			let evm_address = Self::recover_signer(&who, &message_hash, &signature)
+				.map_err(|_| Error::<T>::InvalidSignature)?;
+			ensure!(!Blocked::<T>::contains_key(evm_address), Error::<T>::AddressBlocked);
+			ensure!(
+				who != account &&
+					!Links::<T>::contains_key(&who) &&
+					!ReverseLinks::<T>::contains_key(&account),
+				Error::<T>::AlreadyLinked
+			);
+
+			T::Currency::hold(
+				&HoldReason::LinkDeposit.into(),
+				&account,
+				Self::deposit_of(T::LinkDeposit::get()),
+			)?;
+			AccountNonce::<T>::insert(&who, nonce.saturating_add(1));
+			Links::<T>::insert(&who, &account);
+			ReverseLinks::<T>::insert(&account, &who);
+			Self::deposit_event(Event::AccountsLinked { who, account });
+
+			Ok(())
+		}
+
+		/// Unlink the signed origin, either the mapped or the native account of a link.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::unlink_accounts())]
+		pub fn unlink_accounts(origin: OriginFor<T>) -> DispatchResult {
+			let signer = Self::ensure_account(origin)?;
+
+			let (who, account) = if let Some(account) = Links::<T>::take(&signer) {
+				(signer, account)
+			} else if let Some(who) = ReverseLinks::<T>::get(&signer) {
+				Links::<T>::remove(&who);
+				(who, signer)
+			} else {
+				return Err(Error::<T>::NotLinked.into())
+			};
+			ReverseLinks::<T>::remove(&account);
+			Self::release_deposit(HoldReason::LinkDeposit, &account, T::LinkDeposit::get());
+			Self::deposit_event(Event::AccountsUnlinked { who, account });
+
+			Ok(())
+		}
+
+		/// Meta-transaction from EVM compatible chains which dispatches `call` as the native
+		/// account linked to `who`, e.g. to operate the staking positions of the native account.
+		///
+		/// The fees are paid by `who`.
+		#[pallet::call_index(10)]
+		#[pallet::weight({
+			let di = call.get_dispatch_info();
+			(
+				T::WeightInfo::meta_call_as_linked().saturating_add(di.weight),
+				di.class
+			)
+		})]
+		pub fn meta_call_as_linked(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			call: Box<<T as Config>::RuntimeCall>,
+			nonce: Nonce,
+			signature: MetaSignature,
+			tip: Option<PaymentBalanceOf<T>>,
+		) -> DispatchResult {
+			// This is an unsigned transaction
+			ensure_none(origin)?;
+
+			let account = Links::<T>::get(&who).ok_or(Error::<T>::NotLinked)?;
+			let call_data = <T as Config>::RuntimeCall::encode(&call);
+			let message_hash =
+				|| Self::call_as_linked_message_hash(&who, &account, &call_data, nonce, tip);
+			Self::execute_meta_transaction_as(
+				&who,
+				account.clone(),
+				*call,
+				nonce,
+				message_hash,
+				&signature,
+				tip,
+				None,
+			)
+		}
+
+		/// Grant `grantee` a permission of `kind` until the block `expires_at`, replacing the
+		/// existing grant of the same kind.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::grant())]
+		pub fn grant(
+			origin: OriginFor<T>,
+			grantee: T::AccountId,
+			kind: GrantKind,
+			expires_at: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let granter = Self::ensure_account(origin)?;
+
+			ensure!(
+				expires_at > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::InvalidExpiry
+			);
+			let key = (granter.clone(), grantee.clone(), kind);
+			if let Some(replaced) = Grants::<T>::get(&key) {
+				GrantExpiries::<T>::remove(replaced.expires_at, &key);
+			} else {
+				T::Currency::hold(
+					&HoldReason::GrantDeposit.into(),
+					&granter,
+					Self::deposit_of(T::GrantDeposit::get()),
+				)?;
+			}
+			Grants::<T>::insert(&key, Grant { expires_at });
+			GrantExpiries::<T>::insert(expires_at, &key, ());
+			Self::deposit_event(Event::Granted { granter, grantee, kind, expires_at });
+
+			Ok(())
+		}
+
+		/// Revoke the permission of `kind` granted to `grantee` before it expires.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::revoke())]
+		pub fn revoke(
+			origin: OriginFor<T>,
+			grantee: T::AccountId,
+			kind: GrantKind,
+		) -> DispatchResult {
+			let granter = Self::ensure_account(origin)?;
+
+			let key = (granter.clone(), grantee.clone(), kind);
+			let grant = Grants::<T>::take(&key).ok_or(Error::<T>::NoGrant)?;
+			GrantExpiries::<T>::remove(grant.expires_at, &key);
+			Self::release_deposit(HoldReason::GrantDeposit, &granter, T::GrantDeposit::get());
+			Self::deposit_event(Event::Revoked { granter, grantee, kind });
+
+			Ok(())
+		}
+
+		/// Meta-transaction from EVM compatible chains which tips in an asset instead of the
+		/// native token, the tip is settled by `TipHandler`.
+		#[pallet::call_index(13)]
+		#[pallet::weight({
+			let di = call.get_dispatch_info();
+			(
+				T::WeightInfo::meta_call()
+					.saturating_add(T::TipHandler::weight())
+					.saturating_add(di.weight),
+				di.class
+			)
+		})]
+		pub fn meta_call_with_asset_tip(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			call: Box<<T as Config>::RuntimeCall>,
+			nonce: Nonce,
+			signature: MetaSignature,
+			tip: AssetTipOf<T>,
+		) -> DispatchResult {
+			// This is an unsigned transaction
+			ensure_none(origin)?;
+
+			let call_data = <T as Config>::RuntimeCall::encode(&call);
+			let message_hash = || Self::asset_tip_message_hash(&who, &call_data, nonce, &tip);
+			// The tip is reverted if the meta-transaction fails to execute
+			T::TipHandler::pay(&who, &tip.asset_id, tip.amount)
+				.map_err(|_err| Error::<T>::PaymentError)?;
+			Self::deposit_event(Event::AssetTipPaid {
+				who: who.clone(),
+				asset_id: tip.asset_id.clone(),
+				amount: tip.amount,
+			});
+			Self::execute_meta_transaction(
+				&who,
+				*call,
+				nonce,
+				message_hash,
+				&signature,
+				None,
+				Some(tip.clone()),
+			)
+		}
+
+		/// Meta-transaction from EVM compatible chains whose call is restricted to `proxy_type`, as
+		/// if it was dispatched through a proxy of that type.
+		///
+		/// The calls the call dispatches, e.g. the calls of a batch, are restricted as well.
+		#[pallet::call_index(14)]
+		#[pallet::weight({
+			let di = call.get_dispatch_info();
+			(
+				T::WeightInfo::meta_call().saturating_add(di.weight),
+				di.class
+			)
+		})]
+		pub fn meta_call_with_proxy_type(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			call: Box<<T as Config>::RuntimeCall>,
+			nonce: Nonce,
+			signature: MetaSignature,
+			tip: Option<PaymentBalanceOf<T>>,
+			proxy_type: T::ProxyType,
+		) -> DispatchResult {
+			// This is an unsigned transaction
+			ensure_none(origin)?;
+
+			let call = *call;
+			let call_data = <T as Config>::RuntimeCall::encode(&call);
+			let message_hash =
+				|| Self::proxy_type_message_hash(&who, &call_data, nonce, tip, &proxy_type);
+			ensure!(Self::call_within_depth_limit(&call_data), Error::<T>::CallTooDeep);
+			ensure!(
+				Self::call_allowed(call.into_ref()) && proxy_type.filter(call.into_ref()),
+				Error::<T>::CallFiltered
+			);
+			let prepared =
+				Self::prepare_meta_transaction(&who, &call, nonce, message_hash, &signature, tip)?;
+
+			let mut origin = Self::meta_call_origin(prepared.evm_address, who.clone());
+			origin.add_filter(move |c: &<T as frame_system::Config>::RuntimeCall| {
+				proxy_type.filter(c)
+			});
+			Self::charge_and_execute(&who, nonce, &call, tip, prepared, || {
+				call.clone().dispatch(origin)
+			})
+		}
+
+		/// Meta-transaction from EVM compatible chains which dispatches `call` as the sub-account
+		/// `index` of the signer, see [`Pallet::sub_account_of`].
+		///
+		/// The fees are paid by `who`.
+		#[pallet::call_index(15)]
+		#[pallet::weight({
+			let di = call.get_dispatch_info();
+			(
+				T::WeightInfo::meta_call().saturating_add(di.weight),
+				di.class
+			)
+		})]
+		pub fn meta_call_as_sub_account(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			call: Box<<T as Config>::RuntimeCall>,
+			nonce: Nonce,
+			signature: MetaSignature,
+			tip: Option<PaymentBalanceOf<T>>,
+			index: u16,
+		) -> DispatchResult {
+			// This is an unsigned transaction
+			ensure_none(origin)?;
+
+			let call = *call;
+			let call_data = <T as Config>::RuntimeCall::encode(&call);
+			let message_hash =
+				|| Self::sub_account_message_hash(&who, &call_data, nonce, tip, index);
+			ensure!(Self::call_within_depth_limit(&call_data), Error::<T>::CallTooDeep);
+			ensure!(Self::call_allowed(call.into_ref()), Error::<T>::CallFiltered);
+			let prepared =
+				Self::prepare_meta_transaction(&who, &call, nonce, message_hash, &signature, tip)?;
+
+			let sub_account = Self::sub_account_of(&prepared.evm_address, index);
+			let origin = Self::meta_call_origin(prepared.evm_address, sub_account);
+			Self::charge_and_execute(&who, nonce, &call, tip, prepared, || {
+				call.clone().dispatch(origin)
+			})
+		}
+
+		/// Meta-transaction from EVM compatible chains which may only be included by `relayer`,
+		/// i.e. in the blocks authored by `relayer`, so that the tip isn't sniped by others.
+		///
+		/// The transaction pool doesn't propagate it, `relayer` submits it to its own node.
+		#[pallet::call_index(16)]
+		#[pallet::weight({
+			let di = call.get_dispatch_info();
+			(
+				T::WeightInfo::meta_call().saturating_add(di.weight),
+				di.class
+			)
+		})]
+		pub fn meta_call_with_relayer(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			call: Box<<T as Config>::RuntimeCall>,
+			nonce: Nonce,
+			signature: MetaSignature,
+			tip: Option<PaymentBalanceOf<T>>,
+			relayer: T::AccountId,
+		) -> DispatchResult {
+			// This is an unsigned transaction
+			ensure_none(origin)?;
+
+			ensure!(Self::is_block_author(&relayer), Error::<T>::NotDesignatedRelayer);
+			let call_data = <T as Config>::RuntimeCall::encode(&call);
+			let message_hash =
+				|| Self::relayer_message_hash(&who, &call_data, nonce, tip, &relayer);
+			Self::execute_meta_transaction(&who, *call, nonce, message_hash, &signature, tip, None)
+		}
+
+		/// Meta-transaction from EVM compatible chains which is only valid from the block
+		/// `valid_from` to the block `valid_until` inclusive, e.g. to sign a payment in advance.
+		#[pallet::call_index(17)]
+		#[pallet::weight({
+			let di = call.get_dispatch_info();
+			(
+				T::WeightInfo::meta_call().saturating_add(di.weight),
+				di.class
+			)
+		})]
+		#[allow(clippy::too_many_arguments)]
+		pub fn meta_call_with_mortality(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			call: Box<<T as Config>::RuntimeCall>,
+			nonce: Nonce,
+			signature: MetaSignature,
+			tip: Option<PaymentBalanceOf<T>>,
+			valid_from: BlockNumberFor<T>,
+			valid_until: BlockNumberFor<T>,
+		) -> DispatchResult {
+			// This is an unsigned transaction
+			ensure_none(origin)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(valid_from <= now && now <= valid_until, Error::<T>::OutsideValidityWindow);
+			let call_data = <T as Config>::RuntimeCall::encode(&call);
+			let message_hash = || {
+				Self::mortality_message_hash(&who, &call_data, nonce, tip, valid_from, valid_until)
+			};
+			Self::execute_meta_transaction(&who, *call, nonce, message_hash, &signature, tip, None)
+		}
+
+		/// Meta-transaction from EVM compatible chains dispatching several calls, a failing call is
+		/// handled as `mode` tells.
+		///
+		/// The fee is the one of the `meta_batch` call itself.
+		#[pallet::call_index(18)]
+		#[pallet::weight({
+			let (weight, class) = Pallet::<T>::batch_dispatch_info(calls);
+			(T::WeightInfo::meta_call().saturating_add(weight), class)
+		})]
+		pub fn meta_batch(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			calls: Vec<<T as Config>::RuntimeCall>,
+			nonce: Nonce,
+			signature: MetaSignature,
+			tip: Option<PaymentBalanceOf<T>>,
+			mode: BatchMode,
+		) -> DispatchResult {
+			// This is an unsigned transaction
+			ensure_none(origin)?;
+
+			let message_hash = || Self::batch_message_hash(&who, &calls, nonce, tip, mode);
+			ensure!(
+				calls.iter().all(|call| Self::call_allowed(call.into_ref())),
+				Error::<T>::CallFiltered
+			);
+			let fee_call = <T as Config>::RuntimeCall::from(Call::<T>::meta_batch {
+				who: who.clone(),
+				calls: calls.clone(),
+				nonce,
+				signature,
+				tip,
+				mode,
+			});
+			ensure!(Self::call_within_depth_limit(&fee_call.encode()), Error::<T>::CallTooDeep);
+			let prepared = Self::prepare_meta_transaction(
+				&who,
+				&fee_call,
+				nonce,
+				message_hash,
+				&signature,
+				tip,
+			)?;
+
+			let origin = Self::meta_call_origin(prepared.evm_address, who.clone());
+			Self::charge_and_execute(&who, nonce, &fee_call, tip, prepared, || {
+				Self::dispatch_batch(&who, calls, mode, origin)
+			})
+		}
+
+		/// Meta-transaction from EVM compatible chains whose call may not weigh more than
+		/// `max_weight`, like the gas limit of an EVM transaction.
+		///
+		/// A call exceeding the limit is rejected before anything is charged.
+		#[pallet::call_index(19)]
+		#[pallet::weight({
+			let di = call.get_dispatch_info();
+			(
+				T::WeightInfo::meta_call().saturating_add(di.weight),
+				di.class
+			)
+		})]
+		pub fn meta_call_with_weight_limit(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			call: Box<<T as Config>::RuntimeCall>,
+			nonce: Nonce,
+			signature: MetaSignature,
+			tip: Option<PaymentBalanceOf<T>>,
+			max_weight: Weight,
+		) -> DispatchResult {
+			// This is an unsigned transaction
+			ensure_none(origin)?;
+
+			ensure!(
+				call.get_dispatch_info().weight.all_lte(max_weight),
+				Error::<T>::WeightLimitExceeded
+			);
+			let call_data = <T as Config>::RuntimeCall::encode(&call);
+			let message_hash =
+				|| Self::weight_limit_message_hash(&who, &call_data, nonce, tip, max_weight);
+			Self::execute_meta_transaction(&who, *call, nonce, message_hash, &signature, tip, None)
+		}
+
+		/// Set the rule overriding `CallFilter` for the calls of `selector`, or remove it if
+		/// `rule` is `None`.
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::set_call_filter_rule())]
+		pub fn set_call_filter_rule(
+			origin: OriginFor<T>,
+			selector: CallSelector,
+			rule: Option<CallFilterRule>,
+		) -> DispatchResult {
+			T::CallFilterOrigin::ensure_origin(origin)?;
+
+			CallFilterOverrides::<T>::set(selector, rule);
+			Self::deposit_event(Event::CallFilterUpdated { selector, rule });
+
+			Ok(())
+		}
+
+		/// Transfer `amount` to the account mapped from `identity`, e.g. from an exchange or a
+		/// faucet to a user who only shared the EVM address.
+		///
+		/// The mapping of a public key is recorded, so that the account can be funded by its
+		/// address afterwards.
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::fund_mapped_account())]
+		pub fn fund_mapped_account(
+			origin: OriginFor<T>,
+			identity: EvmIdentity,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let from = Self::ensure_account(origin)?;
+
+			let (evm_address, account) =
+				Self::mapped_account_of(&identity).ok_or(Error::<T>::UnknownEvmAddress)?;
+			if matches!(identity, EvmIdentity::PublicKey(_)) {
+				Self::note_evm_address(&account, evm_address);
+			}
+			T::Currency::transfer(&from, &account, amount, ExistenceRequirement::KeepAlive)?;
+			Self::deposit_event(Event::MappedAccountFunded { from, evm_address, account, amount });
+
+			Ok(())
+		}
+
+		/// Remove the nonce of the reaped account `who` to keep the state bounded.
+		///
+		/// The nonce restarts from zero, so it's only allowed with [`Config::MetaMortality`].
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::purge_nonce())]
+		pub fn purge_nonce(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			ensure!(T::MetaMortality::get(), Error::<T>::ImmortalPayloads);
+
+			ensure!(
+				!frame_system::Pallet::<T>::account_exists(&who) && !Links::<T>::contains_key(&who),
+				Error::<T>::AccountInUse
+			);
+			ensure!(AccountNonce::<T>::contains_key(&who), Error::<T>::NoNonce);
+			let nonce = AccountNonce::<T>::take(&who);
+			Self::deposit_event(Event::NoncePurged { who, nonce });
+
+			Ok(())
+		}
+
+		/// Approve `spender` to transfer `amount` of `asset_id` from `owner` with an EIP-2612
+		/// style permit signed by the EVM account of `owner`, valid up to block `deadline`.
+		///
+		/// Anyone may submit the permit and pay its fee, the permits have their own nonces, see
+		/// [`PermitNonces`].
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::permit().saturating_add(T::AssetApprovals::weight()))]
+		pub fn permit(
+			origin: OriginFor<T>,
+			owner: T::AccountId,
+			asset_id: T::PermitAssetId,
+			spender: T::AccountId,
+			amount: u128,
+			nonce: Nonce,
+			deadline: BlockNumberFor<T>,
+			signature: MetaSignature,
+		) -> DispatchResult {
+			Self::ensure_account(origin)?;
+
+			ensure!(
+				frame_system::Pallet::<T>::block_number() <= deadline,
+				Error::<T>::PermitExpired
+			);
+			ensure!(PermitNonces::<T>::get(&owner) == nonce, Error::<T>::NonceError);
+			let message_hash = signature.signed_digest(&Self::permit_message_hash(
+				&owner, &asset_id, &spender, amount, nonce, deadline,
+			));
+			let signature = signature.normalize();
+			ensure!(is_canonical_signature(&signature), Error::<T>::MalleableSignature);
+			let evm_address = Self::recover_signer(&owner, &message_hash, &signature)
+				.map_err(|_| Error::<T>::InvalidSignature)?;
+			ensure!(!Blocked::<T>::contains_key(evm_address), Error::<T>::AddressBlocked);
+
+			PermitNonces::<T>::insert(&owner, nonce.saturating_add(1));
+			T::AssetApprovals::approve(&asset_id, &owner, &spender, amount)?;
+			Self::deposit_event(Event::PermitApproved { owner, asset_id, spender, amount });
+
+			Ok(())
+		}
+
+		/// Request to rotate the EVM key controlling `who` to `new_controller`, the rotation is
+		/// executable after `ControllerRotationDelay` blocks unless the current key cancels it.
+		///
+		/// The current key of `who` signs the new controller with the nonce of `who` as a
+		/// [`ROTATE_CONTROLLER_TYPE`] struct.
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::request_controller_rotation())]
+		pub fn request_controller_rotation(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			new_controller: EvmIdentity,
+			nonce: Nonce,
+			signature: MetaSignature,
+		) -> DispatchResult {
+			Self::ensure_account(origin)?;
+
+			let identity = new_controller;
+			let new_controller = identity.evm_address();
+			ensure!(AccountNonce::<T>::get(&who) == nonce, Error::<T>::NonceError);
+			let message_hash = signature.signed_digest(&Self::rotate_controller_message_hash(
+				&who,
+				&new_controller,
+				nonce,
+			));
+			let signature = signature.normalize();
+			ensure!(is_canonical_signature(&signature), Error::<T>::MalleableSignature);
+			let evm_address = Self::recover_signer(&who, &message_hash, &signature)
+				.map_err(|_| Error::<T>::InvalidSignature)?;
+			ensure!(!Blocked::<T>::contains_key(evm_address), Error::<T>::AddressBlocked);
+			ensure!(
+				!ControllerRotations::<T>::contains_key(&who),
+				Error::<T>::RotationAlreadyRequested
+			);
+			ensure!(!Self::controller_in_use(&identity), Error::<T>::ControllerInUse);
+
+			AccountNonce::<T>::insert(&who, nonce.saturating_add(1));
+			let executable_at = frame_system::Pallet::<T>::block_number()
+				.saturating_add(T::ControllerRotationDelay::get());
+			ControllerRotations::<T>::insert(
+				&who,
+				ControllerRotation { new_controller, executable_at },
+			);
+			Self::deposit_event(Event::ControllerRotationRequested {
+				who,
+				new_controller,
+				executable_at,
+			});
+
+			Ok(())
+		}
+
+		/// Cancel the pending rotation of the controller of the origin, the current key
+		/// dispatches this with a `meta_call`.
+		#[pallet::call_index(25)]
+		#[pallet::weight(T::WeightInfo::cancel_controller_rotation())]
+		pub fn cancel_controller_rotation(origin: OriginFor<T>) -> DispatchResult {
+			let who = Self::ensure_account(origin)?;
+
+			ensure!(ControllerRotations::<T>::take(&who).is_some(), Error::<T>::NoRotation);
+			Self::deposit_event(Event::ControllerRotationCancelled { who });
+
+			Ok(())
+		}
+
+		/// Execute the rotation of the controller of `who` once its cancellation period has
+		/// passed, only the new key may sign for `who` afterwards.
+		///
+		/// Anyone may execute a due rotation.
+		#[pallet::call_index(26)]
+		#[pallet::weight(T::WeightInfo::execute_controller_rotation())]
+		pub fn execute_controller_rotation(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+		) -> DispatchResult {
+			Self::ensure_account(origin)?;
+
+			let rotation = ControllerRotations::<T>::get(&who).ok_or(Error::<T>::NoRotation)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= rotation.executable_at,
+				Error::<T>::RotationNotExecutable
+			);
+			// The new controller may have sent a meta-transaction meanwhile
+			ensure!(
+				!Self::controller_in_use(&EvmIdentity::Address(rotation.new_controller)),
+				Error::<T>::ControllerInUse
+			);
+			ControllerRotations::<T>::remove(&who);
+
+			// Update the mapping both ways at once
+			if let Some(old_controller) = EvmAddresses::<T>::get(&who) {
+				MappedAccounts::<T>::remove(old_controller);
+			}
+			EvmAddresses::<T>::insert(&who, rotation.new_controller);
+			MappedAccounts::<T>::insert(rotation.new_controller, &who);
+			Controllers::<T>::insert(&who, rotation.new_controller);
+			Self::deposit_event(Event::ControllerRotated {
+				who,
+				new_controller: rotation.new_controller,
+			});
+
+			Ok(())
+		}
+
+		/// Allow or disallow the calls of `selector` to be dispatched by `meta_call_operational`.
+		#[pallet::call_index(27)]
+		#[pallet::weight(T::WeightInfo::set_operational_call())]
+		pub fn set_operational_call(
+			origin: OriginFor<T>,
+			selector: CallSelector,
+			allowed: bool,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			if allowed {
+				OperationalCalls::<T>::insert(selector, ());
+			} else {
+				OperationalCalls::<T>::remove(selector);
+			}
+			Self::deposit_event(Event::OperationalCallUpdated { selector, allowed });
+
+			Ok(())
+		}
+
+		/// Push a meta-transaction signed for `meta_call` through as an `Operational`
+		/// transaction of `AdminOrigin`, so that an urgent call gets in during congestion with
+		/// the priority of operational transactions.
+		///
+		/// Only the calls listed in [`OperationalCalls`] qualify, including the calls they wrap.
+		/// The fees are paid by `who` as for `meta_call`.
+		#[pallet::call_index(28)]
+		#[pallet::weight({
+			let di = call.get_dispatch_info();
+			(
+				T::WeightInfo::meta_call_operational().saturating_add(di.weight),
+				DispatchClass::Operational
+			)
+		})]
+		pub fn meta_call_operational(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			call: Box<<T as Config>::RuntimeCall>,
+			nonce: Nonce,
+			signature: MetaSignature,
+			tip: Option<PaymentBalanceOf<T>>,
+			version: PayloadVersion,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				Self::operational_call_allowed((*call).into_ref()),
+				Error::<T>::OperationalCallNotAllowed
+			);
+			let call_data = <T as Config>::RuntimeCall::encode(&call);
+			let message_hash =
+				|| Self::meta_call_message_hash(version, &who, &call_data, nonce, tip);
+			Self::execute_meta_transaction(&who, *call, nonce, message_hash, &signature, tip, None)
+		}
+
+		/// Meta-transaction from EVM compatible chains tipping `tip_bps` basis points of the
+		/// inclusion fee, so that the signer doesn't have to guess the fee when signing.
+		///
+		/// The tip is resolved when the meta-transaction is executed and capped so that the fee
+		/// including the tip, as adjusted by `FeeAdjustment`, doesn't exceed `max_fee`, the service
+		/// fee comes on top. A
+		/// meta-transaction whose inclusion fee alone exceeds `max_fee` is rejected before
+		/// anything is charged.
+		#[pallet::call_index(29)]
+		#[pallet::weight({
+			let di = call.get_dispatch_info();
+			(
+				T::WeightInfo::meta_call().saturating_add(di.weight),
+				di.class
+			)
+		})]
+		pub fn meta_call_with_tip_bps(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			call: Box<<T as Config>::RuntimeCall>,
+			nonce: Nonce,
+			signature: MetaSignature,
+			tip_bps: u16,
+			max_fee: PaymentBalanceOf<T>,
+		) -> DispatchResult {
+			// This is an unsigned transaction
+			ensure_none(origin)?;
+
+			let tip =
+				Self::tip_of_bps(&call, tip_bps, max_fee).ok_or(Error::<T>::FeeLimitExceeded)?;
+			let call_data = <T as Config>::RuntimeCall::encode(&call);
+			let message_hash =
+				|| Self::tip_bps_message_hash(&who, &call_data, nonce, tip_bps, max_fee);
+			Self::execute_meta_transaction(
+				&who,
+				*call,
+				nonce,
+				message_hash,
+				&signature,
+				Some(tip),
+				None,
+			)
+		}
+
+		/// Pay the fees of the meta-transactions of `target`, up to `budget` in total, until the
+		/// block `expires_at`, replacing the existing sponsorship of `target` by the caller.
+		///
+		/// The signers don't co-sign anything with the sponsor. The budget isn't reserved, the
+		/// fees are charged from the sponsor as the meta-transactions are executed, and a
+		/// meta-transaction whose fees exceed what is left of the budget is paid by its signer
+		/// and depletes the sponsorship. `SponsorshipDeposit` is held from the sponsor until the
+		/// sponsorship is removed.
+		#[pallet::call_index(30)]
+		#[pallet::weight(T::WeightInfo::sponsor())]
+		pub fn sponsor(
+			origin: OriginFor<T>,
+			target: SponsorshipTarget,
+			budget: BalanceOf<T>,
+			expires_at: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let sponsor = Self::ensure_account(origin)?;
+
+			ensure!(
+				expires_at > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::InvalidExpiry
+			);
+			match Sponsorships::<T>::get(target) {
+				// The deposit is already held
+				Some(existing) if existing.sponsor == sponsor => {},
+				existing => {
+					if let Some(existing) = existing {
+						ensure!(
+							Self::active_sponsorship_of(&target).is_none(),
+							Error::<T>::SponsorshipTaken
+						);
+						Self::remove_sponsorship(target, &existing);
+					}
+					T::Currency::hold(
+						&HoldReason::SponsorshipDeposit.into(),
+						&sponsor,
+						Self::deposit_of(T::SponsorshipDeposit::get()),
+					)?;
+					if let SponsorshipTarget::Signer(_) = target {
+						SignerSponsorships::<T>::mutate(|count| count.saturating_inc());
+					}
+				},
+			}
+			Sponsorships::<T>::insert(
+				target,
+				Sponsorship { sponsor: sponsor.clone(), budget, expires_at },
+			);
+			Self::deposit_event(Event::SponsorshipRegistered {
+				sponsor,
+				target,
+				budget,
+				expires_at,
+			});
+
+			Ok(())
+		}
+
+		/// End the sponsorship of `target`, by its sponsor or by anyone once it has expired.
+		#[pallet::call_index(31)]
+		#[pallet::weight(T::WeightInfo::end_sponsorship())]
+		pub fn end_sponsorship(origin: OriginFor<T>, target: SponsorshipTarget) -> DispatchResult {
+			let who = Self::ensure_account(origin)?;
+
+			let sponsorship = Sponsorships::<T>::get(target).ok_or(Error::<T>::NoSponsorship)?;
+			ensure!(
+				sponsorship.sponsor == who ||
+					frame_system::Pallet::<T>::block_number() >= sponsorship.expires_at,
+				Error::<T>::NotSponsor
+			);
+			Self::remove_sponsorship(target, &sponsorship);
+			Self::deposit_event(Event::SponsorshipEnded { sponsor: sponsorship.sponsor, target });
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> frame_support::traits::OnKilledAccount<T::AccountId> for Pallet<T> {
+		fn on_killed_account(who: &T::AccountId) {
+			if T::PurgeNonceOnReap::get() && T::MetaMortality::get() {
+				if let Ok(nonce) = AccountNonce::<T>::try_get(who) {
+					AccountNonce::<T>::remove(who);
+					Self::deposit_event(Event::NoncePurged { who: who.clone(), nonce });
+				}
+			}
+		}
+	}
+
+	impl<T: Config> Pallet<T>
+	where
+		PaymentBalanceOf<T>: FixedPointOperand,
+		BalanceOf<T>: FixedPointOperand,
+		<T as frame_system::Config>::RuntimeCall:
+			Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+		T: frame_system::Config<AccountId = sp_runtime::AccountId32>,
+	{
+		/// Check a meta-transaction sent as `unsigned_call` may be included in the current block,
+		/// shared by `pre_dispatch` and the `CheckMetaTransaction` extension.
+		pub(crate) fn check_inclusion(
+			unsigned_call: &Call<T>,
+		) -> Result<CheckedMetaTransaction<T>, TransactionValidityError> {
+			if MetaTxCount::<T>::get() >= T::MaxMetaTxPerBlock::get() {
+				return Err(InvalidTransaction::ExhaustsResources.into())
+			}
+			// The block author isn't known to the transaction pool, only the inclusion is checked
+			if let Call::meta_call_with_relayer { relayer, .. } = unsigned_call {
+				if !Self::is_block_author(relayer) {
+					return Err(InvalidMetaTransaction::NotDesignatedRelayer.into())
+				}
+			}
+			let checked = Self::check_unsigned(unsigned_call)?;
+			// Only the next nonce may be included, a future one requires the previous
+			if checked.requires.is_some() {
+				return Err(InvalidTransaction::Future.into())
+			}
+			Ok(checked)
+		}
+
+		/// Record the `signer` of a meta-transaction being included for the dispatch, and count
+		/// it in the block.
+		pub(crate) fn note_pre_dispatched(
+			signer: (
+				Keccak256Signature,
+				EIP712Signature,
+				EvmAddress,
+				EIP712VerifyingContractAddress,
+			),
+		) {
+			PreDispatchedSigner::<T>::put(signer);
+			MetaTxCount::<T>::mutate(|count| *count = count.saturating_add(1));
+		}
+
+		/// Check a meta-transaction sent as `unsigned_call`, shared by `validate_unsigned` and
+		/// `pre_dispatch`.
+		pub(crate) fn check_unsigned(
+			unsigned_call: &Call<T>,
+		) -> Result<CheckedMetaTransaction<T>, TransactionValidityError> {
+			match unsigned_call {
+				Call::meta_call { who, call, nonce, signature, tip, version } => {
+					if !Self::call_allowed((**call).into_ref()) {
+						return Err(InvalidMetaTransaction::CallFiltered.into())
+					}
+					Self::check_meta_transaction(
+						who,
+						call,
+						*nonce,
+						|| {
+							Self::meta_call_message_hash(
+								*version,
+								who,
+								&call.encode(),
+								*nonce,
+								*tip,
+							)
+						},
+						signature,
+						*tip,
+						Zero::zero(),
+					)
+				},
+				Call::meta_typed_call { who, typed_call, nonce, signature, tip } => {
+					let Some(call) = T::TypedCallConverter::convert(typed_call.clone()) else {
+						return Err(InvalidTransaction::Call.into())
+					};
+					if !Self::call_allowed(call.into_ref()) {
+						return Err(InvalidMetaTransaction::CallFiltered.into())
+					}
+					Self::check_meta_transaction(
+						who,
+						&call,
+						*nonce,
+						|| Self::typed_call_message_hash(who, typed_call, *nonce),
+						signature,
+						*tip,
+						Zero::zero(),
+					)
+				},
+				Call::meta_call_as_linked { who, call, nonce, signature, tip } => {
+					let Some(account) = Links::<T>::get(who) else {
+						return Err(InvalidMetaTransaction::NotLinked.into())
+					};
+					if !Self::call_allowed((**call).into_ref()) {
+						return Err(InvalidMetaTransaction::CallFiltered.into())
+					}
+					Self::check_meta_transaction(
+						who,
+						call,
+						*nonce,
+						|| {
+							Self::call_as_linked_message_hash(
+								who,
+								&account,
+								&call.encode(),
+								*nonce,
+								*tip,
+							)
+						},
+						signature,
+						*tip,
+						Zero::zero(),
+					)
+				},
+				Call::meta_call_with_asset_tip { who, call, nonce, signature, tip } => {
+					if !Self::call_allowed((**call).into_ref()) {
+						return Err(InvalidMetaTransaction::CallFiltered.into())
+					}
+					if !T::TipHandler::can_pay(who, &tip.asset_id, tip.amount) {
+						return Err(InvalidTransaction::Payment.into())
+					}
+					// The asset tip doesn't count towards the priority
+					Self::check_meta_transaction(
+						who,
+						call,
+						*nonce,
+						|| Self::asset_tip_message_hash(who, &call.encode(), *nonce, tip),
+						signature,
+						None,
+						Zero::zero(),
+					)
+				},
+				Call::meta_call_with_proxy_type {
+					who,
+					call,
+					nonce,
+					signature,
+					tip,
+					proxy_type,
+				} => {
+					if !Self::call_allowed((**call).into_ref()) ||
+						!proxy_type.filter((**call).into_ref())
+					{
+						return Err(InvalidMetaTransaction::CallFiltered.into())
+					}
+					Self::check_meta_transaction(
+						who,
+						call,
+						*nonce,
+						|| {
+							Self::proxy_type_message_hash(
+								who,
+								&call.encode(),
+								*nonce,
+								*tip,
+								proxy_type,
+							)
+						},
+						signature,
+						*tip,
+						Zero::zero(),
+					)
+				},
+				Call::meta_call_as_sub_account { who, call, nonce, signature, tip, index } => {
+					if !Self::call_allowed((**call).into_ref()) {
+						return Err(InvalidMetaTransaction::CallFiltered.into())
+					}
+					Self::check_meta_transaction(
+						who,
+						call,
+						*nonce,
+						|| {
+							Self::sub_account_message_hash(
+								who,
+								&call.encode(),
+								*nonce,
+								*tip,
+								*index,
+							)
+						},
+						signature,
+						*tip,
+						Zero::zero(),
+					)
+				},
+				Call::meta_call_with_relayer { who, call, nonce, signature, tip, relayer } => {
+					if !Self::call_allowed((**call).into_ref()) {
+						return Err(InvalidMetaTransaction::CallFiltered.into())
+					}
+					let checked = Self::check_meta_transaction(
+						who,
+						call,
+						*nonce,
+						|| Self::relayer_message_hash(who, &call.encode(), *nonce, *tip, relayer),
+						signature,
+						*tip,
+						Zero::zero(),
+					)?;
+					// Only the designated relayer may include it, it isn't gossiped to the nodes
+					// which can't
+					Ok(CheckedMetaTransaction { propagate: false, ..checked })
+				},
+				Call::meta_call_with_mortality {
+					who,
+					call,
+					nonce,
+					signature,
+					tip,
+					valid_from,
+					valid_until,
+				} => {
+					let now = frame_system::Pallet::<T>::block_number();
+					if now < *valid_from {
+						return Err(InvalidTransaction::Future.into())
+					}
+					if now > *valid_until {
+						return Err(InvalidMetaTransaction::Expired.into())
+					}
+					if !Self::call_allowed((**call).into_ref()) {
+						return Err(InvalidMetaTransaction::CallFiltered.into())
+					}
+					let mut checked = Self::check_meta_transaction(
+						who,
+						call,
+						*nonce,
+						|| {
+							Self::mortality_message_hash(
+								who,
+								&call.encode(),
+								*nonce,
+								*tip,
+								*valid_from,
+								*valid_until,
+							)
+						},
+						signature,
+						*tip,
+						Zero::zero(),
+					)?;
+					// Leave the transaction pool once the validity window has passed
+					let remaining = valid_until.saturating_sub(now).saturated_into::<u64>();
+					checked.longevity = checked.longevity.min(remaining.saturating_add(1));
+					Ok(checked)
+				},
+				Call::meta_call_with_weight_limit {
+					who,
+					call,
+					nonce,
+					signature,
+					tip,
+					max_weight,
+				} => {
+					if call.get_dispatch_info().weight.any_gt(*max_weight) {
+						return Err(InvalidMetaTransaction::WeightLimitExceeded.into())
+					}
+					if !Self::call_allowed((**call).into_ref()) {
+						return Err(InvalidMetaTransaction::CallFiltered.into())
+					}
+					Self::check_meta_transaction(
+						who,
+						call,
+						*nonce,
+						|| {
+							Self::weight_limit_message_hash(
+								who,
+								&call.encode(),
+								*nonce,
+								*tip,
+								*max_weight,
+							)
+						},
+						signature,
+						*tip,
+						Zero::zero(),
+					)
+				},
+				Call::meta_call_with_tip_bps { who, call, nonce, signature, tip_bps, max_fee } => {
+					if !Self::call_allowed((**call).into_ref()) {
+						return Err(InvalidMetaTransaction::CallFiltered.into())
+					}
+					let tip = Self::tip_of_bps(call, *tip_bps, *max_fee)
+						.ok_or(InvalidMetaTransaction::FeeLimitExceeded)?;
+					Self::check_meta_transaction(
+						who,
+						call,
+						*nonce,
+						|| {
+							Self::tip_bps_message_hash(
+								who,
+								&call.encode(),
+								*nonce,
+								*tip_bps,
+								*max_fee,
+							)
+						},
+						signature,
+						Some(tip),
+						Zero::zero(),
+					)
+				},
+				Call::meta_batch { who, calls, nonce, signature, tip, mode } => {
+					if !calls.iter().all(|call| Self::call_allowed(call.into_ref())) {
+						return Err(InvalidMetaTransaction::CallFiltered.into())
+					}
+					// The batch pays the fee of the `meta_batch` call itself
+					let call = <T as Config>::RuntimeCall::from(unsigned_call.clone());
+					Self::check_meta_transaction(
+						who,
+						&call,
+						*nonce,
+						|| Self::batch_message_hash(who, calls, *nonce, *tip, *mode),
+						signature,
+						*tip,
+						Zero::zero(),
+					)
+				},
+				Call::meta_transfer { who, dest, amount, nonce, signature } => {
+					// The transfer pays the fee of the `meta_transfer` call itself
+					let call = <T as Config>::RuntimeCall::from(unsigned_call.clone());
+					Self::check_meta_transaction(
+						who,
+						&call,
+						*nonce,
+						|| Self::meta_transfer_message_hash(who, dest, *amount, *nonce),
+						signature,
+						None,
+						*amount,
+					)
+				},
+				_ => Err(InvalidTransaction::Call.into()),
+			}
+		}
+
+		/// Check a meta-transaction of `who` executing `call`, `message_hash` computes the
+		/// EIP-712 hash of the signed payload and `spent` is the amount the payload takes from
+		/// `who` on top of the fees.
+		///
+		/// The signature is only recovered after the cheap checks have passed, so that a payload
+		/// which would be rejected anyway doesn't cost the node a hash and an ECDSA recovery. Only
+		/// the fees the signer can't pay while a sponsorship may apply are checked after the
+		/// recovery, as the sponsorship of the signer is only known then.
+		pub(crate) fn check_meta_transaction(
+			who: &T::AccountId,
+			call: &<T as Config>::RuntimeCall,
+			nonce: Nonce,
+			message_hash: impl Fn() -> Keccak256Signature,
+			signature: &MetaSignature,
+			tip: Option<PaymentBalanceOf<T>>,
+			spent: BalanceOf<T>,
+		) -> Result<CheckedMetaTransaction<T>, TransactionValidityError> {
+			// Skip frame_system::CheckNonZeroSender
+			// Skip frame_system::CheckSpecVersion<Runtime>
+			// Skip frame_system::CheckTxVersion<Runtime>
+			// Skip frame_system::CheckGenesis<Runtime>
+			// Skip frame_system::CheckEra<Runtime>
+
+			// frame_system::CheckNonce<Runtime>, as decided by `ReplayProtection`, the digest is
+			// only computed once and only if needed
+			let digest = core::cell::OnceCell::new();
+			let signed_digest = || *digest.get_or_init(|| signature.signed_digest(&message_hash()));
+			let ReplayTags { provides, requires, longevity } =
+				T::ReplayProtection::check(who, nonce, &signed_digest)?;
+
+			if Self::rate_limit_reached(who) {
+				return Err(InvalidMetaTransaction::RateLimited.into())
+			}
+
+			// Skip frame_system::CheckWeight<Runtime>
+			// it has implemented `validate_unsigned` and `pre_dispatch_unsigned`, we don't need to
+			// do the validate here.
+
+			// pallet_transaction_payment::ChargeTransactionPayment<Runtime>
+			let tip = tip.unwrap_or(0u32.into());
+			if !Self::call_within_depth_limit(&call.encode()) {
+				return Err(InvalidMetaTransaction::CallTooDeep.into())
+			}
+			let len = call.encoded_size();
+			let info = call.get_dispatch_info();
+			Self::check_dispatch_info(&info)?;
+			// TODO: Need check this work with assets-payment
+			// We don't withdraw the fee here, because we can't cache the imbalance
+			// Instead, we check the account has enough fee
+			// I think this is a hack, or the type can't match
+			let fees = Self::meta_transaction_fees(who, call, tip);
+			let usable_balance_for_fees =
+				T::Currency::reducible_balance(who, Preservation::Preserve, Fortitude::Polite)
+					.saturated_into::<u128>();
+			// The fees keep the account alive, the spent amount may reap it if allowed
+			let usable_balance = match T::SpendExistenceRequirement::get() {
+				ExistenceRequirement::KeepAlive => usable_balance_for_fees,
+				ExistenceRequirement::AllowDeath =>
+					T::Currency::reducible_balance(who, Preservation::Expendable, Fortitude::Polite)
+						.saturated_into::<u128>(),
+			};
+			let spent = spent.saturated_into::<u128>();
+			let signer_pays = frame_system::Pallet::<T>::account_exists(who) &&
+				fees <= usable_balance_for_fees &&
+				fees.saturating_add(spent) <= usable_balance;
+			// Unless a sponsorship may apply the fees the signer can't pay are rejected right away
+			if !signer_pays && (spent > usable_balance || !Self::may_be_sponsored(who, call)) {
+				return Err(InvalidTransaction::Payment.into())
+			}
+
+			// Check the signature and get the signer's EVM address, within the domain of
+			// `EIP712VerifyingContractAddress` first
+			let digest = signed_digest();
+			let normalized = signature.normalize();
+			let (evm_address, verifying_contract) =
+				match Self::recover_signer(who, &digest, &normalized) {
+					Ok(evm_address) => (evm_address, T::EIP712VerifyingContractAddress::get()),
+					Err(error) =>
+						Self::recover_signer_in_extra_domains(who, message_hash, signature)
+							.ok_or(error)?,
+				};
+			if Blocked::<T>::contains_key(evm_address) {
+				return Err(InvalidMetaTransaction::AddressBlocked.into())
+			}
+			if !signer_pays &&
+				!Self::active_sponsorship(&evm_address, call)
+					.is_some_and(|(_, sponsorship)| Self::sponsorship_covers(&sponsorship, fees))
+			{
+				return Err(InvalidTransaction::Payment.into())
+			}
+
+			Ok(CheckedMetaTransaction {
+				evm_address,
+				message_hash: digest,
+				signature: normalized,
+				verifying_contract,
+				info,
+				len,
+				tip,
+				provides,
+				requires,
+				longevity,
+				propagate: true,
+			})
+		}
+
+		/// The transaction pool entry of a `checked` meta-transaction.
+		pub(crate) fn valid_transaction(checked: CheckedMetaTransaction<T>) -> ValidTransaction {
+			// Calculate priority
+			let priority = T::PriorityPolicy::priority(&checked.info, checked.len, checked.tip);
+
+			let valid_transaction_builder =
+				ValidTransaction::with_tag_prefix(T::UnsignedTagPrefix::get())
+					.priority(priority)
+					.and_provides(checked.provides)
+					.longevity(checked.longevity)
+					.propagate(checked.propagate);
+			match checked.requires {
+				Some(requires) => valid_transaction_builder.and_requires(requires),
+				None => valid_transaction_builder,
+			}
+			.into()
+		}
+
+		/// Check the call of a meta-transaction fits into a block and is of an allowed dispatch
+		/// class.
+		pub(crate) fn check_dispatch_info(
+			info: &DispatchInfo,
+		) -> Result<(), TransactionValidityError> {
+			match info.class {
+				DispatchClass::Normal => {},
+				DispatchClass::Operational if T::AllowOperationalCalls::get() => {},
+				DispatchClass::Operational =>
+					return Err(InvalidMetaTransaction::DispatchClassNotAllowed.into()),
+				DispatchClass::Mandatory => return Err(InvalidTransaction::BadMandatory.into()),
+			}
+
+			let block_weights = <T as frame_system::Config>::BlockWeights::get();
+			let max_weight =
+				block_weights.get(info.class).max_extrinsic.unwrap_or(block_weights.max_block);
+			if info.weight.any_gt(max_weight) {
+				return Err(InvalidTransaction::ExhaustsResources.into())
+			}
+
+			Ok(())
+		}
+
+		/// The fees a meta-transaction of `who` dispatching `call` is charged: the inclusion fee
+		/// with the `tip`, and the service fee unless it is waived.
+		pub(crate) fn meta_transaction_fees(
+			who: &T::AccountId,
+			call: &<T as Config>::RuntimeCall,
+			tip: PaymentBalanceOf<T>,
+		) -> u128 {
+			let len = call.encoded_size();
+			let info = call.get_dispatch_info();
+			// We shall get the same `fee` later
+			let est_fee = T::FeeAdjustment::adjust_fee(
+				call,
+				pallet_transaction_payment::Pallet::<T>::compute_fee(len as u32, &info, tip),
+			);
+			// We can't get the actual size of the meta-tx itself,
+			// so we have to introducing service fee.
+			let service_fee = if Self::service_fee_waived(call) || Self::has_free_call(who) {
+				0u128
+			} else {
+				T::ServiceFee::get().saturated_into::<u128>()
+			};
+			est_fee.saturated_into::<u128>().saturating_add(service_fee)
+		}
+
+		/// Execute a validated meta-transaction of `who`, `message_hash` computes the EIP-712 hash
+		/// of the signed payload.
+		pub(crate) fn execute_meta_transaction(
+			who: &T::AccountId,
+			call: <T as Config>::RuntimeCall,
+			nonce: Nonce,
+			message_hash: impl Fn() -> Keccak256Signature,
+			signature: &MetaSignature,
+			tip: Option<PaymentBalanceOf<T>>,
+			asset_tip: Option<AssetTipOf<T>>,
+		) -> DispatchResult {
+			Self::execute_meta_transaction_as(
+				who,
+				who.clone(),
+				call,
+				nonce,
+				message_hash,
+				signature,
+				tip,
+				asset_tip,
+			)
+		}
+
+		/// Execute a validated meta-transaction of `who` which dispatches `call` as `account`, the
+		/// fees are paid by `who`.
+		#[allow(clippy::too_many_arguments)]
+		pub(crate) fn execute_meta_transaction_as(
+			who: &T::AccountId,
+			account: T::AccountId,
+			call: <T as Config>::RuntimeCall,
+			nonce: Nonce,
+			message_hash: impl Fn() -> Keccak256Signature,
+			signature: &MetaSignature,
+			tip: Option<PaymentBalanceOf<T>>,
+			asset_tip: Option<AssetTipOf<T>>,
+		) -> DispatchResult {
+			ensure!(Self::call_within_depth_limit(&call.encode()), Error::<T>::CallTooDeep);
+			ensure!(Self::call_allowed(call.into_ref()), Error::<T>::CallFiltered);
+			let prepared =
+				Self::prepare_meta_transaction(who, &call, nonce, message_hash, signature, tip)?;
+
+			// Call
+			let prepared = PreparedMetaTransaction { asset_tip, ..prepared };
+			let origin = Self::meta_call_origin(prepared.evm_address, account);
+			Self::charge_and_execute(who, nonce, &call, tip, prepared, || {
+				call.clone().dispatch(origin)
+			})
+		}
+
+		/// Dispatch the `calls` of a `meta_batch` of `who` with `origin`, each in its own storage
+		/// layer so that a failing call is reverted alone unless `mode` reverts them all.
+		pub(crate) fn dispatch_batch(
+			who: &T::AccountId,
+			calls: Vec<<T as Config>::RuntimeCall>,
+			mode: BatchMode,
+			origin: <T as frame_system::Config>::RuntimeOrigin,
+		) -> DispatchResultWithPostInfo {
+			use frame_support::{dispatch::extract_actual_weight, storage::with_storage_layer};
+
+			let mut weight = T::WeightInfo::meta_call();
+			let result = with_storage_layer(|| -> DispatchResult {
+				for (index, call) in calls.into_iter().enumerate() {
+					let index = index as u32;
+					let info = call.get_dispatch_info();
+					let result = with_storage_layer(|| call.dispatch(origin.clone()));
+					weight.saturating_accrue(extract_actual_weight(&result, &info));
+					let Err(error) = result.map_err(|e| e.error) else { continue };
+					match mode {
+						BatchMode::StopOnError => {
+							Self::deposit_event(Event::BatchInterrupted {
+								who: who.clone(),
+								index,
+								error,
+							});
+							return Ok(())
+						},
+						BatchMode::AllOrNothing => return Err(error),
+						BatchMode::ContinueOnError => {
+							Self::deposit_event(Event::BatchCallFailed {
+								who: who.clone(),
+								index,
+								error,
+							});
+						},
+					}
+				}
+				Ok(())
+			});
+
+			result.map(|()| Some(weight).into()).map_err(|error| {
+				sp_runtime::DispatchErrorWithPostInfo { post_info: Some(weight).into(), error }
+			})
+		}
+
+		/// The account of a signed `origin`, or of the [`MappedOrigin::EvmMapped`] origin the calls
+		/// of meta-transactions are dispatched with if `DispatchAsEvmMapped` is enabled.
+		pub(crate) fn ensure_account(origin: OriginFor<T>) -> Result<T::AccountId, DispatchError> {
+			let origin: <T as Config>::RuntimeOrigin = origin.into();
+			match EnsureEvmMapped::<T::AccountId>::try_origin(origin) {
+				Ok((_, account_id)) => Ok(account_id),
+				Err(origin) => {
+					let origin: OriginFor<T> = origin.into();
+					Ok(ensure_signed(origin)?)
+				},
+			}
+		}
+
+		/// The origin the call of a meta-transaction signed by `evm_address` is dispatched with,
+		/// filtered by `CallFilter` and its overrides.
+		pub(crate) fn meta_call_origin(
+			evm_address: EvmAddress,
+			account: T::AccountId,
+		) -> <T as frame_system::Config>::RuntimeOrigin {
+			let mut origin: <T as frame_system::Config>::RuntimeOrigin =
+				if T::DispatchAsEvmMapped::get() {
+					<T as Config>::RuntimeOrigin::from(Origin::<T>::EvmMapped {
+						evm_address,
+						account_id: account,
+					})
+					.into()
+				} else {
+					RawOrigin::Signed(account).into()
+				};
+			origin.add_filter(Self::call_filter_contains);
+			origin
+		}
+
+		/// Check the signer of a meta-transaction again, find the sponsorship paying its fees, if
+		/// any, charge the service fee and bump the nonce.
+		///
+		/// `message_hash` computes the EIP-712 hash of the signed payload within the domain in use,
+		/// see [`Self::with_verifying_contract`].
+		pub(crate) fn prepare_meta_transaction(
+			who: &T::AccountId,
+			call: &<T as Config>::RuntimeCall,
+			nonce: Nonce,
+			message_hash: impl Fn() -> Keccak256Signature,
+			signature: &MetaSignature,
+			tip: Option<PaymentBalanceOf<T>>,
+		) -> Result<PreparedMetaTransaction<T>, DispatchError> {
+			// Re-validate the signature, the denylist may have changed since the transaction was
+			// validated.
+			let digest = signature.signed_digest(&message_hash());
+			let normalized = signature.normalize();
+			ensure!(is_canonical_signature(&normalized), Error::<T>::MalleableSignature);
+			// Reuse the signer recovered by `pre_dispatch` for the same payload, otherwise recover
+			// it within the same domains as `check_meta_transaction`
+			let (evm_address, verifying_contract) = match PreDispatchedSigner::<T>::take() {
+				Some((hash, pre_dispatched, evm_address, verifying_contract))
+					if hash == digest && pre_dispatched == normalized =>
+					(evm_address, verifying_contract),
+				_ => match Self::recover_signer(who, &digest, &normalized) {
+					Ok(evm_address) => (evm_address, T::EIP712VerifyingContractAddress::get()),
+					Err(_) => Self::recover_signer_in_extra_domains(who, &message_hash, signature)
+						.ok_or(Error::<T>::InvalidSignature)?,
+				},
+			};
+			ensure!(!Blocked::<T>::contains_key(evm_address), Error::<T>::AddressBlocked);
+			ensure!(!Self::rate_limit_reached(who), Error::<T>::RateLimited);
+
+			// A sponsorship whose budget no longer covers the fees is depleted, the signer pays
+			let fees = Self::meta_transaction_fees(who, call, tip.unwrap_or_else(Zero::zero));
+			let sponsorship = match Self::active_sponsorship(&evm_address, call) {
+				Some((target, sponsorship)) if Self::sponsorship_covers(&sponsorship, fees) =>
+					Some((target, sponsorship.sponsor)),
+				Some((target, sponsorship)) => {
+					Self::remove_sponsorship(target, &sponsorship);
+					Self::deposit_event(Event::SponsorshipDepleted {
+						sponsor: sponsorship.sponsor,
+						target,
+						budget: sponsorship.budget,
+					});
+					None
+				},
+				None => None,
+			};
+			let payer =
+				sponsorship.as_ref().map_or_else(|| who.clone(), |(_, sponsor)| sponsor.clone());
+
+			// The calls matching `ServiceFeeWaiver` and the first `FreeCallsPerAccount`
+			// meta-transactions of an account skip the service fee.
+			let service_fee = if Self::service_fee_waived(call) {
+				Self::deposit_event(Event::ServiceFeeWaivedForCall {
+					who: who.clone(),
+					waived_fee: T::ServiceFee::get(),
+				});
+				Zero::zero()
+			} else if Self::has_free_call(who) {
+				FreeCallsUsed::<T>::mutate(who, |used| *used = used.saturating_add(1));
+				Self::deposit_event(Event::ServiceFeeWaived {
+					who: who.clone(),
+					waived_fee: T::ServiceFee::get(),
+				});
+				Zero::zero()
+			} else {
+				// It is possible that an account passed `validate_unsigned` check,
+				// but for some reason, its balance isn't enough for the service fee.
+				use frame_support::traits::tokens::WithdrawReasons;
+				// NOTE: it is possible that the account doesn't have enough fee, which is a
+				// vulnerable.
+				let withdrawn = T::Currency::withdraw(
+					&payer,
+					T::ServiceFee::get(),
+					WithdrawReasons::FEE,
+					ExistenceRequirement::KeepAlive,
+				)
+				.map_err(|_err| Error::<T>::PaymentError)?;
+				let withdrawn_fee = withdrawn.peek();
+				T::OnUnbalancedForServiceFee::on_unbalanced(withdrawn);
+				Self::deposit_event(Event::ServiceFeePaid {
+					who: who.clone(),
+					actual_fee: withdrawn_fee,
+					expected_fee: T::ServiceFee::get(),
+				});
+				withdrawn_fee
+			};
+
+			// Use up the nonce
+			ensure!(T::ReplayProtection::consume(who, nonce, &digest), Error::<T>::NonceError);
+			Self::note_rate_limited_call(who);
+			Self::note_evm_address(who, evm_address);
+
+			Ok(PreparedMetaTransaction {
+				evm_address,
+				verifying_contract,
+				payer,
+				sponsorship: sponsorship.map(|(target, _)| target),
+				service_fee,
+				asset_tip: None,
+			})
+		}
+
+		/// Charge the transaction fee of `call` from the payer of the `prepared` meta-transaction
+		/// of `who` around `execute`, which performs it.
+		///
+		/// The fees are reported in [`Event::MetaTransactionFeeSettled`], including the ones
+		/// charged while preparing the meta-transaction.
+		pub(crate) fn charge_and_execute(
+			who: &T::AccountId,
+			nonce: Nonce,
+			call: &<T as Config>::RuntimeCall,
+			tip: Option<PaymentBalanceOf<T>>,
+			prepared: PreparedMetaTransaction<T>,
+			execute: impl FnOnce() -> DispatchResultWithPostInfo,
+		) -> DispatchResult {
+			let len = call.encoded_size();
+			let info = call.get_dispatch_info();
+			let tip = tip.unwrap_or(0u32.into());
+			// The tip paid to the block author is left out of the fee
+			let author = if T::TipToBlockAuthor::get() { T::BlockAuthor::get() } else { None };
+			let fee_tip = if author.is_some() { Zero::zero() } else { tip };
+			let est_fee = T::FeeAdjustment::adjust_fee(
+				call,
+				pallet_transaction_payment::Pallet::<T>::compute_fee(len as u32, &info, fee_tip),
+			);
+			// Add the service fee
+			let payer = &prepared.payer;
+			let already_withdrawn = T::OnChargeMetaTransaction::withdraw_fee(
+				payer,
+				&call.clone().into(),
+				&info,
+				est_fee,
+				fee_tip,
+			)
+			.map_err(|_err| Error::<T>::PaymentError)?;
+			if let Some(author) = author.filter(|_| !tip.is_zero()) {
+				T::Currency::transfer(
+					payer,
+					&author,
+					tip.saturated_into::<u128>().saturated_into(),
+					ExistenceRequirement::KeepAlive,
+				)
+				.map_err(|_err| Error::<T>::PaymentError)?;
+				Self::deposit_event(Event::TipPaidToAuthor { who: who.clone(), author, tip });
+			}
+
+			let call_result = execute();
+			let post_info = match call_result {
+				Ok(post_info) => post_info,
+				Err(error_and_info) => error_and_info.post_info,
+			};
+			// Deposit the call's result
+			let result = call_result.map(|_| ()).map_err(|e| e.error);
+			if result.is_err() {
+				Self::note_rejection(RejectionCategory::CallFailed);
+			}
+			Self::index_meta_transaction(who, &prepared.evm_address, nonce, result);
+			Self::deposit_event(Event::CallDone {
+				who: who.clone(),
+				call_result,
+				verifying_contract: prepared.verifying_contract,
+			});
+
+			// Nothing can be refunded to an account reaped by the call, the estimated fee is kept
+			let actual_fee = if frame_system::Pallet::<T>::account_exists(payer) {
+				T::FeeAdjustment::adjust_fee(
+					call,
+					pallet_transaction_payment::Pallet::<T>::compute_actual_fee(
+						len as u32, &info, &post_info, fee_tip,
+					),
+				)
+			} else {
+				est_fee
+			};
+			T::OnChargeMetaTransaction::correct_and_deposit_fee(
+				payer,
+				&info,
+				&post_info,
+				actual_fee,
+				fee_tip,
+				already_withdrawn,
+			)
+			.map_err(|_err| Error::<T>::PaymentError)?;
+			let inclusion_fee = actual_fee.saturating_sub(fee_tip);
+			let actual_fee = inclusion_fee.saturating_add(tip);
+			Self::store_receipt(who, nonce, result, actual_fee);
+			if let Some(target) = prepared.sponsorship {
+				let spent = prepared.service_fee.saturated_into::<u128>();
+				Self::spend_sponsorship(target, spent.saturating_add(actual_fee.saturated_into()));
+			}
+			Self::deposit_event(Event::MetaTransactionFeeSettled {
+				who: who.clone(),
+				payer: payer.clone(),
+				service_fee: prepared.service_fee,
+				inclusion_fee,
+				tip,
+				asset_tip: prepared.asset_tip,
+			});
+			Self::deposit_event(Event::TransactionFeePaid { who: who.clone(), actual_fee, tip });
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// A deposit in the balance type of the holds.
+		pub(crate) fn deposit_of(
+			deposit: BalanceOf<T>,
+		) -> <T::Currency as InspectFungible<T::AccountId>>::Balance {
+			deposit.saturated_into::<u128>().saturated_into()
+		}
+
+		/// Release the `deposit` held from `who` for `reason`.
+		///
+		/// The entries created before the deposits were introduced hold nothing, the release is
+		/// best effort so that they are removed all the same.
+		pub(crate) fn release_deposit(
+			reason: HoldReason,
+			who: &T::AccountId,
+			deposit: BalanceOf<T>,
+		) {
+			use frame_support::traits::tokens::Precision;
+
+			let _ = T::Currency::release(
+				&reason.into(),
+				who,
+				Self::deposit_of(deposit),
+				Precision::BestEffort,
+			);
+		}
+
+		/// The weight of the `calls` of a `meta_batch` and its dispatch class, operational only if
+		/// all of them are.
+		pub(crate) fn batch_dispatch_info(
+			calls: &[<T as Config>::RuntimeCall],
+		) -> (Weight, DispatchClass) {
+			let mut weight = Weight::zero();
+			let mut operational = !calls.is_empty();
+			for call in calls {
+				let info = call.get_dispatch_info();
+				weight.saturating_accrue(info.weight);
+				operational &= info.class == DispatchClass::Operational;
+			}
+			let class =
+				if operational { DispatchClass::Operational } else { DispatchClass::Normal };
+			(weight, class)
+		}
+
+		/// Keep the outcome of the meta-transaction `(who, nonce)` for `ReceiptRetention` blocks.
+		pub(crate) fn store_receipt(
+			who: &T::AccountId,
+			nonce: Nonce,
+			result: DispatchResult,
+			actual_fee: PaymentBalanceOf<T>,
+		) {
+			let retention = T::ReceiptRetention::get();
+			if retention.is_zero() {
+				return
+			}
+
+			let now = frame_system::Pallet::<T>::block_number();
+			Receipts::<T>::insert(
+				who,
+				nonce,
+				Receipt {
+					block_number: now,
+					success: result.is_ok(),
+					error: result.err(),
+					actual_fee,
+				},
+			);
+			ReceiptExpiries::<T>::insert(now.saturating_add(retention), (who, nonce), ());
+		}
+
+		/// Write the outcome of the meta-transaction `(evm_address, nonce)` into the off-chain
+		/// index, if `IndexMetaTransactions` is enabled.
+		pub(crate) fn index_meta_transaction(
+			who: &T::AccountId,
+			evm_address: &EvmAddress,
+			nonce: Nonce,
+			result: DispatchResult,
+		) {
+			if !T::IndexMetaTransactions::get() {
+				return
+			}
+
+			let entry = IndexedMetaTransaction {
+				who: who.clone(),
+				block_number: frame_system::Pallet::<T>::block_number(),
+				extrinsic_index: frame_system::Pallet::<T>::extrinsic_index().unwrap_or_default(),
+				success: result.is_ok(),
+				error: result.err(),
+			};
+			sp_io::offchain_index::set(&offchain_index_key(evm_address, nonce), &entry.encode());
+		}
+
+		/// Remove the receipts expiring at block `n`.
+		pub(crate) fn prune_receipts(n: BlockNumberFor<T>) -> Weight {
+			let mut pruned = 0u64;
+			for ((who, nonce), _) in ReceiptExpiries::<T>::drain_prefix(n) {
+				Receipts::<T>::remove(who, nonce);
+				pruned += 1;
+			}
+
+			T::DbWeight::get().reads_writes(pruned.saturating_add(1), pruned.saturating_mul(2))
+		}
+
+		/// The grant of `kind` from `granter` to `grantee`, `None` if it doesn't exist or has
+		/// expired.
+		pub fn active_grant(
+			granter: &T::AccountId,
+			grantee: &T::AccountId,
+			kind: GrantKind,
+		) -> Option<Grant<BlockNumberFor<T>>> {
+			Grants::<T>::get((granter, grantee, kind))
+				.filter(|grant| frame_system::Pallet::<T>::block_number() < grant.expires_at)
+		}
+
+		/// Remove the grants which expired up to block `n` within `remaining_weight`, the sweep
+		/// continues in the next block if the weight runs out.
+		pub(crate) fn sweep_expired_grants(
+			n: BlockNumberFor<T>,
+			remaining_weight: Weight,
+		) -> Weight {
+			let db_weight = T::DbWeight::get();
+			// Reading and writing the cursor
+			let mut used_weight = db_weight.reads_writes(1, 1);
+			if remaining_weight.any_lt(used_weight) {
+				return Weight::zero()
+			}
+			let per_block = db_weight.reads(1);
+			// Removing the grant and releasing its deposit
+			let per_grant = db_weight.reads_writes(3, 4);
+
+			// Grants never expire in the past, start from the current block on the first sweep
+			let mut block = NextGrantSweep::<T>::get().unwrap_or(n);
+			'sweep: while block <= n {
+				used_weight.saturating_accrue(per_block);
+				let mut expiries = GrantExpiries::<T>::drain_prefix(block);
+				loop {
+					if remaining_weight.any_lt(used_weight.saturating_add(per_grant)) {
+						break 'sweep
+					}
+					let Some((key, ())) = expiries.next() else { break };
+					used_weight.saturating_accrue(per_grant);
+					Grants::<T>::remove(&key);
+					Self::release_deposit(HoldReason::GrantDeposit, &key.0, T::GrantDeposit::get());
+				}
+				block.saturating_inc();
+			}
+			NextGrantSweep::<T>::put(block);
+
+			used_weight
+		}
+	}
+
+	impl<T: Config> Pallet<T>
+	where
+		T: frame_system::Config<AccountId = sp_runtime::AccountId32>,
+	{
+		/// Estimate the fees of a meta-transaction, `call_data` is the SCALE encoded call.
+		pub fn estimate_meta_fee(
+			call_data: &[u8],
+			tip: PaymentBalanceOf<T>,
+		) -> Option<MetaFeeEstimate<PaymentBalanceOf<T>>>
+		where
+			PaymentBalanceOf<T>: FixedPointOperand,
+		{
+			Self::estimate_meta_fee_with(call_data, tip, false)
+		}
+
+		/// Estimate the fees `who` pays for a meta-transaction, the service fee is waived if `who`
+		/// has a free call left.
+		pub fn estimate_meta_fee_of(
+			who: &T::AccountId,
+			call_data: &[u8],
+			tip: PaymentBalanceOf<T>,
+		) -> Option<MetaFeeEstimate<PaymentBalanceOf<T>>>
+		where
+			PaymentBalanceOf<T>: FixedPointOperand,
+		{
+			Self::estimate_meta_fee_with(call_data, tip, Self::has_free_call(who))
+		}
+
+		/// The account paying the fees `total` of a meta-transaction of `who` dispatching the
+		/// SCALE encoded `call_data`, the sponsor of the active sponsorship if its budget covers
+		/// them or `who`.
+		///
+		/// The sponsorship of the signer is only found once the EVM address of `who` is recorded.
+		/// Returns `None` if `call_data` can't be decoded.
+		pub fn fee_payer_of(
+			who: &T::AccountId,
+			call_data: &[u8],
+			total: PaymentBalanceOf<T>,
+		) -> Option<T::AccountId> {
+			let call = <T as Config>::RuntimeCall::decode_with_depth_limit(
+				T::MaxCallDepth::get(),
+				&mut &call_data[..],
+			)
+			.ok()?;
+			let (pallet_index, call_index) = Self::call_indices(call.into_ref());
+			let sponsorship = EvmAddresses::<T>::get(who)
+				.map(SponsorshipTarget::Signer)
+				.into_iter()
+				.chain([
+					SponsorshipTarget::Call(CallSelector::Call(pallet_index, call_index)),
+					SponsorshipTarget::Call(CallSelector::Pallet(pallet_index)),
+				])
+				.find_map(|target| Self::active_sponsorship_of(&target));
+			// Like at the dispatch, the signer pays what the first sponsorship doesn't cover
+			Some(match sponsorship {
+				Some(sponsorship)
+					if Self::sponsorship_covers(&sponsorship, total.saturated_into()) =>
+					sponsorship.sponsor,
+				_ => who.clone(),
+			})
+		}
 
-			// TODO: will refactor this in Kevin's way for performance.
-			let eip712_domain = crate::eip712::EIP712Domain {
-				name: T::EIP712Name::get(),
-				version: T::EIP712Version::get(),
-				chain_id: T::EIP712ChainID::get(),
-				verifying_contract: T::EIP712VerifyingContractAddress::get(),
-				salt: None,
+		fn estimate_meta_fee_with(
+			call_data: &[u8],
+			tip: PaymentBalanceOf<T>,
+			free_call: bool,
+		) -> Option<MetaFeeEstimate<PaymentBalanceOf<T>>>
+		where
+			PaymentBalanceOf<T>: FixedPointOperand,
+		{
+			let call = <T as Config>::RuntimeCall::decode_with_depth_limit(
+				T::MaxCallDepth::get(),
+				&mut &call_data[..],
+			)
+			.ok()?;
+			let free_call = free_call || Self::service_fee_waived(&call);
+			let len = call.encoded_size();
+			let info = call.get_dispatch_info();
+			let fee = T::FeeAdjustment::adjust_fee(
+				&call,
+				pallet_transaction_payment::Pallet::<T>::compute_fee(len as u32, &info, tip),
+			);
+
+			let service_fee = if free_call {
+				Zero::zero()
+			} else {
+				T::ServiceFee::get()
+					.saturated_into::<u128>()
+					.saturated_into::<PaymentBalanceOf<T>>()
+			};
+			Some(MetaFeeEstimate {
+				inclusion_fee: fee.saturating_sub(tip),
+				tip,
+				service_fee,
+				total: fee.saturating_add(service_fee),
+			})
+		}
+
+		/// The tip of `tip_bps` basis points of the inclusion fee of `call`, capped so that the
+		/// fee including the tip, as adjusted by `FeeAdjustment`, doesn't exceed `max_fee`, or
+		/// `None` if the inclusion fee alone exceeds it.
+		pub fn tip_of_bps(
+			call: &<T as Config>::RuntimeCall,
+			tip_bps: u16,
+			max_fee: PaymentBalanceOf<T>,
+		) -> Option<PaymentBalanceOf<T>>
+		where
+			PaymentBalanceOf<T>: FixedPointOperand,
+		{
+			let len = call.encoded_size();
+			let info = call.get_dispatch_info();
+			let fee_with_tip = |tip: u128| {
+				T::FeeAdjustment::adjust_fee(
+					call,
+					pallet_transaction_payment::Pallet::<T>::compute_fee(
+						len as u32,
+						&info,
+						tip.saturated_into(),
+					),
+				)
 			};
-			let domain_separator = eip712_domain.separator();
+			let inclusion_fee = fee_with_tip(0);
+			if inclusion_fee > max_fee {
+				return None
+			}
+			let tip =
+				inclusion_fee.saturated_into::<u128>().saturating_mul(tip_bps.into()) / 10_000;
+			if fee_with_tip(tip) <= max_fee {
+				return Some(tip.saturated_into())
+			}
+
+			// The adjustment may not be linear, search the largest tip which fits
+			let (mut fits, mut exceeds) = (0u128, tip);
+			while exceeds - fits > 1 {
+				let middle = fits + (exceeds - fits) / 2;
+				if fee_with_tip(middle) <= max_fee {
+					fits = middle;
+				} else {
+					exceeds = middle;
+				}
+			}
+			Some(fits.saturated_into())
+		}
+
+		/// Whether `call_data` decodes into a call nested no deeper than `MaxCallDepth`.
+		pub(crate) fn call_within_depth_limit(call_data: &[u8]) -> bool {
+			<T as Config>::RuntimeCall::decode_with_depth_limit(
+				T::MaxCallDepth::get(),
+				&mut &call_data[..],
+			)
+			.is_ok()
+		}
+
+		/// Whether `CallFilter` and its overrides allow `call` and every call it wraps.
+		pub(crate) fn call_allowed(call: &<T as frame_system::Config>::RuntimeCall) -> bool {
+			Self::call_filter_contains(call) &&
+				T::InspectInnerCalls::inner_calls(call).into_iter().all(Self::call_allowed)
+		}
 
-			let type_hash = sp_io::hashing::keccak_256(
-				"SubstrateCall(string who,bytes callData,uint64 nonce)".as_bytes(),
+		/// Whether `CallFilter` allows `call`, as overridden by [`CallFilterOverrides`].
+		///
+		/// The calls are selected by the first two bytes of their encoding, the pallet and the
+		/// call indices of `construct_runtime`.
+		pub(crate) fn call_filter_contains(
+			call: &<T as frame_system::Config>::RuntimeCall,
+		) -> bool {
+			let (pallet_index, call_index) = Self::call_indices(call);
+			CallFilterOverrides::<T>::get(CallSelector::Call(pallet_index, call_index))
+				.or_else(|| CallFilterOverrides::<T>::get(CallSelector::Pallet(pallet_index)))
+				.map_or_else(|| T::CallFilter::contains(call), |rule| rule == CallFilterRule::Allow)
+		}
+
+		/// Whether `call` and every call it wraps are listed in [`OperationalCalls`].
+		pub(crate) fn operational_call_allowed(
+			call: &<T as frame_system::Config>::RuntimeCall,
+		) -> bool {
+			let (pallet_index, call_index) = Self::call_indices(call);
+			(OperationalCalls::<T>::contains_key(CallSelector::Call(pallet_index, call_index)) ||
+				OperationalCalls::<T>::contains_key(CallSelector::Pallet(pallet_index))) &&
+				T::InspectInnerCalls::inner_calls(call)
+					.into_iter()
+					.all(Self::operational_call_allowed)
+		}
+
+		/// The pallet and the call indices of `call`, the first two bytes of its encoding.
+		fn call_indices(call: &<T as frame_system::Config>::RuntimeCall) -> (u8, u8) {
+			call.using_encoded(|encoded| {
+				let index = |i: usize| encoded.get(i).copied().unwrap_or_default();
+				(index(0), index(1))
+			})
+		}
+
+		/// The active sponsorship paying the fees of the meta-transactions of `evm_address`
+		/// dispatching `call`, with its target. The sponsorship of the signer comes first, then the
+		/// one of the call and the one of its pallet.
+		pub fn active_sponsorship(
+			evm_address: &EvmAddress,
+			call: &<T as Config>::RuntimeCall,
+		) -> Option<(SponsorshipTarget, SponsorshipOf<T>)> {
+			let (pallet_index, call_index) = Self::call_indices(call.into_ref());
+			[
+				SponsorshipTarget::Signer(*evm_address),
+				SponsorshipTarget::Call(CallSelector::Call(pallet_index, call_index)),
+				SponsorshipTarget::Call(CallSelector::Pallet(pallet_index)),
+			]
+			.into_iter()
+			.find_map(|target| Some((target, Self::active_sponsorship_of(&target)?)))
+		}
+
+		/// The sponsorship of `target`, unless it has expired.
+		pub fn active_sponsorship_of(target: &SponsorshipTarget) -> Option<SponsorshipOf<T>> {
+			Sponsorships::<T>::get(target).filter(|sponsorship| {
+				frame_system::Pallet::<T>::block_number() < sponsorship.expires_at
+			})
+		}
+
+		/// Whether what is left of the budget of `sponsorship` and the balance of its sponsor
+		/// cover `fees`.
+		pub(crate) fn sponsorship_covers(sponsorship: &SponsorshipOf<T>, fees: u128) -> bool {
+			let usable_balance = T::Currency::reducible_balance(
+				&sponsorship.sponsor,
+				Preservation::Preserve,
+				Fortitude::Polite,
 			);
-			// Token::Uint(U256::from(keccak_256(&self.name)))
-			use sp_core::crypto::Ss58Codec;
-			let ss58_who = who.to_ss58check_with_version(T::SS58Prefix::get().into());
-			let hashed_call_data = sp_io::hashing::keccak_256(call_data);
-			let message_hash = sp_io::hashing::keccak_256(&ethabi::encode(&[
-				ethabi::Token::FixedBytes(type_hash.to_vec()),
-				ethabi::Token::FixedBytes(sp_io::hashing::keccak_256(ss58_who.as_bytes()).to_vec()),
-				ethabi::Token::FixedBytes(hashed_call_data.to_vec()),
-				ethabi::Token::Uint(nonce.into()),
+			sponsorship.budget.saturated_into::<u128>() >= fees &&
+				usable_balance.saturated_into::<u128>() >= fees
+		}
+
+		/// Take the `spent` fees from the budget of the sponsorship of `target`, which is
+		/// depleted once its budget is spent.
+		pub(crate) fn spend_sponsorship(target: SponsorshipTarget, spent: u128) {
+			let Some(mut sponsorship) = Sponsorships::<T>::get(target) else { return };
+			sponsorship.budget = sponsorship.budget.saturating_sub(spent.saturated_into());
+			if sponsorship.budget.is_zero() {
+				Self::remove_sponsorship(target, &sponsorship);
+				Self::deposit_event(Event::SponsorshipDepleted {
+					sponsor: sponsorship.sponsor,
+					target,
+					budget: sponsorship.budget,
+				});
+			} else {
+				Sponsorships::<T>::insert(target, sponsorship);
+			}
+		}
+
+		/// Remove the `sponsorship` of `target` and release the deposit of its sponsor.
+		pub(crate) fn remove_sponsorship(
+			target: SponsorshipTarget,
+			sponsorship: &SponsorshipOf<T>,
+		) {
+			Sponsorships::<T>::remove(target);
+			if let SponsorshipTarget::Signer(_) = target {
+				SignerSponsorships::<T>::mutate(|count| count.saturating_dec());
+			}
+			Self::release_deposit(
+				HoldReason::SponsorshipDeposit,
+				&sponsorship.sponsor,
+				T::SponsorshipDeposit::get(),
+			);
+		}
+
+		/// Whether a sponsorship may pay the fees of a meta-transaction of `who` dispatching
+		/// `call`, before its signer is recovered.
+		pub(crate) fn may_be_sponsored(
+			who: &T::AccountId,
+			call: &<T as Config>::RuntimeCall,
+		) -> bool {
+			let (pallet_index, call_index) = Self::call_indices(call.into_ref());
+			let signer_may_be_sponsored = match EvmAddresses::<T>::get(who) {
+				Some(evm_address) =>
+					Self::active_sponsorship_of(&SponsorshipTarget::Signer(evm_address)).is_some(),
+				None => SignerSponsorships::<T>::get() > 0,
+			};
+			signer_may_be_sponsored ||
+				[
+					SponsorshipTarget::Call(CallSelector::Call(pallet_index, call_index)),
+					SponsorshipTarget::Call(CallSelector::Pallet(pallet_index)),
+				]
+				.iter()
+				.any(|target| Self::active_sponsorship_of(target).is_some())
+		}
+
+		/// Count a meta-transaction of the current block rejected or failed for `category` in
+		/// [`RejectionCounts`].
+		pub(crate) fn note_rejection(category: RejectionCategory) {
+			RejectionCounts::<T>::mutate(|stats| stats.note(category));
+		}
+
+		/// Count a meta-transaction of the current block whose dispatch failed with `error` in
+		/// [`RejectionCounts`].
+		pub fn note_dispatch_error(error: &DispatchError) {
+			use frame_support::traits::PalletInfoAccess;
+
+			let category = match error {
+				DispatchError::Module(module_error)
+					if module_error.index as usize == Self::index() =>
+					match Error::<T>::decode(&mut &module_error.error[..]) {
+						Ok(Error::<T>::InvalidSignature | Error::<T>::MalleableSignature) =>
+							RejectionCategory::BadSignature,
+						Ok(Error::<T>::NonceError) => RejectionCategory::StaleNonce,
+						Ok(Error::<T>::PaymentError | Error::<T>::FeeLimitExceeded) =>
+							RejectionCategory::Payment,
+						Ok(Error::<T>::AddressBlocked) => RejectionCategory::Blocked,
+						Ok(Error::<T>::RateLimited) => RejectionCategory::RateLimited,
+						Ok(Error::<T>::CallFiltered | Error::<T>::CallTooDeep) =>
+							RejectionCategory::Filtered,
+						_ => RejectionCategory::Other,
+					},
+				_ => RejectionCategory::Other,
+			};
+			Self::note_rejection(category);
+		}
+
+		/// The meta-transactions rejected or failed in the current block.
+		pub fn rejection_stats() -> RejectionStats {
+			RejectionCounts::<T>::get()
+		}
+
+		/// Whether `relayer` is the author of the current block.
+		pub(crate) fn is_block_author(relayer: &T::AccountId) -> bool {
+			T::BlockAuthor::get().as_ref() == Some(relayer)
+		}
+
+		/// Whether the meta-transactions dispatching `call` skip the service fee.
+		pub(crate) fn service_fee_waived(call: &<T as Config>::RuntimeCall) -> bool {
+			T::ServiceFeeWaiver::contains(call.into_ref())
+		}
+
+		/// Whether the next meta-transaction of `who` skips the service fee.
+		pub(crate) fn has_free_call(who: &T::AccountId) -> bool {
+			FreeCallsUsed::<T>::get(who) < T::FreeCallsPerAccount::get()
+		}
+
+		/// Whether `who` has used up its meta-transactions for the current rate limit window.
+		pub(crate) fn rate_limit_reached(who: &T::AccountId) -> bool {
+			let window = T::RateLimitWindow::get();
+			if window.is_zero() {
+				return false
+			}
+
+			let now = frame_system::Pallet::<T>::block_number();
+			match CallRateLimits::<T>::get(who) {
+				Some(state) if now < state.window_start.saturating_add(window) =>
+					state.calls >= T::MaxCallsPerWindow::get(),
+				_ => false,
+			}
+		}
+
+		/// Record that `who` is mapped from `evm_address`, so that the mapping can be looked up in
+		/// both directions.
+		///
+		/// An address already mapped, e.g. the rotated controller of another account, keeps its
+		/// mapping.
+		pub(crate) fn note_evm_address(who: &T::AccountId, evm_address: EvmAddress) {
+			if !EvmAddresses::<T>::contains_key(who) &&
+				!MappedAccounts::<T>::contains_key(evm_address)
+			{
+				EvmAddresses::<T>::insert(who, evm_address);
+				MappedAccounts::<T>::insert(evm_address, who);
+			}
+		}
+
+		/// The account `evm_address` is mapped to, either recorded by a meta-transaction or a
+		/// rotation of the controller of the account, or derived from the address.
+		pub fn map_evm_address(evm_address: &EvmAddress) -> Option<T::AccountId> {
+			MappedAccounts::<T>::get(evm_address)
+				.or_else(|| T::AddressConverter::try_convert_evm_address(evm_address))
+		}
+
+		/// The account derived from `identity` by the `AddressConverter`, if it can be derived.
+		pub(crate) fn derived_account_of(identity: &EvmIdentity) -> Option<T::AccountId> {
+			match identity {
+				EvmIdentity::Address(evm_address) =>
+					T::AddressConverter::try_convert_evm_address(evm_address),
+				EvmIdentity::PublicKey(public_key) => T::AddressConverter::try_convert(
+					&encode_public_key(public_key, &T::AddressConverter::SECP256K1_PUBLIC_KEY_FORM),
+				),
+			}
+		}
+
+		/// Whether `identity` can't become the controller of another account, as it's already
+		/// mapped or its own account exists.
+		pub(crate) fn controller_in_use(identity: &EvmIdentity) -> bool {
+			MappedAccounts::<T>::contains_key(identity.evm_address()) ||
+				Self::derived_account_of(identity)
+					.is_some_and(|account| frame_system::Pallet::<T>::account_exists(&account))
+		}
+
+		/// The EVM address of `identity` and the account it's mapped to, `None` if only the
+		/// address is given and its account isn't known, see [`Self::map_evm_address`].
+		pub fn mapped_account_of(identity: &EvmIdentity) -> Option<(EvmAddress, T::AccountId)> {
+			match identity {
+				EvmIdentity::Address(evm_address) =>
+					Self::map_evm_address(evm_address).map(|account| (*evm_address, account)),
+				EvmIdentity::PublicKey(public_key) =>
+					Some((evm_address_of(public_key), Self::derived_account_of(identity)?)),
+			}
+		}
+
+		/// The sub-account `index` of `evm_address`, an isolated account controlled by the same EVM
+		/// key: `(b"evm_mapping/sub", evm_address, index).using_encoded(blake2_256)`.
+		pub fn sub_account_of(evm_address: &EvmAddress, index: u16) -> T::AccountId {
+			let entropy = (b"evm_mapping/sub", evm_address, index).using_encoded(blake2_256);
+			Decode::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
+				.expect("infinite length input; no invalid inputs for type; qed")
+		}
+
+		/// The EVM address `who` is mapped from, if it has sent a meta-transaction.
+		pub fn evm_address_of_account(who: &T::AccountId) -> Option<EvmAddress> {
+			EvmAddresses::<T>::get(who)
+		}
+
+		/// The receipt of the meta-transaction of `who` with `nonce`, if it was executed within
+		/// the last `ReceiptRetention` blocks.
+		pub fn receipt(who: &T::AccountId, nonce: Nonce) -> Option<ReceiptOf<T>> {
+			Receipts::<T>::get(who, nonce)
+		}
+
+		/// Count a dispatched meta-transaction of `who` into its rate limit window.
+		pub(crate) fn note_rate_limited_call(who: &T::AccountId) {
+			let window = T::RateLimitWindow::get();
+			if window.is_zero() {
+				return
+			}
+
+			let now = frame_system::Pallet::<T>::block_number();
+			CallRateLimits::<T>::mutate(who, |state| match state {
+				Some(state) if now < state.window_start.saturating_add(window) =>
+					state.calls = state.calls.saturating_add(1),
+				_ => *state = Some(RateLimitState { window_start: now, calls: 1 }),
+			});
+		}
+
+		/// Recover the uncompressed secp256k1 public key (without the `0x04` prefix) which signed
+		/// `message_hash`, `None` if the signature is malformed.
+		///
+		/// `message_hash` is the final hash the wallet signed, e.g. the result of
+		/// [`Self::eip712_message_hash`].
+		pub fn ecdsa_recover_public_key(
+			signature: &EIP712Signature,
+			message_hash: &Keccak256Signature,
+		) -> Option<[u8; 64]> {
+			sp_io::crypto::secp256k1_ecdsa_recover(signature, message_hash).ok()
+		}
+
+		/// Check `signature` is signed by `who` for `message_hash`, returning the signer's EVM
+		/// address.
+		///
+		/// The signer is the key `who` is derived from, or the key of its controller once it was
+		/// rotated, see [`Controllers`].
+		pub(crate) fn recover_signer(
+			who: &T::AccountId,
+			message_hash: &Keccak256Signature,
+			signature: &EIP712Signature,
+		) -> Result<EvmAddress, InvalidTransaction> {
+			if !is_canonical_signature(signature) {
+				return Err(InvalidTransaction::Custom(
+					InvalidMetaTransaction::MalleableSignature as u8,
+				))
+			}
+			let Some(public_key) = Self::ecdsa_recover_public_key(signature, message_hash) else {
+				return Err(InvalidTransaction::Call)
+			};
+			// Only the rotated controller signs for the account, not the key it's derived from
+			if let Some(controller) = Controllers::<T>::get(who) {
+				let evm_address = evm_address_of(&public_key);
+				if evm_address != controller {
+					return Err(InvalidTransaction::BadSigner)
+				}
+				return Ok(evm_address)
+			}
+
+			// Deserialize the actual caller
+			let encoded_public_key = encode_public_key(
+				&public_key,
+				&<T as Config>::AddressConverter::SECP256K1_PUBLIC_KEY_FORM,
+			);
+			let Some(decoded_account) =
+				<T as Config>::AddressConverter::try_convert(&encoded_public_key)
+			else {
+				return Err(InvalidTransaction::Call)
+			};
+			if who != &decoded_account {
+				return Err(InvalidTransaction::BadSigner)
+			}
+
+			Ok(evm_address_of(&public_key))
+		}
+
+		/// Recover the signer of `signature` for the EIP-712 hash computed by `message_hash` within
+		/// the domains of `EIP712ExtraVerifyingContracts`, returning its EVM address and the
+		/// verifying contract of the first domain `who` signed within.
+		pub(crate) fn recover_signer_in_extra_domains(
+			who: &T::AccountId,
+			message_hash: impl Fn() -> Keccak256Signature,
+			signature: &MetaSignature,
+		) -> Option<(EvmAddress, EIP712VerifyingContractAddress)> {
+			let normalized = signature.normalize();
+			T::EIP712ExtraVerifyingContracts::get().into_iter().find_map(|contract| {
+				let message_hash = Self::with_verifying_contract(contract, &message_hash);
+				Self::recover_signer(who, &signature.signed_digest(&message_hash), &normalized)
+					.ok()
+					.map(|evm_address| (evm_address, contract))
+			})
+		}
+
+		/// Whether `signature` is signed by the EVM account mapped to `who` for `struct_hash`, the
+		/// EIP-712 struct hash of a statement signed within the domain of this pallet.
+		///
+		/// Other pallets can use this to verify EVM-signed statements, e.g. airdrop claims, with
+		/// the same domain and account derivation as meta-transactions.
+		pub fn verify_signed_by(
+			who: &T::AccountId,
+			struct_hash: &Keccak256Signature,
+			signature: &MetaSignature,
+		) -> bool {
+			let message_hash = signature.signed_digest(&Self::eip712_typed_data_hash(struct_hash));
+			Self::recover_signer(who, &message_hash, &signature.normalize()).is_ok()
+		}
+
+		/// The EIP-712 hash a wallet signs for a `meta_call` of `who` dispatching the SCALE encoded
+		/// `call_data` with `nonce`.
+		pub fn eip712_message_hash(
+			who: T::AccountId,
+			call_data: &[u8],
+			nonce: Nonce,
+		) -> Keccak256Signature {
+			Self::meta_call_message_hash(PayloadVersion::V0, &who, call_data, nonce, None)
+		}
+
+		/// The EIP-712 hash a wallet signs for a `meta_call` of the payload `version`.
+		pub fn meta_call_message_hash(
+			version: PayloadVersion,
+			who: &T::AccountId,
+			call_data: &[u8],
+			nonce: Nonce,
+			tip: Option<PaymentBalanceOf<T>>,
+		) -> Keccak256Signature {
+			let tip = tip.unwrap_or_else(Zero::zero).saturated_into::<u128>();
+			let message_hash =
+				meta_call_struct_hash(version, &Self::ss58_address_of(who), call_data, nonce, tip);
+			Self::eip712_typed_data_hash(&message_hash)
+		}
+
+		/// The EIP-712 hash of a [`TypedCall`] payload.
+		pub(crate) fn typed_call_message_hash(
+			who: &T::AccountId,
+			typed_call: &TypedCallOf<T>,
+			nonce: Nonce,
+		) -> Keccak256Signature {
+			use sp_io::hashing::keccak_256;
+
+			let hashed_who = keccak_256(&Self::ss58_address_of(who));
+			let encoded = match typed_call {
+				TypedCall::BalanceTransfer { to, amount } => abi::encode(&[
+					Token::FixedBytes(&keccak_256(BALANCE_TRANSFER_TYPE)),
+					Token::FixedBytes(&hashed_who),
+					Token::FixedBytes(&keccak_256(&Self::ss58_address_of(to))),
+					Token::Uint((*amount).saturated_into::<u128>().into()),
+					Token::Uint(nonce.into()),
+				]),
+				TypedCall::Remark { remark } => abi::encode(&[
+					Token::FixedBytes(&keccak_256(REMARK_TYPE)),
+					Token::FixedBytes(&hashed_who),
+					Token::FixedBytes(&keccak_256(remark)),
+					Token::Uint(nonce.into()),
+				]),
+			};
+
+			Self::eip712_typed_data_hash(&keccak_256(&encoded))
+		}
+
+		/// The EIP-712 hash of a `meta_transfer` payload.
+		pub(crate) fn meta_transfer_message_hash(
+			who: &T::AccountId,
+			dest: &T::AccountId,
+			amount: BalanceOf<T>,
+			nonce: Nonce,
+		) -> Keccak256Signature {
+			use sp_io::hashing::keccak_256;
+
+			let message_hash = keccak_256(&abi::encode(&[
+				Token::FixedBytes(&keccak_256(META_TRANSFER_TYPE)),
+				Token::FixedBytes(&keccak_256(&Self::ss58_address_of(who))),
+				Token::FixedBytes(&keccak_256(&Self::ss58_address_of(dest))),
+				Token::Uint(amount.saturated_into::<u128>().into()),
+				Token::Uint(nonce.into()),
+			]));
+
+			Self::eip712_typed_data_hash(&message_hash)
+		}
+
+		/// The EIP-712 hash of a `permit` of `owner` approving `spender` to transfer `amount` of
+		/// `asset_id`.
+		pub fn permit_message_hash(
+			owner: &T::AccountId,
+			asset_id: &T::PermitAssetId,
+			spender: &T::AccountId,
+			amount: u128,
+			nonce: Nonce,
+			deadline: BlockNumberFor<T>,
+		) -> Keccak256Signature {
+			use sp_io::hashing::keccak_256;
+
+			let message_hash = keccak_256(&abi::encode(&[
+				Token::FixedBytes(&keccak_256(PERMIT_TYPE)),
+				Token::FixedBytes(&keccak_256(&Self::ss58_address_of(owner))),
+				Token::FixedBytes(&keccak_256(&asset_id.encode())),
+				Token::FixedBytes(&keccak_256(&Self::ss58_address_of(spender))),
+				Token::Uint(amount.into()),
+				Token::Uint(nonce.into()),
+				Token::Uint(deadline.saturated_into::<u64>().into()),
+			]));
+
+			Self::eip712_typed_data_hash(&message_hash)
+		}
+
+		/// The EIP-712 hash of a `meta_call_as_linked` payload, `account` is the native account
+		/// linked to `who`.
+		pub fn call_as_linked_message_hash(
+			who: &T::AccountId,
+			account: &T::AccountId,
+			call_data: &[u8],
+			nonce: Nonce,
+			tip: Option<PaymentBalanceOf<T>>,
+		) -> Keccak256Signature {
+			use sp_io::hashing::keccak_256;
+
+			let tip = tip.unwrap_or_else(Zero::zero).saturated_into::<u128>();
+			let message_hash = keccak_256(&abi::encode(&[
+				Token::FixedBytes(&keccak_256(SUBSTRATE_CALL_AS_LINKED_TYPE)),
+				Token::FixedBytes(&keccak_256(&Self::ss58_address_of(who))),
+				Token::FixedBytes(&keccak_256(&Self::ss58_address_of(account))),
+				Token::FixedBytes(&keccak_256(call_data)),
+				Token::Uint(nonce.into()),
+				Token::Uint(tip.into()),
+			]));
+
+			Self::eip712_typed_data_hash(&message_hash)
+		}
+
+		/// The EIP-712 hash a wallet signs for a `meta_call_with_asset_tip`.
+		pub fn asset_tip_message_hash(
+			who: &T::AccountId,
+			call_data: &[u8],
+			nonce: Nonce,
+			tip: &AssetTipOf<T>,
+		) -> Keccak256Signature {
+			use sp_io::hashing::keccak_256;
+
+			let message_hash = keccak_256(&abi::encode(&[
+				Token::FixedBytes(&keccak_256(SUBSTRATE_CALL_WITH_ASSET_TIP_TYPE)),
+				Token::FixedBytes(&keccak_256(&Self::ss58_address_of(who))),
+				Token::FixedBytes(&keccak_256(call_data)),
+				Token::Uint(nonce.into()),
+				Token::FixedBytes(&keccak_256(&tip.asset_id.encode())),
+				Token::Uint(tip.amount.into()),
+			]));
+
+			Self::eip712_typed_data_hash(&message_hash)
+		}
+
+		/// The EIP-712 hash a wallet signs for a `meta_call_with_proxy_type`.
+		pub fn proxy_type_message_hash(
+			who: &T::AccountId,
+			call_data: &[u8],
+			nonce: Nonce,
+			tip: Option<PaymentBalanceOf<T>>,
+			proxy_type: &T::ProxyType,
+		) -> Keccak256Signature {
+			use sp_io::hashing::keccak_256;
+
+			let tip = tip.unwrap_or_else(Zero::zero).saturated_into::<u128>();
+			let message_hash = keccak_256(&abi::encode(&[
+				Token::FixedBytes(&keccak_256(SUBSTRATE_CALL_WITH_PROXY_TYPE_TYPE)),
+				Token::FixedBytes(&keccak_256(&Self::ss58_address_of(who))),
+				Token::FixedBytes(&keccak_256(call_data)),
+				Token::Uint(nonce.into()),
+				Token::Uint(tip.into()),
+				Token::FixedBytes(&keccak_256(&proxy_type.encode())),
+			]));
+
+			Self::eip712_typed_data_hash(&message_hash)
+		}
+
+		/// The EIP-712 hash a wallet signs for a `meta_call_as_sub_account`.
+		pub fn sub_account_message_hash(
+			who: &T::AccountId,
+			call_data: &[u8],
+			nonce: Nonce,
+			tip: Option<PaymentBalanceOf<T>>,
+			index: u16,
+		) -> Keccak256Signature {
+			use sp_io::hashing::keccak_256;
+
+			let tip = tip.unwrap_or_else(Zero::zero).saturated_into::<u128>();
+			let message_hash = keccak_256(&abi::encode(&[
+				Token::FixedBytes(&keccak_256(SUBSTRATE_CALL_AS_SUB_ACCOUNT_TYPE)),
+				Token::FixedBytes(&keccak_256(&Self::ss58_address_of(who))),
+				Token::FixedBytes(&keccak_256(call_data)),
+				Token::Uint(nonce.into()),
+				Token::Uint(tip.into()),
+				Token::Uint(index.into()),
+			]));
+
+			Self::eip712_typed_data_hash(&message_hash)
+		}
+
+		/// The EIP-712 hash a wallet signs for a `meta_call_with_relayer`.
+		pub fn relayer_message_hash(
+			who: &T::AccountId,
+			call_data: &[u8],
+			nonce: Nonce,
+			tip: Option<PaymentBalanceOf<T>>,
+			relayer: &T::AccountId,
+		) -> Keccak256Signature {
+			use sp_io::hashing::keccak_256;
+
+			let tip = tip.unwrap_or_else(Zero::zero).saturated_into::<u128>();
+			let message_hash = keccak_256(&abi::encode(&[
+				Token::FixedBytes(&keccak_256(SUBSTRATE_CALL_WITH_RELAYER_TYPE)),
+				Token::FixedBytes(&keccak_256(&Self::ss58_address_of(who))),
+				Token::FixedBytes(&keccak_256(call_data)),
+				Token::Uint(nonce.into()),
+				Token::Uint(tip.into()),
+				Token::FixedBytes(&keccak_256(&Self::ss58_address_of(relayer))),
+			]));
+
+			Self::eip712_typed_data_hash(&message_hash)
+		}
+
+		/// The EIP-712 hash a wallet signs for a `meta_call_with_mortality`.
+		pub fn mortality_message_hash(
+			who: &T::AccountId,
+			call_data: &[u8],
+			nonce: Nonce,
+			tip: Option<PaymentBalanceOf<T>>,
+			valid_from: BlockNumberFor<T>,
+			valid_until: BlockNumberFor<T>,
+		) -> Keccak256Signature {
+			use sp_io::hashing::keccak_256;
+
+			let tip = tip.unwrap_or_else(Zero::zero).saturated_into::<u128>();
+			let message_hash = keccak_256(&abi::encode(&[
+				Token::FixedBytes(&keccak_256(SUBSTRATE_CALL_WITH_MORTALITY_TYPE)),
+				Token::FixedBytes(&keccak_256(&Self::ss58_address_of(who))),
+				Token::FixedBytes(&keccak_256(call_data)),
+				Token::Uint(nonce.into()),
+				Token::Uint(tip.into()),
+				Token::Uint(valid_from.saturated_into::<u64>().into()),
+				Token::Uint(valid_until.saturated_into::<u64>().into()),
+			]));
+
+			Self::eip712_typed_data_hash(&message_hash)
+		}
+
+		/// The EIP-712 hash a wallet signs for a `meta_call_with_weight_limit`.
+		pub fn weight_limit_message_hash(
+			who: &T::AccountId,
+			call_data: &[u8],
+			nonce: Nonce,
+			tip: Option<PaymentBalanceOf<T>>,
+			max_weight: Weight,
+		) -> Keccak256Signature {
+			use sp_io::hashing::keccak_256;
+
+			let tip = tip.unwrap_or_else(Zero::zero).saturated_into::<u128>();
+			let message_hash = keccak_256(&abi::encode(&[
+				Token::FixedBytes(&keccak_256(SUBSTRATE_CALL_WITH_WEIGHT_LIMIT_TYPE)),
+				Token::FixedBytes(&keccak_256(&Self::ss58_address_of(who))),
+				Token::FixedBytes(&keccak_256(call_data)),
+				Token::Uint(nonce.into()),
+				Token::Uint(tip.into()),
+				Token::Uint(max_weight.ref_time().into()),
+				Token::Uint(max_weight.proof_size().into()),
+			]));
+
+			Self::eip712_typed_data_hash(&message_hash)
+		}
+
+		/// The EIP-712 hash a wallet signs for a `meta_call_with_tip_bps`.
+		pub fn tip_bps_message_hash(
+			who: &T::AccountId,
+			call_data: &[u8],
+			nonce: Nonce,
+			tip_bps: u16,
+			max_fee: PaymentBalanceOf<T>,
+		) -> Keccak256Signature {
+			use sp_io::hashing::keccak_256;
+
+			let max_fee = max_fee.saturated_into::<u128>();
+			let message_hash = keccak_256(&abi::encode(&[
+				Token::FixedBytes(&keccak_256(SUBSTRATE_CALL_WITH_TIP_BPS_TYPE)),
+				Token::FixedBytes(&keccak_256(&Self::ss58_address_of(who))),
+				Token::FixedBytes(&keccak_256(call_data)),
+				Token::Uint(nonce.into()),
+				Token::Uint(tip_bps.into()),
+				Token::Uint(max_fee.into()),
+			]));
+
+			Self::eip712_typed_data_hash(&message_hash)
+		}
+
+		/// The EIP-712 hash a wallet signs for a `meta_batch` of `calls`.
+		pub fn batch_message_hash(
+			who: &T::AccountId,
+			calls: &[<T as Config>::RuntimeCall],
+			nonce: Nonce,
+			tip: Option<PaymentBalanceOf<T>>,
+			mode: BatchMode,
+		) -> Keccak256Signature {
+			use sp_io::hashing::keccak_256;
+			use substrate_eip712::typed_data::{hash_struct, Member, Types, Value};
+
+			// The members of `SUBSTRATE_CALLS_TYPE`
+			let types = Types::from([(
+				"SubstrateCalls",
+				sp_std::vec![
+					Member::new("who", "string"),
+					Member::new("callHashes", "bytes32[]"),
+					Member::new("nonce", "uint64"),
+					Member::new("tip", "uint128"),
+					Member::new("mode", "uint8"),
+				],
+			)]);
+			let call_hashes = calls
+				.iter()
+				.map(|call| Value::FixedBytes(keccak_256(&call.encode()).to_vec()))
+				.collect();
+			let tip = tip.unwrap_or_else(Zero::zero).saturated_into::<u128>();
+			let message_hash = hash_struct(
+				"SubstrateCalls",
+				&[
+					Value::String(Self::ss58_address_of(who)),
+					Value::Array(call_hashes),
+					Value::Uint(nonce.into()),
+					Value::Uint(tip.into()),
+					Value::Uint((mode as u8).into()),
+				],
+				&types,
+			)
+			.expect("the values match the members of the type; qed");
+
+			Self::eip712_typed_data_hash(&message_hash)
+		}
+
+		/// The EIP-712 hash of a `request_controller_rotation` payload.
+		pub(crate) fn rotate_controller_message_hash(
+			who: &T::AccountId,
+			new_controller: &EvmAddress,
+			nonce: Nonce,
+		) -> Keccak256Signature {
+			use sp_io::hashing::keccak_256;
+
+			let message_hash = keccak_256(&abi::encode(&[
+				Token::FixedBytes(&keccak_256(ROTATE_CONTROLLER_TYPE)),
+				Token::FixedBytes(&keccak_256(&Self::ss58_address_of(who))),
+				Token::Address(*new_controller),
+				Token::Uint(nonce.into()),
+			]));
+
+			Self::eip712_typed_data_hash(&message_hash)
+		}
+
+		/// The EIP-712 hash of a `link_accounts` payload.
+		pub(crate) fn link_accounts_message_hash(
+			who: &T::AccountId,
+			account: &T::AccountId,
+			nonce: Nonce,
+		) -> Keccak256Signature {
+			use sp_io::hashing::keccak_256;
+
+			let message_hash = keccak_256(&abi::encode(&[
+				Token::FixedBytes(&keccak_256(LINK_ACCOUNTS_TYPE)),
+				Token::FixedBytes(&keccak_256(&Self::ss58_address_of(who))),
+				Token::FixedBytes(&keccak_256(&Self::ss58_address_of(account))),
+				Token::Uint(nonce.into()),
 			]));
 
-			let typed_data_hash_input = &vec![
-				crate::encode::SolidityDataType::String("\x19\x01"),
-				crate::encode::SolidityDataType::Bytes(&domain_separator),
-				crate::encode::SolidityDataType::Bytes(&message_hash),
-			];
-			let bytes = crate::encode::abi::encode_packed(typed_data_hash_input);
-			sp_io::hashing::keccak_256(bytes.as_slice())
+			Self::eip712_typed_data_hash(&message_hash)
+		}
+
+		/// The SS58 address of `who` as it is shown in the signed payloads.
+		fn ss58_address_of(who: &T::AccountId) -> Vec<u8> {
+			use sp_core::crypto::Ss58Codec;
+			who.to_ss58check_with_version(T::SS58Prefix::get().into()).into_bytes()
+		}
+
+		/// Hash a struct hash with the EIP-712 domain of this chain, the result is what the wallet
+		/// signs.
+		pub(crate) fn eip712_typed_data_hash(message_hash: &[u8; 32]) -> Keccak256Signature {
+			// TODO: will refactor this in Kevin's way for performance.
+			crate::eip712::typed_data_hash(&Self::eip712_domain().separator(), message_hash)
+		}
+
+		/// Compute the EIP-712 hashes of `f`, e.g. [`Self::meta_call_message_hash`], within the
+		/// domain of `contract` instead of `EIP712VerifyingContractAddress`.
+		pub fn with_verifying_contract<R>(
+			mut contract: EIP712VerifyingContractAddress,
+			f: impl FnOnce() -> R,
+		) -> R {
+			verifying_contract::using(&mut contract, f)
+		}
+
+		/// The EIP-712 domain of the payloads signed for this chain.
+		pub fn eip712_domain() -> crate::eip712::EIP712Domain {
+			crate::eip712::EIP712Domain {
+				name: T::EIP712Name::get(),
+				version: T::EIP712Version::get(),
+				chain_id: T::EIP712ChainID::get(),
+				verifying_contract: verifying_contract::with(|contract| *contract)
+					.unwrap_or_else(T::EIP712VerifyingContractAddress::get),
+				salt: match T::EIP712ParaId::get() {
+					Some(para_id) => Some(parachain_eip712_salt(para_id, T::EIP712Salt::get())),
+					None => T::EIP712Salt::get(),
+				},
+			}
 		}
 	}
 }
@@ -0,0 +1,176 @@
+// This file is part of EVM Account Mapping Pallet.
+
+// Copyright (C) HashForest Technology Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage migrations for the pallet.
+
+use super::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{GetStorageVersion, OnRuntimeUpgrade},
+};
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+/// Migrate the pallet storage from the unversioned layout (v0) to v1.
+pub mod v1 {
+	use super::*;
+
+	/// The v0 layout has no storage version, `AccountNonce` entries were written with
+	/// `ValueQuery` so that a zero nonce may or may not have been persisted.
+	///
+	/// v1 formalizes the map: only accounts which have actually used the pallet (nonce > 0) keep
+	/// an entry, then the storage version is set to 1.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 0 {
+				log::info!(
+					target: LOG_TARGET,
+					"skipping v1 migration: on-chain storage version is {:?}",
+					on_chain_version,
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0u64;
+			let mut removed = 0u64;
+			AccountNonce::<T>::translate::<u64, _>(|_who, nonce| {
+				translated += 1;
+				if nonce == 0 {
+					removed += 1;
+					None
+				} else {
+					Some(nonce)
+				}
+			});
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+
+			log::info!(
+				target: LOG_TARGET,
+				"migrated {} AccountNonce entries to v1, removed {} empty entries",
+				translated,
+				removed,
+			);
+
+			T::DbWeight::get()
+				.reads_writes(translated.saturating_add(1), translated.saturating_add(1))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			let non_zero =
+				AccountNonce::<T>::iter_values().filter(|nonce| *nonce > 0).count() as u64;
+			Ok(non_zero.encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let expected = u64::decode(&mut &state[..])
+				.map_err(|_| "failed to decode the pre-upgrade state")?;
+
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() == 1,
+				"storage version should be 1 after the migration"
+			);
+			ensure!(
+				AccountNonce::<T>::iter_values().all(|nonce| nonce > 0),
+				"empty AccountNonce entries should have been removed"
+			);
+			ensure!(
+				AccountNonce::<T>::iter_values().count() as u64 == expected,
+				"AccountNonce entries with a non-zero nonce should be kept"
+			);
+
+			Ok(())
+		}
+	}
+}
+
+/// Migrate the pallet storage from v1 to v2.
+pub mod v2 {
+	use super::*;
+	use frame_system::pallet_prelude::BlockNumberFor;
+
+	/// The v1 layout of [`Receipt`], without the actual fee.
+	#[derive(Encode, Decode)]
+	pub struct ReceiptV1<BlockNumber> {
+		pub block_number: BlockNumber,
+		pub success: bool,
+		pub error: Option<sp_runtime::DispatchError>,
+	}
+
+	/// v2 adds the actual fee to the `Receipts`, the fee of the receipts kept from v1 isn't
+	/// known and is set to zero.
+	pub struct MigrateToV2<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+			if on_chain_version != 1 {
+				log::info!(
+					target: LOG_TARGET,
+					"skipping v2 migration: on-chain storage version is {:?}",
+					on_chain_version,
+				);
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut translated = 0u64;
+			Receipts::<T>::translate::<ReceiptV1<BlockNumberFor<T>>, _>(|_who, _nonce, receipt| {
+				translated += 1;
+				Some(Receipt {
+					block_number: receipt.block_number,
+					success: receipt.success,
+					error: receipt.error,
+					actual_fee: Zero::zero(),
+				})
+			});
+
+			StorageVersion::new(2).put::<Pallet<T>>();
+
+			log::info!(target: LOG_TARGET, "migrated {} Receipts entries to v2", translated);
+
+			T::DbWeight::get()
+				.reads_writes(translated.saturating_add(1), translated.saturating_add(1))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+			Ok((Receipts::<T>::iter_keys().count() as u64).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			let expected = u64::decode(&mut &state[..])
+				.map_err(|_| "failed to decode the pre-upgrade state")?;
+
+			ensure!(
+				Pallet::<T>::on_chain_storage_version() == 2,
+				"storage version should be 2 after the migration"
+			);
+			ensure!(
+				Receipts::<T>::iter_values().count() as u64 == expected,
+				"all the Receipts entries should be kept"
+			);
+
+			Ok(())
+		}
+	}
+}
@@ -16,19 +16,26 @@
 // limitations under the License.
 
 use crate as pallet_evm_account_mapping;
+use crate::ReplayProtection;
 use frame_support::{
 	derive_impl,
 	dispatch::DispatchClass,
 	pallet_prelude::*,
 	parameter_types,
 	traits::{
-		fungible::Mutate, ConstU128, ConstU16, ConstU32, ConstU64, Get, Imbalance, OnUnbalanced,
+		fungible::Mutate, ConstBool, ConstU128, ConstU16, ConstU32, ConstU64, Contains,
+		ExistenceRequirement, Get, Imbalance, InstanceFilter, OnUnbalanced,
+	},
+	weights::{
+		constants::WEIGHT_REF_TIME_PER_SECOND, RuntimeDbWeight, Weight, WeightToFee as WeightToFeeT,
 	},
-	weights::{Weight, WeightToFee as WeightToFeeT},
 };
 use pallet_transaction_payment::CurrencyAdapter;
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentifyAccount, IdentityLookup, Verify},
+	traits::{
+		BlakeTwo256, Convert, DispatchInfoOf, IdentifyAccount, IdentityLookup, PostDispatchInfoOf,
+		Verify,
+	},
 	BuildStorage, MultiSignature, SaturatedConversion,
 };
 
@@ -55,6 +62,7 @@ frame_support::construct_runtime!(
 
 parameter_types! {
 	pub(crate) static ExtrinsicBaseWeight: Weight = Weight::zero();
+	pub(crate) static DbWeight: RuntimeDbWeight = RuntimeDbWeight { read: 0, write: 0 };
 }
 
 pub struct BlockWeights;
@@ -66,7 +74,8 @@ impl Get<frame_system::limits::BlockWeights> for BlockWeights {
 				weights.base_extrinsic = ExtrinsicBaseWeight::get();
 			})
 			.for_class(DispatchClass::non_mandatory(), |weights| {
-				weights.max_total = Weight::from_parts(1024, u64::MAX).into();
+				weights.max_total =
+					Weight::from_parts(2 * WEIGHT_REF_TIME_PER_SECOND, u64::MAX).into();
 			})
 			.build_or_panic()
 	}
@@ -93,12 +102,12 @@ impl frame_system::Config for Test {
 	type Lookup = IdentityLookup<Self::AccountId>;
 	type Block = Block;
 	type BlockHashCount = ConstU64<250>;
-	type DbWeight = ();
+	type DbWeight = DbWeight;
 	type Version = ();
 	type PalletInfo = PalletInfo;
 	type AccountData = pallet_balances::AccountData<Balance>;
 	type OnNewAccount = ();
-	type OnKilledAccount = ();
+	type OnKilledAccount = EvmAccountMapping;
 	type SystemWeightInfo = ();
 	type SS58Prefix = ConstU16<42>;
 	type OnSetCode = ();
@@ -118,7 +127,7 @@ impl pallet_balances::Config for Test {
 	type FreezeIdentifier = ();
 	type MaxLocks = ();
 	type MaxReserves = ConstU32<50>;
-	type MaxHolds = ();
+	type MaxHolds = ConstU32<4>;
 	type MaxFreezes = ();
 }
 
@@ -173,20 +182,290 @@ parameter_types! {
 	pub EIP712Version: Vec<u8> = b"1".to_vec();
 	pub EIP712ChainID: crate::EIP712ChainID = sp_core::U256::from(0);
 	pub EIP712VerifyingContractAddress: crate::EIP712VerifyingContractAddress = sp_core::H160::from([0u8; 20]);
+	pub static EIP712Salt: Option<[u8; 32]> = None;
+	pub static EIP712ExtraVerifyingContracts: Vec<crate::EIP712VerifyingContractAddress> = Vec::new();
+	pub static EIP712ParaId: Option<u32> = None;
+	pub static FreeCallsPerAccount: u32 = 0;
+	pub static DispatchAsEvmMapped: bool = false;
+	pub static WaiveRemarkServiceFee: bool = false;
+	pub static WaiveTransactionFees: bool = false;
+	pub static SpendExistenceRequirement: ExistenceRequirement = ExistenceRequirement::KeepAlive;
+	pub const UnsignedTagPrefix: &'static str = "EVMAccountMapping";
+	pub static BlockAuthor: Option<AccountId> = None;
+	pub static TipToBlockAuthor: bool = false;
+	pub static LinkDeposit: Balance = 0;
+	pub static GrantDeposit: Balance = 0;
+	pub static SponsorshipDeposit: Balance = 0;
+	pub static MaxMetaTxPerBlock: u32 = 100;
+	pub static PurgeNonceOnReap: bool = false;
+	pub static MetaMortality: bool = false;
+	pub static LastApproval: Option<(u32, AccountId, AccountId, u128)> = None;
+	pub static ConvertTypedCalls: bool = true;
+	pub static RemarkFeeSurcharge: Option<(Balance, Balance)> = None;
+}
+
+/// Convert the typed calls unless `ConvertTypedCalls` is unset.
+pub struct ConvertTypedCall;
+impl Convert<crate::TypedCallOf<Test>, Option<RuntimeCall>> for ConvertTypedCall {
+	fn convert(typed_call: crate::TypedCallOf<Test>) -> Option<RuntimeCall> {
+		if !ConvertTypedCalls::get() {
+			return None
+		}
+		Some(match typed_call {
+			crate::TypedCall::BalanceTransfer { to, amount } =>
+				RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive {
+					dest: to,
+					value: amount,
+				}),
+			crate::TypedCall::Remark { remark } =>
+				RuntimeCall::System(frame_system::Call::remark { remark }),
+		})
+	}
+}
+
+/// Forbid `balances.transferAll`.
+pub struct NoTransferAll;
+impl Contains<RuntimeCall> for NoTransferAll {
+	fn contains(call: &RuntimeCall) -> bool {
+		!matches!(call, RuntimeCall::Balances(pallet_balances::Call::transfer_all { .. }))
+	}
+}
+
+/// Waive the service fee of `system.remarkWithEvent` if `WaiveRemarkServiceFee` is set.
+pub struct RemarkServiceFeeWaiver;
+impl Contains<RuntimeCall> for RemarkServiceFeeWaiver {
+	fn contains(call: &RuntimeCall) -> bool {
+		WaiveRemarkServiceFee::get() &&
+			matches!(call, RuntimeCall::System(frame_system::Call::remark_with_event { .. }))
+	}
+}
+
+/// Charge meta-transactions as native transactions unless `WaiveTransactionFees` is set.
+pub struct WaivableTransactionFees;
+type NativeTransactionFees = crate::TransactionPaymentAdapter;
+impl crate::OnChargeMetaTransaction<Test> for WaivableTransactionFees {
+	type LiquidityInfo = Option<pallet_balances::NegativeImbalance<Test>>;
+
+	fn withdraw_fee(
+		who: &AccountId,
+		call: &RuntimeCall,
+		info: &DispatchInfoOf<RuntimeCall>,
+		fee: Balance,
+		tip: Balance,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		if WaiveTransactionFees::get() {
+			return Ok(None)
+		}
+		<NativeTransactionFees as crate::OnChargeMetaTransaction<Test>>::withdraw_fee(
+			who, call, info, fee, tip,
+		)
+	}
+
+	fn correct_and_deposit_fee(
+		who: &AccountId,
+		info: &DispatchInfoOf<RuntimeCall>,
+		post_info: &PostDispatchInfoOf<RuntimeCall>,
+		corrected_fee: Balance,
+		tip: Balance,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		<NativeTransactionFees as crate::OnChargeMetaTransaction<Test>>::correct_and_deposit_fee(
+			who,
+			info,
+			post_info,
+			corrected_fee,
+			tip,
+			already_withdrawn,
+		)
+	}
+}
+
+/// Treat the call of a nested `meta_call` as a wrapped call, there is no batch pallet in the mock.
+pub struct InnerMetaCalls;
+impl crate::InspectInnerCalls<RuntimeCall> for InnerMetaCalls {
+	fn inner_calls(call: &RuntimeCall) -> Vec<&RuntimeCall> {
+		match call {
+			RuntimeCall::EvmAccountMapping(crate::Call::meta_call { call, .. }) => vec![&**call],
+			_ => vec![],
+		}
+	}
+}
+
+/// Halve the transaction fee of `system.remarkWithEvent`, and add the surcharge of
+/// `RemarkFeeSurcharge` to the fees of `system.remark` above its threshold.
+pub struct AdjustRemarkFee;
+impl crate::AdjustFee<RuntimeCall, Balance> for AdjustRemarkFee {
+	fn adjust_fee(call: &RuntimeCall, fee: Balance) -> Balance {
+		match call {
+			RuntimeCall::System(frame_system::Call::remark_with_event { .. }) => fee / 2,
+			RuntimeCall::System(frame_system::Call::remark { .. }) =>
+				match RemarkFeeSurcharge::get() {
+					Some((threshold, surcharge)) if fee > threshold => fee + surcharge,
+					_ => fee,
+				},
+			_ => fee,
+		}
+	}
+}
+
+/// The account collecting the asset tips.
+pub(crate) const TIP_COLLECTOR: AccountId = AccountId::new([0xffu8; 32]);
+
+/// Only the asset `1` is supported, it is backed by the native token one to one.
+pub struct NativeBackedTips;
+impl crate::TipHandler<AccountId, u32> for NativeBackedTips {
+	fn can_pay(who: &AccountId, asset_id: &u32, amount: u128) -> bool {
+		*asset_id == 1 && Balances::free_balance(who) >= amount
+	}
+
+	fn pay(who: &AccountId, asset_id: &u32, amount: u128) -> DispatchResult {
+		use frame_support::traits::tokens::Preservation;
+
+		ensure!(*asset_id == 1, sp_runtime::DispatchError::Other("Unsupported asset"));
+		<Balances as Mutate<AccountId>>::transfer(
+			who,
+			&TIP_COLLECTOR,
+			amount,
+			Preservation::Preserve,
+		)
+		.map(|_| ())
+	}
+
+	fn weight() -> Weight {
+		Weight::zero()
+	}
+}
+
+/// Record the last approval of a permit.
+pub struct RecordedApprovals;
+impl crate::AssetApprovals<AccountId, u32> for RecordedApprovals {
+	fn approve(
+		asset_id: &u32,
+		owner: &AccountId,
+		spender: &AccountId,
+		amount: u128,
+	) -> DispatchResult {
+		LastApproval::set(Some((*asset_id, owner.clone(), spender.clone(), amount)));
+		Ok(())
+	}
+
+	fn weight() -> Weight {
+		Weight::zero()
+	}
+}
+
+parameter_types! {
+	pub static UseWindowedReplayProtection: bool = false;
+	pub const ReplayWindow: u64 = 5;
+}
+
+/// The sequential nonces, or the windowed payload hashes once `UseWindowedReplayProtection` is
+/// set.
+pub struct SwitchableReplayProtection;
+impl ReplayProtection<AccountId, u64> for SwitchableReplayProtection {
+	fn check(
+		who: &AccountId,
+		nonce: u64,
+		message_hash: impl Fn() -> [u8; 32],
+	) -> Result<crate::ReplayTags, sp_runtime::transaction_validity::InvalidTransaction> {
+		if UseWindowedReplayProtection::get() {
+			Windowed::check(who, nonce, message_hash)
+		} else {
+			Sequential::check(who, nonce, message_hash)
+		}
+	}
+
+	fn consume(who: &AccountId, nonce: u64, message_hash: &[u8; 32]) -> bool {
+		if UseWindowedReplayProtection::get() {
+			Windowed::consume(who, nonce, message_hash)
+		} else {
+			Sequential::consume(who, nonce, message_hash)
+		}
+	}
+
+	fn prune(n: u64) -> Weight {
+		// Always prune, the strategy may have been switched
+		Windowed::prune(n).saturating_add(Sequential::prune(n))
+	}
+}
+
+type Sequential = crate::SequentialNonces<Test>;
+type Windowed = crate::WindowedPayloadHashes<Test, ReplayWindow>;
+
+/// The proxy-like classes meta-transactions may be restricted to.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub enum ProxyType {
+	Any,
+	NonTransfer,
+}
+
+impl InstanceFilter<RuntimeCall> for ProxyType {
+	fn filter(&self, call: &RuntimeCall) -> bool {
+		match self {
+			ProxyType::Any => true,
+			ProxyType::NonTransfer => !matches!(call, RuntimeCall::Balances(..)),
+		}
+	}
+
+	fn is_superset(&self, o: &Self) -> bool {
+		self == &ProxyType::Any || self == o
+	}
 }
 
 impl pallet_evm_account_mapping::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
 	type RuntimeCall = RuntimeCall;
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type Currency = Balances;
+	type LinkDeposit = LinkDeposit;
+	type GrantDeposit = GrantDeposit;
+	type SponsorshipDeposit = SponsorshipDeposit;
 	type AddressConverter = pallet_evm_account_mapping::SubstrateAddressConverter;
 	type ServiceFee = ConstU128<1000>;
+	type TypedCallConverter = ConvertTypedCall;
+	type FeeAdjustment = AdjustRemarkFee;
 	type OnUnbalancedForServiceFee = ();
-	type CallFilter = frame_support::traits::Everything;
+	type OnChargeMetaTransaction = WaivableTransactionFees;
+	type SpendExistenceRequirement = SpendExistenceRequirement;
+	type CallFilter = NoTransferAll;
+	type CallFilterOrigin = frame_system::EnsureRoot<AccountId>;
+	type ServiceFeeWaiver = RemarkServiceFeeWaiver;
+	type InspectInnerCalls = InnerMetaCalls;
+	type ReceiptRetention = ConstU64<10>;
+	type IndexMetaTransactions = ConstBool<true>;
+	type FreeCallsPerAccount = FreeCallsPerAccount;
+	type RateLimitWindow = ConstU64<10>;
+	type MaxCallsPerWindow = ConstU32<2>;
+	type MaxCallDepth = ConstU32<8>;
+	type AllowOperationalCalls = ConstBool<false>;
+	type DispatchAsEvmMapped = DispatchAsEvmMapped;
+	type TipAssetId = u32;
+	type TipHandler = NativeBackedTips;
+	type PermitAssetId = u32;
+	type AssetApprovals = RecordedApprovals;
+	type ProxyType = ProxyType;
+	type PriorityPolicy = pallet_evm_account_mapping::DefaultPriority<Test>;
+	type UnsignedLongevity = ConstU64<5>;
+	type MaxNonceGap = ConstU64<2>;
+	type ReplayProtection = SwitchableReplayProtection;
+	type MaxMetaTxPerBlock = MaxMetaTxPerBlock;
+	type PurgeNonceOnReap = PurgeNonceOnReap;
+	type MetaMortality = MetaMortality;
+	type UnsignedTagPrefix = UnsignedTagPrefix;
+	type BlockAuthor = BlockAuthor;
+	type TipToBlockAuthor = TipToBlockAuthor;
+	type AdminOrigin = frame_system::EnsureRoot<AccountId>;
+	type RecoveryOrigin = frame_system::EnsureRoot<AccountId>;
+	type RecoveryDelay = ConstU64<10>;
+	type ControllerRotationDelay = ConstU64<10>;
 	type EIP712Name = EIP712Name;
 	type EIP712Version = EIP712Version;
 	type EIP712ChainID = EIP712ChainID;
 	type EIP712VerifyingContractAddress = EIP712VerifyingContractAddress;
+	type EIP712ExtraVerifyingContracts = EIP712ExtraVerifyingContracts;
+	type EIP712Salt = EIP712Salt;
+	type EIP712ParaId = EIP712ParaId;
 	type WeightInfo = ();
 }
 
@@ -198,7 +477,13 @@ pub(crate) fn set_balance(who: AccountId, new_free: Balance) {
 
 // Build genesis storage according to the mock runtime.
 pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
-	frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into()
+	let mut ext: sp_io::TestExternalities =
+		frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into();
+	// Benchmarks sign their payloads with keys generated in the keystore
+	ext.register_extension(sp_keystore::KeystoreExt::new(
+		sp_keystore::testing::MemoryKeystore::new(),
+	));
+	ext
 }
 
 pub(crate) fn run_to_block(n: u64) {
@@ -206,7 +491,9 @@ pub(crate) fn run_to_block(n: u64) {
 	assert!(n > current_block);
 	while System::block_number() < n {
 		Balances::on_finalize(System::block_number());
+		EvmAccountMapping::on_finalize(System::block_number());
 		System::set_block_number(System::block_number() + 1);
 		Balances::on_initialize(System::block_number());
+		EvmAccountMapping::on_initialize(System::block_number());
 	}
 }
@@ -0,0 +1,80 @@
+// This file is part of EVM Account Mapping Pallet.
+
+// Copyright (C) HashForest Technology Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Signing utilities for the tests of runtimes integrating the pallet.
+//!
+//! The payloads are hashed by the same code as on-chain, so a signature made here is accepted by
+//! the pallet as long as the domain matches, see [`crate::Pallet::eip712_domain`].
+
+use crate::{
+	eip712::EIP712Domain, encode_public_key, AddressConversion, EIP712Signature,
+	Keccak256Signature, Nonce, PayloadVersion,
+};
+use sp_core::{
+	crypto::{AccountId32, Ss58Codec},
+	ecdsa, Pair,
+};
+
+/// The SS58 address of `who` with `prefix`, as shown in the signed payloads.
+pub fn ss58_address(who: &AccountId32, prefix: u16) -> String {
+	who.to_ss58check_with_version(prefix.into())
+}
+
+/// The account `keypair` is mapped to by `C`.
+pub fn mapped_account<C: AddressConversion<AccountId32>>(
+	keypair: &ecdsa::Pair,
+) -> Option<AccountId32> {
+	let message_hash = [0u8; 32];
+	let signature = sign_message_hash(keypair, &message_hash);
+	let public_key = sp_io::crypto::secp256k1_ecdsa_recover(&signature, &message_hash).ok()?;
+	C::try_convert(&encode_public_key(&public_key, &C::SECP256K1_PUBLIC_KEY_FORM))
+}
+
+/// The EIP-712 hash a wallet signs for a `meta_call` of `version` within `domain`, `who` is the
+/// SS58 address of the account.
+pub fn meta_call_message_hash(
+	version: PayloadVersion,
+	who: &str,
+	call_data: &[u8],
+	nonce: Nonce,
+	tip: u128,
+	domain: &EIP712Domain,
+) -> Keccak256Signature {
+	let struct_hash = crate::meta_call_struct_hash(version, who.as_bytes(), call_data, nonce, tip);
+	crate::eip712::typed_data_hash(&domain.separator(), &struct_hash)
+}
+
+/// Sign `message_hash` with `keypair`.
+pub fn sign_message_hash(
+	keypair: &ecdsa::Pair,
+	message_hash: &Keccak256Signature,
+) -> EIP712Signature {
+	keypair.sign_prehashed(message_hash).0
+}
+
+/// Sign a `meta_call` of [`PayloadVersion::V0`] dispatching the SCALE encoded `call_data` with
+/// `nonce`, `who` is the SS58 address of the account.
+pub fn sign_remote_call(
+	keypair: &ecdsa::Pair,
+	who: &str,
+	call_data: &[u8],
+	nonce: Nonce,
+	domain: &EIP712Domain,
+) -> EIP712Signature {
+	let message_hash = meta_call_message_hash(PayloadVersion::V0, who, call_data, nonce, 0, domain);
+	sign_message_hash(keypair, &message_hash)
+}
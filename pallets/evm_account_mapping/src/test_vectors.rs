@@ -0,0 +1,178 @@
+// This file is part of EVM Account Mapping Pallet.
+
+// Copyright (C) HashForest Technology Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Deterministic test vectors of the signed payloads, for validating SDKs against the pallet.
+//!
+//! The vectors are produced by the same hashing code as on-chain and signed with fixed dev keys,
+//! [`to_json`] renders them as canonical JSON, i.e. without whitespace and with sorted keys.
+
+use crate::{
+	eip712::EIP712Domain, evm_address_of, test_utils, EIP712Signature, EvmAddress,
+	Keccak256Signature, Nonce, PayloadVersion, SubstrateAddressConverter,
+};
+use sp_core::{ecdsa, Pair, H160, U256};
+
+/// The SS58 prefix of the accounts in the vectors.
+pub const SS58_PREFIX: u16 = 42;
+
+/// A signed `meta_call` payload and the values the pallet derives from it.
+#[derive(Clone, Debug)]
+pub struct MetaCallVector {
+	pub domain: EIP712Domain,
+	pub version: PayloadVersion,
+	/// The SS58 address of the mapped account.
+	pub who: String,
+	pub call_data: Vec<u8>,
+	pub nonce: Nonce,
+	pub tip: u128,
+	/// The EIP-712 struct hash of the payload.
+	pub struct_hash: Keccak256Signature,
+	/// The hash the wallet signs.
+	pub message_hash: Keccak256Signature,
+	pub signature: EIP712Signature,
+	/// The EVM address of the signer.
+	pub signer: EvmAddress,
+}
+
+/// The domains the vectors are signed within.
+pub fn domains() -> Vec<EIP712Domain> {
+	vec![
+		EIP712Domain {
+			name: b"Substrate".to_vec(),
+			version: b"1".to_vec(),
+			chain_id: U256::zero(),
+			verifying_contract: H160::zero(),
+			salt: None,
+		},
+		EIP712Domain {
+			name: b"Phala Network".to_vec(),
+			version: b"2".to_vec(),
+			chain_id: U256::from(2035),
+			verifying_contract: H160::repeat_byte(0x11),
+			salt: Some([0x22; 32]),
+		},
+	]
+}
+
+/// The `meta_call` vectors of every domain, payload version and dev key.
+pub fn meta_call_vectors() -> Vec<MetaCallVector> {
+	// `system.remarkWithEvent("Hello")` of the runtime in this repository
+	let call_data = vec![0x00, 0x07, 0x14, 0x48, 0x65, 0x6c, 0x6c, 0x6f];
+
+	let mut vectors = Vec::new();
+	for domain in domains() {
+		for version in [PayloadVersion::V0, PayloadVersion::V1] {
+			for (seed, nonce, tip) in [(1u8, 0, 0), (2u8, 7, 10_000_000_000)] {
+				let keypair = ecdsa::Pair::from_seed(&[seed; 32]);
+				vectors.push(meta_call_vector(&keypair, &domain, version, &call_data, nonce, tip));
+			}
+		}
+	}
+	vectors
+}
+
+/// Sign a `meta_call` payload with `keypair`, the tip is only signed since
+/// [`PayloadVersion::V1`].
+pub fn meta_call_vector(
+	keypair: &ecdsa::Pair,
+	domain: &EIP712Domain,
+	version: PayloadVersion,
+	call_data: &[u8],
+	nonce: Nonce,
+	tip: u128,
+) -> MetaCallVector {
+	let account = test_utils::mapped_account::<SubstrateAddressConverter>(keypair)
+		.expect("Dev keys are convertible");
+	let who = test_utils::ss58_address(&account, SS58_PREFIX);
+	let tip = match version {
+		PayloadVersion::V0 => 0,
+		PayloadVersion::V1 => tip,
+	};
+	let struct_hash = crate::meta_call_struct_hash(version, who.as_bytes(), call_data, nonce, tip);
+	let message_hash =
+		test_utils::meta_call_message_hash(version, &who, call_data, nonce, tip, domain);
+	let signature = test_utils::sign_message_hash(keypair, &message_hash);
+	let public_key = sp_io::crypto::secp256k1_ecdsa_recover(&signature, &message_hash)
+		.expect("The signature is valid");
+
+	MetaCallVector {
+		domain: domain.clone(),
+		version,
+		who,
+		call_data: call_data.to_vec(),
+		nonce,
+		tip,
+		struct_hash,
+		message_hash,
+		signature,
+		signer: evm_address_of(&public_key),
+	}
+}
+
+/// Render `vectors` as canonical JSON.
+pub fn to_json(vectors: &[MetaCallVector]) -> String {
+	let vectors: Vec<String> = vectors.iter().map(MetaCallVector::to_json).collect();
+	format!("[{}]", vectors.join(","))
+}
+
+impl MetaCallVector {
+	/// Render the vector as canonical JSON, big numbers are decimal strings and bytes are `0x`
+	/// prefixed hex strings.
+	pub fn to_json(&self) -> String {
+		let domain = &self.domain;
+		let salt = domain.salt.map(|salt| hex_string(&salt)).unwrap_or_else(|| "null".into());
+		format!(
+			concat!(
+				r#"{{"domain":{{"chainId":"{}","name":{},"salt":{},"verifyingContract":{},"version":{}}},"#,
+				r#""expected":{{"messageHash":{},"signature":{},"signer":{},"structHash":{}}},"#,
+				r#""message":{{"callData":{},"nonce":{},"tip":"{}","version":{},"who":{}}}}}"#,
+			),
+			domain.chain_id,
+			json_string(&domain.name),
+			salt,
+			hex_string(domain.verifying_contract.as_bytes()),
+			json_string(&domain.version),
+			hex_string(&self.message_hash),
+			hex_string(&self.signature),
+			hex_string(self.signer.as_bytes()),
+			hex_string(&self.struct_hash),
+			hex_string(&self.call_data),
+			self.nonce,
+			self.tip,
+			self.version as u8,
+			json_string(self.who.as_bytes()),
+		)
+	}
+}
+
+fn hex_string(bytes: &[u8]) -> String {
+	format!("\"0x{}\"", hex::encode(bytes))
+}
+
+fn json_string(bytes: &[u8]) -> String {
+	let mut escaped = String::from("\"");
+	for c in String::from_utf8_lossy(bytes).chars() {
+		match c {
+			'"' => escaped.push_str("\\\""),
+			'\\' => escaped.push_str("\\\\"),
+			c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+			c => escaped.push(c),
+		}
+	}
+	escaped.push('"');
+	escaped
+}
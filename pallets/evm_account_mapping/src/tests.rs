@@ -16,9 +16,9 @@
 // limitations under the License.
 
 #[allow(unused)]
-use crate::{mock::*, Error, Event};
+use crate::{mock::*, Error, Event, PayloadVersion};
 use codec::Decode;
-use frame_support::assert_ok;
+use frame_support::{assert_noop, assert_ok};
 
 use sp_core::crypto::Ss58Codec;
 use sp_runtime::traits::TrailingZeroInput;
@@ -46,8 +46,9 @@ fn it_works() {
 				account,
 				Box::<RuntimeCall>::new(call),
 				nonce,
-				signature,
-				0u128.into()
+				signature.into(),
+				0u128.into(),
+				PayloadVersion::V0
 			)
 		);
 
@@ -91,6 +92,7 @@ fn evm_transparent_converter_works() {
 		account_id.to_string(),
 		"5EmhBEe8vsSfqYseKctWsaQqNKCF9FFao6Mqa9hNfcdF25oE"
 	);
+	assert_eq!(crate::EvmTransparentConverter::try_convert_evm_address(&h20), Some(account_id));
 }
 
 #[test]
@@ -108,6 +110,8 @@ fn evm_substrate_address_converter_works() {
 
 #[test]
 fn eip712() {
+	use crate::eip712::encode::abi::{self, Token};
+
 	let eip712_name = b"Substrate".to_vec();
 	let eip712_version = b"1".to_vec();
 	let eip712_chain_id: crate::EIP712ChainID = sp_core::U256::from(0);
@@ -136,21 +140,21 @@ fn eip712() {
 	let call_data =
 		sp_io::hashing::keccak_256(&hex::decode("00071448656c6c6f").expect("Decodable"));
 	let nonce = 0u64;
-	let message_hash = sp_io::hashing::keccak_256(&ethabi::encode(&[
-		ethabi::Token::FixedBytes(type_hash.to_vec()),
-		ethabi::Token::FixedBytes(sp_io::hashing::keccak_256(who.as_bytes()).to_vec()),
-		ethabi::Token::FixedBytes(call_data.to_vec()),
-		ethabi::Token::Uint(nonce.into()),
+	let message_hash = sp_io::hashing::keccak_256(&abi::encode(&[
+		Token::FixedBytes(&type_hash),
+		Token::FixedBytes(&sp_io::hashing::keccak_256(who.as_bytes())),
+		Token::FixedBytes(&call_data),
+		Token::Uint(nonce.into()),
 	]));
 
 	// panic!("{}", hex::encode(message_hash));
 
 	let typed_data_hash_input = &vec![
-		crate::encode::SolidityDataType::String("\x19\x01"),
-		crate::encode::SolidityDataType::Bytes(&domain_separator),
-		crate::encode::SolidityDataType::Bytes(&message_hash),
+		crate::eip712::encode::SolidityDataType::String("\x19\x01"),
+		crate::eip712::encode::SolidityDataType::Bytes(&domain_separator),
+		crate::eip712::encode::SolidityDataType::Bytes(&message_hash),
 	];
-	let bytes = crate::encode::abi::encode_packed(typed_data_hash_input);
+	let bytes = crate::eip712::encode::abi::encode_packed(typed_data_hash_input);
 	let signing_message = sp_io::hashing::keccak_256(bytes.as_slice());
 
 	let signature: [u8; 65] = hex::decode("37cb6ff8e296d7e476ee13a6cfababe788217519d428fcc723b482dc97cb4d1359a8d1c020fe3cebc1d06a67e61b1f0e296739cecacc640b0ba48e8a7555472e1b").expect("Decodable").try_into().expect("Decodable");
@@ -167,3 +171,4275 @@ fn eip712() {
 			.expect("Decodable");
 	assert_eq!(decoded_account.to_ss58check(), who);
 }
+
+#[test]
+fn migrate_to_v1_works() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<EvmAccountMapping>();
+
+		let alice = AccountId::from([1u8; 32]);
+		let bob = AccountId::from([2u8; 32]);
+		crate::AccountNonce::<Test>::insert(&alice, 3);
+		crate::AccountNonce::<Test>::insert(&bob, 0);
+
+		crate::migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(EvmAccountMapping::on_chain_storage_version(), 1);
+		assert_eq!(crate::AccountNonce::<Test>::get(&alice), 3);
+		assert!(!crate::AccountNonce::<Test>::contains_key(&bob));
+
+		// Running it again is a no-op
+		crate::AccountNonce::<Test>::insert(&bob, 0);
+		crate::migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		assert!(crate::AccountNonce::<Test>::contains_key(&bob));
+	});
+}
+
+#[test]
+fn migrate_to_v2_works() {
+	use frame_support::{
+		storage::unhashed,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(1).put::<EvmAccountMapping>();
+
+		let alice = AccountId::from([1u8; 32]);
+		let key = crate::Receipts::<Test>::hashed_key_for(&alice, 3);
+		let receipt = crate::migrations::v2::ReceiptV1 {
+			block_number: 5u64,
+			success: false,
+			error: Some(sp_runtime::DispatchError::BadOrigin),
+		};
+		unhashed::put_raw(&key, &codec::Encode::encode(&receipt));
+
+		crate::migrations::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+
+		assert_eq!(EvmAccountMapping::on_chain_storage_version(), 2);
+		assert_eq!(
+			crate::Receipts::<Test>::get(&alice, 3),
+			Some(crate::Receipt {
+				block_number: 5,
+				success: false,
+				error: Some(sp_runtime::DispatchError::BadOrigin),
+				actual_fee: 0,
+			})
+		);
+	});
+}
+
+/// The `system.remarkWithEvent("Hello")` payload signed for
+/// `5DT96geTS2iLpkH8fAhYAAphNpxddKCV36s5ShVFavf1xQiF` with nonce 0.
+fn signed_remark() -> (AccountId, RuntimeCall, u64, crate::MetaSignature) {
+	let account =
+		AccountId::from_ss58check("5DT96geTS2iLpkH8fAhYAAphNpxddKCV36s5ShVFavf1xQiF").unwrap();
+	let call_data = hex::decode("00071448656c6c6f").expect("Valid");
+	let call = RuntimeCall::decode(&mut TrailingZeroInput::new(&call_data)).expect("Valid");
+	let signature: [u8; 65] = hex::decode("37cb6ff8e296d7e476ee13a6cfababe788217519d428fcc723b482dc97cb4d1359a8d1c020fe3cebc1d06a67e61b1f0e296739cecacc640b0ba48e8a7555472e1b").expect("Decodable").try_into().expect("Valid");
+
+	(account, call, 0, signature.into())
+}
+
+#[test]
+fn recovered_public_key_compresses_to_the_signer() {
+	new_test_ext().execute_with(|| {
+		let (account, call, nonce, signature) = signed_remark();
+		let message_hash =
+			EvmAccountMapping::eip712_message_hash(account, &codec::Encode::encode(&call), nonce);
+		let public_key =
+			EvmAccountMapping::ecdsa_recover_public_key(&signature.normalize(), &message_hash)
+				.expect("Recoverable");
+
+		assert_eq!(
+			hex::encode(crate::encode_public_key(
+				&public_key,
+				&crate::Secp256K1PublicKeyForm::Compressed
+			)),
+			"027cf2fa7bfe66adad4149481ff86794ce7e1ab2f7ed615ad3918f91581d2c00f1"
+		);
+	});
+}
+
+#[test]
+fn evm_addresses_are_recorded() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let (account, call, nonce, signature) = signed_remark();
+		let message_hash = EvmAccountMapping::eip712_message_hash(
+			account.clone(),
+			&codec::Encode::encode(&call),
+			nonce,
+		);
+		let public_key =
+			EvmAccountMapping::ecdsa_recover_public_key(&signature.normalize(), &message_hash)
+				.expect("Recoverable");
+		let evm_address = crate::evm_address_of(&public_key);
+		set_balance(account.clone(), DOLLARS);
+
+		// The public key of an EVM address is unknown until it signs a meta-transaction
+		assert_eq!(EvmAccountMapping::map_evm_address(&evm_address), None);
+		assert_eq!(EvmAccountMapping::evm_address_of_account(&account), None);
+
+		assert_ok!(EvmAccountMapping::meta_call(
+			RuntimeOrigin::none(),
+			account.clone(),
+			Box::new(call),
+			nonce,
+			signature,
+			None,
+			PayloadVersion::V0
+		));
+		assert_eq!(EvmAccountMapping::map_evm_address(&evm_address), Some(account.clone()));
+		assert_eq!(EvmAccountMapping::evm_address_of_account(&account), Some(evm_address));
+	});
+}
+
+#[test]
+fn blocked_address_cannot_meta_call() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let (account, call, nonce, signature) = signed_remark();
+		set_balance(account.clone(), DOLLARS);
+
+		let message_hash = EvmAccountMapping::eip712_message_hash(
+			account.clone(),
+			&codec::Encode::encode(&call),
+			nonce,
+		);
+		let evm_address =
+			EvmAccountMapping::recover_signer(&account, &message_hash, &signature.normalize())
+				.expect("Valid signature");
+
+		assert_noop!(
+			EvmAccountMapping::block_address(RuntimeOrigin::signed(account.clone()), evm_address),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(EvmAccountMapping::block_address(RuntimeOrigin::root(), evm_address));
+		System::assert_last_event(Event::AddressBlocked { address: evm_address }.into());
+		assert_noop!(
+			EvmAccountMapping::block_address(RuntimeOrigin::root(), evm_address),
+			Error::<Test>::AlreadyBlocked
+		);
+
+		assert_noop!(
+			EvmAccountMapping::meta_call(
+				RuntimeOrigin::none(),
+				account.clone(),
+				Box::new(call.clone()),
+				nonce,
+				signature,
+				None,
+				PayloadVersion::V0
+			),
+			Error::<Test>::AddressBlocked
+		);
+
+		assert_ok!(EvmAccountMapping::unblock_address(RuntimeOrigin::root(), evm_address));
+		assert_ok!(EvmAccountMapping::meta_call(
+			RuntimeOrigin::none(),
+			account,
+			Box::new(call),
+			nonce,
+			signature,
+			None,
+			PayloadVersion::V0
+		));
+	});
+}
+
+#[test]
+fn rate_limit_works() {
+	new_test_ext().execute_with(|| {
+		run_to_block(2);
+
+		let (account, call, nonce, signature) = signed_remark();
+		set_balance(account.clone(), DOLLARS);
+
+		crate::CallRateLimits::<Test>::insert(
+			&account,
+			crate::RateLimitState { window_start: 1, calls: 2 },
+		);
+		assert_noop!(
+			EvmAccountMapping::meta_call(
+				RuntimeOrigin::none(),
+				account.clone(),
+				Box::new(call.clone()),
+				nonce,
+				signature,
+				None,
+				PayloadVersion::V0
+			),
+			Error::<Test>::RateLimited
+		);
+
+		// A new window starts
+		run_to_block(11);
+		assert_ok!(EvmAccountMapping::meta_call(
+			RuntimeOrigin::none(),
+			account.clone(),
+			Box::new(call),
+			nonce,
+			signature,
+			None,
+			PayloadVersion::V0
+		));
+		assert_eq!(
+			crate::CallRateLimits::<Test>::get(&account),
+			Some(crate::RateLimitState { window_start: 11, calls: 1 })
+		);
+	});
+}
+
+#[test]
+fn free_calls_skip_service_fee() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		FreeCallsPerAccount::set(1);
+
+		let (account, call, nonce, signature) = signed_remark();
+		set_balance(account.clone(), DOLLARS);
+
+		assert_ok!(EvmAccountMapping::meta_call(
+			RuntimeOrigin::none(),
+			account.clone(),
+			Box::new(call),
+			nonce,
+			signature,
+			None,
+			PayloadVersion::V0
+		));
+		System::assert_has_event(
+			Event::ServiceFeeWaived { who: account.clone(), waived_fee: 1000 }.into(),
+		);
+		assert_eq!(crate::FreeCallsUsed::<Test>::get(&account), 1);
+		assert!(!EvmAccountMapping::has_free_call(&account));
+	});
+}
+
+#[test]
+fn service_fee_waiver_applies() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		FreeCallsPerAccount::set(1);
+		WaiveRemarkServiceFee::set(true);
+
+		let (account, call, nonce, signature) = signed_remark();
+		set_balance(account.clone(), DOLLARS);
+		let estimate = EvmAccountMapping::estimate_meta_fee(&codec::Encode::encode(&call), 0)
+			.expect("Decodable");
+		assert_eq!(estimate.service_fee, 0);
+
+		assert_ok!(EvmAccountMapping::meta_call(
+			RuntimeOrigin::none(),
+			account.clone(),
+			Box::new(call),
+			nonce,
+			signature,
+			None,
+			PayloadVersion::V0
+		));
+		System::assert_has_event(
+			Event::ServiceFeeWaivedForCall { who: account.clone(), waived_fee: 1000 }.into(),
+		);
+		// The free call is kept for the calls which aren't waived
+		assert_eq!(crate::FreeCallsUsed::<Test>::get(&account), 0);
+		assert!(EvmAccountMapping::has_free_call(&account));
+	});
+}
+
+#[test]
+fn on_charge_meta_transaction_applies() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		WaiveTransactionFees::set(true);
+
+		let (account, call, nonce, signature) = signed_remark();
+		set_balance(account.clone(), DOLLARS);
+
+		assert_ok!(EvmAccountMapping::meta_call(
+			RuntimeOrigin::none(),
+			account.clone(),
+			Box::new(call),
+			nonce,
+			signature,
+			None,
+			PayloadVersion::V0
+		));
+		// Only the service fee is charged
+		assert_eq!(Balances::free_balance(&account), DOLLARS - 1000);
+		assert_eq!(FeeUnbalancedAmount::get(), 0);
+	});
+}
+
+#[test]
+fn fee_adjustment_applies() {
+	use codec::Encode;
+	use frame_support::dispatch::GetDispatchInfo;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let (account, call, nonce, signature) = signed_remark();
+		set_balance(account.clone(), DOLLARS);
+
+		let full_fee = TransactionPayment::compute_fee(
+			call.encoded_size() as u32,
+			&call.get_dispatch_info(),
+			0,
+		);
+		assert!(full_fee > 0);
+
+		assert_ok!(EvmAccountMapping::meta_call(
+			RuntimeOrigin::none(),
+			account.clone(),
+			Box::new(call),
+			nonce,
+			signature,
+			None,
+			PayloadVersion::V0
+		));
+		System::assert_last_event(
+			Event::TransactionFeePaid { who: account, actual_fee: full_fee / 2, tip: 0 }.into(),
+		);
+	});
+}
+
+#[test]
+fn meta_transaction_fees_are_settled_in_one_event() {
+	use codec::Encode;
+	use frame_support::dispatch::GetDispatchInfo;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let (account, call, nonce, signature) = signed_remark();
+		set_balance(account.clone(), DOLLARS);
+		let full_fee = TransactionPayment::compute_fee(
+			call.encoded_size() as u32,
+			&call.get_dispatch_info(),
+			0,
+		);
+
+		assert_ok!(EvmAccountMapping::meta_call(
+			RuntimeOrigin::none(),
+			account.clone(),
+			Box::new(call),
+			nonce,
+			signature,
+			None,
+			PayloadVersion::V0
+		));
+		System::assert_has_event(
+			Event::MetaTransactionFeeSettled {
+				who: account.clone(),
+				payer: account,
+				service_fee: 1000,
+				inclusion_fee: full_fee / 2,
+				tip: 0,
+				asset_tip: None,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn receipts_are_stored_and_pruned() {
+	use codec::Encode;
+	use frame_support::dispatch::GetDispatchInfo;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let (account, call, nonce, signature) = signed_remark();
+		set_balance(account.clone(), DOLLARS);
+		// The fee of remarks is halved by `FeeAdjustment`
+		let actual_fee = TransactionPayment::compute_fee(
+			call.encoded_size() as u32,
+			&call.get_dispatch_info(),
+			0,
+		) / 2;
+
+		assert_ok!(EvmAccountMapping::meta_call(
+			RuntimeOrigin::none(),
+			account.clone(),
+			Box::new(call),
+			nonce,
+			signature,
+			None,
+			PayloadVersion::V0
+		));
+		assert_eq!(
+			crate::Receipts::<Test>::get(&account, nonce),
+			Some(crate::Receipt { block_number: 1, success: true, error: None, actual_fee })
+		);
+		assert_eq!(
+			EvmAccountMapping::receipt(&account, nonce),
+			crate::Receipts::<Test>::get(&account, nonce)
+		);
+
+		run_to_block(10);
+		assert!(crate::Receipts::<Test>::contains_key(&account, nonce));
+		run_to_block(11);
+		assert!(!crate::Receipts::<Test>::contains_key(&account, nonce));
+		assert_eq!(crate::ReceiptExpiries::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn estimate_meta_fee_works() {
+	use codec::Encode;
+	use frame_support::dispatch::GetDispatchInfo;
+
+	new_test_ext().execute_with(|| {
+		let (_, call, _, _) = signed_remark();
+		let call_data = call.encode();
+		let fee = TransactionPayment::compute_fee(
+			call_data.len() as u32,
+			&call.get_dispatch_info(),
+			10,
+		) / 2;
+
+		assert_eq!(
+			EvmAccountMapping::estimate_meta_fee(&call_data, 10),
+			Some(crate::MetaFeeEstimate {
+				inclusion_fee: fee - 10,
+				tip: 10,
+				service_fee: 1000,
+				total: fee + 1000,
+			})
+		);
+		assert_eq!(EvmAccountMapping::estimate_meta_fee(&[0xff, 0xff], 0), None);
+
+		// The service fee is waived for the free calls of the account
+		let (account, ..) = signed_remark();
+		assert_eq!(
+			EvmAccountMapping::estimate_meta_fee_of(&account, &call_data, 10)
+				.map(|estimate| estimate.service_fee),
+			Some(1000)
+		);
+		FreeCallsPerAccount::set(1);
+		assert_eq!(
+			EvmAccountMapping::estimate_meta_fee_of(&account, &call_data, 10),
+			Some(crate::MetaFeeEstimate {
+				inclusion_fee: fee - 10,
+				tip: 10,
+				service_fee: 0,
+				total: fee,
+			})
+		);
+	});
+}
+
+/// Sign `message_hash` with the dev ECDSA key of `seed`.
+fn sign(seed: u8, message_hash: &[u8; 32]) -> crate::MetaSignature {
+	use sp_core::Pair;
+
+	let pair = sp_core::ecdsa::Pair::from_seed(&[seed; 32]);
+	pair.sign_prehashed(message_hash).0.into()
+}
+
+/// The account mapped from the dev ECDSA key of `seed`.
+fn mapped_account(seed: u8) -> AccountId {
+	use sp_core::Pair;
+
+	let public = sp_core::ecdsa::Pair::from_seed(&[seed; 32]).public();
+	crate::SubstrateAddressConverter::try_convert(&public.0).expect("Convertable")
+}
+
+#[test]
+fn meta_typed_call_works() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let account = mapped_account(1);
+		let dest = AccountId::from([2u8; 32]);
+		set_balance(account.clone(), DOLLARS);
+
+		let typed_call = crate::TypedCall::BalanceTransfer { to: dest.clone(), amount: 10 * CENTS };
+		let message_hash = EvmAccountMapping::typed_call_message_hash(&account, &typed_call, 0);
+		let signature = sign(1, &message_hash);
+
+		assert_ok!(EvmAccountMapping::meta_typed_call(
+			RuntimeOrigin::none(),
+			account.clone(),
+			typed_call.clone(),
+			0,
+			signature,
+			None
+		));
+		assert_eq!(Balances::free_balance(&dest), 10 * CENTS);
+		assert_eq!(crate::AccountNonce::<Test>::get(&account), 1);
+
+		// The signature doesn't cover a different amount
+		let tampered = crate::TypedCall::BalanceTransfer { to: dest, amount: 20 * CENTS };
+		assert_noop!(
+			EvmAccountMapping::meta_typed_call(
+				RuntimeOrigin::none(),
+				account,
+				tampered,
+				1,
+				signature,
+				None
+			),
+			Error::<Test>::InvalidSignature
+		);
+	});
+}
+
+#[test]
+fn unconvertible_typed_call_is_charged_its_failure_weight() {
+	use crate::WeightInfo;
+	use frame_support::dispatch::GetDispatchInfo;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let account = mapped_account(1);
+		set_balance(account.clone(), DOLLARS);
+		ConvertTypedCalls::set(false);
+
+		let typed_call = crate::TypedCall::Remark { remark: b"Hello".to_vec() };
+		let message_hash = EvmAccountMapping::typed_call_message_hash(&account, &typed_call, 0);
+		let signature = sign(1, &message_hash);
+		let call = crate::Call::<Test>::meta_typed_call {
+			who: account.clone(),
+			typed_call: typed_call.clone(),
+			nonce: 0,
+			signature,
+			tip: None,
+		};
+		let info = call.get_dispatch_info();
+		assert_eq!(info.weight, <() as WeightInfo>::meta_typed_call_unconvertible());
+		assert!(!info.weight.is_zero());
+
+		assert_noop!(
+			EvmAccountMapping::meta_typed_call(
+				RuntimeOrigin::none(),
+				account,
+				typed_call,
+				0,
+				signature,
+				None
+			),
+			Error::<Test>::UnconvertibleCall
+		);
+	});
+}
+
+#[test]
+fn meta_transfer_works() {
+	use frame_support::{pallet_prelude::ValidateUnsigned, unsigned::TransactionSource};
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let account = mapped_account(1);
+		let dest = AccountId::from([2u8; 32]);
+		set_balance(account.clone(), DOLLARS);
+
+		// The account can't afford to transfer its whole balance on top of the fees
+		let message_hash =
+			EvmAccountMapping::meta_transfer_message_hash(&account, &dest, DOLLARS, 0);
+		let call = crate::Call::meta_transfer {
+			who: account.clone(),
+			dest: dest.clone(),
+			amount: DOLLARS,
+			nonce: 0,
+			signature: sign(1, &message_hash),
+		};
+		assert_eq!(
+			EvmAccountMapping::validate_unsigned(TransactionSource::External, &call),
+			Err(sp_runtime::transaction_validity::InvalidTransaction::Payment.into())
+		);
+
+		let message_hash =
+			EvmAccountMapping::meta_transfer_message_hash(&account, &dest, 10 * CENTS, 0);
+		let signature = sign(1, &message_hash);
+		let call = crate::Call::meta_transfer {
+			who: account.clone(),
+			dest: dest.clone(),
+			amount: 10 * CENTS,
+			nonce: 0,
+			signature,
+		};
+		assert_ok!(EvmAccountMapping::validate_unsigned(TransactionSource::External, &call));
+
+		assert_ok!(EvmAccountMapping::meta_transfer(
+			RuntimeOrigin::none(),
+			account.clone(),
+			dest.clone(),
+			10 * CENTS,
+			0,
+			signature
+		));
+		assert_eq!(Balances::free_balance(&dest), 10 * CENTS);
+		assert_eq!(crate::AccountNonce::<Test>::get(&account), 1);
+		assert!(crate::Receipts::<Test>::get(&account, 0).expect("Stored").success);
+
+		// The signature doesn't cover a different destination
+		assert_noop!(
+			EvmAccountMapping::meta_transfer(
+				RuntimeOrigin::none(),
+				account.clone(),
+				AccountId::from([3u8; 32]),
+				10 * CENTS,
+				1,
+				signature
+			),
+			Error::<Test>::InvalidSignature
+		);
+	});
+}
+
+#[test]
+fn unsigned_longevity_and_tag_prefix_apply() {
+	use frame_support::{pallet_prelude::ValidateUnsigned, unsigned::TransactionSource};
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let (account, call, nonce, signature) = signed_remark();
+		set_balance(account.clone(), DOLLARS);
+
+		let valid = EvmAccountMapping::validate_unsigned(
+			TransactionSource::External,
+			&crate::Call::meta_call {
+				who: account.clone(),
+				call: Box::new(call),
+				nonce,
+				signature,
+				tip: None,
+				version: PayloadVersion::V0,
+			},
+		)
+		.expect("Valid");
+		assert_eq!(valid.longevity, 5);
+		assert_eq!(
+			valid.provides,
+			vec![codec::Encode::encode(&(
+				"EVMAccountMapping",
+				codec::Encode::encode(&(&account, nonce))
+			))]
+		);
+	});
+}
+
+#[test]
+fn spend_existence_requirement_applies() {
+	use codec::Encode;
+	use frame_support::{
+		dispatch::GetDispatchInfo, pallet_prelude::ValidateUnsigned, traits::ExistenceRequirement,
+		unsigned::TransactionSource,
+	};
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let account = mapped_account(1);
+		let dest = AccountId::from([2u8; 32]);
+		set_balance(account.clone(), DOLLARS);
+
+		let fee_of = |amount| {
+			let call = RuntimeCall::from(crate::Call::meta_transfer {
+				who: account.clone(),
+				dest: dest.clone(),
+				amount,
+				nonce: 0,
+				signature: [0u8; 65].into(),
+			});
+			TransactionPayment::compute_fee(
+				call.encoded_size() as u32,
+				&call.get_dispatch_info(),
+				0,
+			)
+		};
+		// Transfer everything left after the fees
+		let amount = DOLLARS - 1000 - fee_of(DOLLARS);
+		assert_eq!(fee_of(amount), fee_of(DOLLARS));
+
+		let message_hash =
+			EvmAccountMapping::meta_transfer_message_hash(&account, &dest, amount, 0);
+		let signature = sign(1, &message_hash);
+		let call = crate::Call::meta_transfer {
+			who: account.clone(),
+			dest: dest.clone(),
+			amount,
+			nonce: 0,
+			signature,
+		};
+		assert_eq!(
+			EvmAccountMapping::validate_unsigned(TransactionSource::External, &call),
+			Err(sp_runtime::transaction_validity::InvalidTransaction::Payment.into())
+		);
+
+		SpendExistenceRequirement::set(ExistenceRequirement::AllowDeath);
+		assert_ok!(EvmAccountMapping::validate_unsigned(TransactionSource::External, &call));
+		assert_ok!(EvmAccountMapping::meta_transfer(
+			RuntimeOrigin::none(),
+			account.clone(),
+			dest.clone(),
+			amount,
+			0,
+			signature
+		));
+		assert_eq!(Balances::free_balance(&dest), amount);
+		assert!(!System::account_exists(&account));
+		// The nonce outlives the account
+		assert_eq!(crate::AccountNonce::<Test>::get(&account), 1);
+	});
+}
+
+#[test]
+fn pre_dispatch_records_the_signer() {
+	use frame_support::pallet_prelude::ValidateUnsigned;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let (account, call, nonce, signature) = signed_remark();
+		set_balance(account.clone(), DOLLARS);
+		let meta_call = crate::Call::meta_call {
+			who: account.clone(),
+			call: Box::new(call.clone()),
+			nonce,
+			signature,
+			tip: None,
+			version: PayloadVersion::V0,
+		};
+
+		assert_ok!(EvmAccountMapping::pre_dispatch(&meta_call));
+		assert!(crate::PreDispatchedSigner::<Test>::exists());
+
+		assert_ok!(EvmAccountMapping::meta_call(
+			RuntimeOrigin::none(),
+			account.clone(),
+			Box::new(call),
+			nonce,
+			signature,
+			None,
+			PayloadVersion::V0
+		));
+		assert!(!crate::PreDispatchedSigner::<Test>::exists());
+		assert!(crate::EvmAddresses::<Test>::contains_key(&account));
+
+		assert_eq!(
+			EvmAccountMapping::pre_dispatch(&meta_call),
+			Err(sp_runtime::transaction_validity::InvalidTransaction::Stale.into())
+		);
+	});
+}
+
+#[test]
+fn pre_dispatched_signer_does_not_outlive_the_block() {
+	use frame_support::pallet_prelude::ValidateUnsigned;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let (account, call, nonce, signature) = signed_remark();
+		set_balance(account.clone(), DOLLARS);
+		let meta_call = crate::Call::meta_call {
+			who: account.clone(),
+			call: Box::new(call.clone()),
+			nonce,
+			signature,
+			tip: None,
+			version: PayloadVersion::V0,
+		};
+		assert_ok!(EvmAccountMapping::pre_dispatch(&meta_call));
+		let (_, _, evm_address, _) =
+			crate::PreDispatchedSigner::<Test>::get().expect("Recorded by pre_dispatch");
+
+		// The failed dispatch reverts the take of the signer
+		assert_ok!(EvmAccountMapping::block_address(RuntimeOrigin::root(), evm_address));
+		assert_noop!(
+			EvmAccountMapping::meta_call(
+				RuntimeOrigin::none(),
+				account,
+				Box::new(call),
+				nonce,
+				signature,
+				None,
+				PayloadVersion::V0
+			),
+			Error::<Test>::AddressBlocked
+		);
+		assert!(crate::PreDispatchedSigner::<Test>::exists());
+
+		run_to_block(2);
+		assert!(!crate::PreDispatchedSigner::<Test>::exists());
+	});
+}
+
+#[test]
+fn priority_policy_applies() {
+	use crate::PriorityPolicy;
+	use frame_support::{
+		dispatch::GetDispatchInfo, pallet_prelude::ValidateUnsigned, unsigned::TransactionSource,
+	};
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let account = mapped_account(1);
+		set_balance(account.clone(), DOLLARS);
+
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		let message_hash = EvmAccountMapping::eip712_message_hash(
+			account.clone(),
+			&codec::Encode::encode(&call),
+			0,
+		);
+		let validate = |tip| {
+			EvmAccountMapping::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::meta_call {
+					who: account.clone(),
+					call: Box::new(call.clone()),
+					nonce: 0,
+					signature: sign(1, &message_hash),
+					tip,
+					version: PayloadVersion::V0,
+				},
+			)
+			.expect("Valid")
+			.priority
+		};
+
+		let info = call.get_dispatch_info();
+		let len = codec::Encode::encoded_size(&call);
+		assert_eq!(validate(None), crate::DefaultPriority::<Test>::priority(&info, len, 0));
+		assert!(validate(Some(CENTS)) > validate(None));
+	});
+}
+
+#[test]
+fn over_weight_or_wrong_class_calls_are_rejected() {
+	use frame_support::{
+		dispatch::{DispatchClass, DispatchInfo},
+		pallet_prelude::ValidateUnsigned,
+		unsigned::TransactionSource,
+		weights::Weight,
+	};
+	use sp_runtime::transaction_validity::InvalidTransaction;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		assert_ok!(EvmAccountMapping::check_dispatch_info(&DispatchInfo::default()));
+		assert_eq!(
+			EvmAccountMapping::check_dispatch_info(&DispatchInfo {
+				weight: Weight::MAX,
+				..Default::default()
+			}),
+			Err(InvalidTransaction::ExhaustsResources.into())
+		);
+		assert_eq!(
+			EvmAccountMapping::check_dispatch_info(&DispatchInfo {
+				class: DispatchClass::Mandatory,
+				..Default::default()
+			}),
+			Err(InvalidTransaction::BadMandatory.into())
+		);
+
+		// `set_heap_pages` is an operational call
+		let account = mapped_account(1);
+		set_balance(account.clone(), DOLLARS);
+		let call = RuntimeCall::System(frame_system::Call::set_heap_pages { pages: 64 });
+		let message_hash = EvmAccountMapping::eip712_message_hash(
+			account.clone(),
+			&codec::Encode::encode(&call),
+			0,
+		);
+		assert_eq!(
+			EvmAccountMapping::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::meta_call {
+					who: account,
+					call: Box::new(call),
+					nonce: 0,
+					signature: sign(1, &message_hash),
+					tip: None,
+					version: PayloadVersion::V0,
+				},
+			),
+			Err(crate::InvalidMetaTransaction::DispatchClassNotAllowed.into())
+		);
+	});
+}
+
+#[test]
+fn deeply_nested_calls_are_rejected() {
+	use frame_support::{pallet_prelude::ValidateUnsigned, unsigned::TransactionSource};
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let account = mapped_account(1);
+		set_balance(account.clone(), DOLLARS);
+
+		// Every `meta_call` boxes the call it wraps
+		let mut call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		for _ in 0..10 {
+			call = RuntimeCall::EvmAccountMapping(crate::Call::meta_call {
+				who: account.clone(),
+				call: Box::new(call),
+				nonce: 0,
+				signature: [0u8; 65].into(),
+				tip: None,
+				version: PayloadVersion::V0,
+			});
+		}
+		assert!(!EvmAccountMapping::call_within_depth_limit(&codec::Encode::encode(&call)));
+		assert_eq!(EvmAccountMapping::estimate_meta_fee(&codec::Encode::encode(&call), 0), None);
+
+		let message_hash = EvmAccountMapping::eip712_message_hash(
+			account.clone(),
+			&codec::Encode::encode(&call),
+			0,
+		);
+		let signature = sign(1, &message_hash);
+		assert_eq!(
+			EvmAccountMapping::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::meta_call {
+					who: account.clone(),
+					call: Box::new(call.clone()),
+					nonce: 0,
+					signature,
+					tip: None,
+					version: PayloadVersion::V0,
+				},
+			),
+			Err(crate::InvalidMetaTransaction::CallTooDeep.into())
+		);
+		assert_noop!(
+			EvmAccountMapping::meta_call(
+				RuntimeOrigin::none(),
+				account,
+				Box::new(call),
+				0,
+				signature,
+				None,
+				PayloadVersion::V0
+			),
+			Error::<Test>::CallTooDeep
+		);
+	});
+}
+
+#[test]
+fn call_filter_applies_to_wrapped_calls() {
+	use frame_support::{pallet_prelude::ValidateUnsigned, unsigned::TransactionSource};
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let account = mapped_account(1);
+		set_balance(account.clone(), DOLLARS);
+
+		let transfer_all = RuntimeCall::Balances(pallet_balances::Call::transfer_all {
+			dest: AccountId::from([2u8; 32]),
+			keep_alive: true,
+		});
+		let wrapped = RuntimeCall::EvmAccountMapping(crate::Call::meta_call {
+			who: account.clone(),
+			call: Box::new(transfer_all.clone()),
+			nonce: 0,
+			signature: [0u8; 65].into(),
+			tip: None,
+			version: PayloadVersion::V0,
+		});
+		assert!(!EvmAccountMapping::call_allowed(&transfer_all));
+		assert!(!EvmAccountMapping::call_allowed(&wrapped));
+
+		let message_hash = EvmAccountMapping::eip712_message_hash(
+			account.clone(),
+			&codec::Encode::encode(&wrapped),
+			0,
+		);
+		let signature = sign(1, &message_hash);
+		assert_eq!(
+			EvmAccountMapping::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::meta_call {
+					who: account.clone(),
+					call: Box::new(wrapped.clone()),
+					nonce: 0,
+					signature,
+					tip: None,
+					version: PayloadVersion::V0,
+				},
+			),
+			Err(crate::InvalidMetaTransaction::CallFiltered.into())
+		);
+		assert_noop!(
+			EvmAccountMapping::meta_call(
+				RuntimeOrigin::none(),
+				account,
+				Box::new(wrapped),
+				0,
+				signature,
+				None,
+				PayloadVersion::V0
+			),
+			Error::<Test>::CallFiltered
+		);
+	});
+}
+
+#[test]
+fn executed_meta_transactions_are_indexed_off_chain() {
+	let account = mapped_account(1);
+	let dest = AccountId::from([2u8; 32]);
+	let mut evm_address = Default::default();
+
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		run_to_block(1);
+		set_balance(account.clone(), DOLLARS);
+
+		let message_hash =
+			EvmAccountMapping::meta_transfer_message_hash(&account, &dest, 10 * CENTS, 0);
+		let signature = sign(1, &message_hash);
+		evm_address =
+			EvmAccountMapping::recover_signer(&account, &message_hash, &signature.normalize())
+				.expect("Valid signature");
+
+		assert_ok!(EvmAccountMapping::meta_transfer(
+			RuntimeOrigin::none(),
+			account.clone(),
+			dest.clone(),
+			10 * CENTS,
+			0,
+			signature
+		));
+	});
+	ext.persist_offchain_overlay();
+
+	let entry = ext
+		.offchain_db()
+		.get(&crate::offchain_index_key(&evm_address, 0))
+		.expect("Indexed");
+	assert_eq!(
+		crate::IndexedMetaTransaction::<AccountId, u64>::decode(&mut &entry[..])
+			.expect("Decodable"),
+		crate::IndexedMetaTransaction {
+			who: account,
+			block_number: 1,
+			extrinsic_index: 0,
+			success: true,
+			error: None,
+		}
+	);
+}
+
+#[test]
+fn verify_signed_by_works() {
+	new_test_ext().execute_with(|| {
+		let account = mapped_account(1);
+		let struct_hash = sp_io::hashing::keccak_256(b"Claim(string who)");
+		let message_hash = EvmAccountMapping::eip712_typed_data_hash(&struct_hash);
+
+		let signature = sign(1, &message_hash);
+		assert!(EvmAccountMapping::verify_signed_by(&account, &struct_hash, &signature));
+		// Signed by another key
+		let signature = sign(2, &message_hash);
+		assert!(!EvmAccountMapping::verify_signed_by(&account, &struct_hash, &signature));
+		// Signed without the domain
+		let signature = sign(1, &struct_hash);
+		assert!(!EvmAccountMapping::verify_signed_by(&account, &struct_hash, &signature));
+	});
+}
+
+#[test]
+fn eip712_salt_applies() {
+	new_test_ext().execute_with(|| {
+		let account = mapped_account(1);
+		let struct_hash = sp_io::hashing::keccak_256(b"Claim(string who)");
+		let unsalted_signature = sign(1, &EvmAccountMapping::eip712_typed_data_hash(&struct_hash));
+
+		EIP712Salt::set(Some([0x5a; 32]));
+		let domain = crate::eip712::EIP712Domain {
+			name: b"Substrate".to_vec(),
+			version: b"1".to_vec(),
+			chain_id: sp_core::U256::from(0),
+			verifying_contract: sp_core::H160::from([0u8; 20]),
+			salt: Some([0x5a; 32]),
+		};
+		let message_hash = crate::eip712::typed_data_hash(&domain.separator(), &struct_hash);
+		assert_eq!(EvmAccountMapping::eip712_typed_data_hash(&struct_hash), message_hash);
+
+		let signature = sign(1, &message_hash);
+		assert!(EvmAccountMapping::verify_signed_by(&account, &struct_hash, &signature));
+		// Signed within the domain without the salt
+		assert!(!EvmAccountMapping::verify_signed_by(&account, &struct_hash, &unsalted_signature));
+	});
+}
+
+#[test]
+fn cheap_checks_run_before_signature_recovery() {
+	use frame_support::{pallet_prelude::ValidateUnsigned, unsigned::TransactionSource};
+	use sp_runtime::transaction_validity::InvalidTransaction;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let (account, call, nonce, _) = signed_remark();
+		let meta_call = |nonce| crate::Call::meta_call {
+			who: account.clone(),
+			call: Box::new(call.clone()),
+			nonce,
+			signature: [0u8; 65].into(),
+			tip: None,
+			version: PayloadVersion::V0,
+		};
+
+		// The account doesn't exist
+		assert_eq!(
+			EvmAccountMapping::validate_unsigned(TransactionSource::External, &meta_call(nonce)),
+			Err(InvalidTransaction::Payment.into())
+		);
+
+		set_balance(account.clone(), DOLLARS);
+		crate::AccountNonce::<Test>::insert(&account, 1);
+		assert_eq!(
+			EvmAccountMapping::validate_unsigned(TransactionSource::External, &meta_call(nonce)),
+			Err(InvalidTransaction::Stale.into())
+		);
+
+		// Nor hashed
+		assert_eq!(
+			EvmAccountMapping::check_meta_transaction(
+				&account,
+				&call,
+				nonce,
+				|| -> [u8; 32] { unreachable!("A stale payload is hashed") },
+				&crate::MetaSignature::from([0u8; 65]),
+				None,
+				0,
+			)
+			.map(|_| ()),
+			Err(InvalidTransaction::Stale.into())
+		);
+
+		// Only a payload passing the cheap checks gets its signature recovered
+		assert_eq!(
+			EvmAccountMapping::validate_unsigned(TransactionSource::External, &meta_call(1)),
+			Err(InvalidTransaction::Call.into())
+		);
+	});
+}
+
+#[test]
+fn only_applicable_sponsorships_defer_the_payment_check() {
+	use crate::{CallSelector, SponsorshipTarget};
+	use frame_support::{pallet_prelude::ValidateUnsigned, unsigned::TransactionSource};
+	use sp_runtime::transaction_validity::InvalidTransaction;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let sponsor = AccountId::from([9u8; 32]);
+		set_balance(sponsor.clone(), DOLLARS);
+		let sponsor_target = |target| {
+			assert_ok!(EvmAccountMapping::sponsor(
+				RuntimeOrigin::signed(sponsor.clone()),
+				target,
+				DOLLARS,
+				10
+			));
+		};
+		// The account has no balance
+		let (account, call, nonce, _) = signed_remark();
+		let meta_call = crate::Call::meta_call {
+			who: account.clone(),
+			call: Box::new(call),
+			nonce,
+			signature: [0u8; 65].into(),
+			tip: None,
+			version: PayloadVersion::V0,
+		};
+		let validate =
+			|| EvmAccountMapping::validate_unsigned(TransactionSource::External, &meta_call);
+
+		// The sponsorships of other calls and signers don't apply
+		sponsor_target(SponsorshipTarget::Call(CallSelector::Pallet(1)));
+		crate::EvmAddresses::<Test>::insert(&account, sp_core::H160::repeat_byte(1));
+		sponsor_target(SponsorshipTarget::Signer(sp_core::H160::repeat_byte(2)));
+		assert_eq!(validate(), Err(InvalidTransaction::Payment.into()));
+
+		// The signer of an account whose address isn't recorded is only known after the recovery
+		crate::EvmAddresses::<Test>::remove(&account);
+		assert_eq!(validate(), Err(InvalidTransaction::Call.into()));
+
+		crate::EvmAddresses::<Test>::insert(&account, sp_core::H160::repeat_byte(1));
+		sponsor_target(SponsorshipTarget::Call(CallSelector::Pallet(0)));
+		assert_eq!(validate(), Err(InvalidTransaction::Call.into()));
+	});
+}
+
+#[test]
+fn payload_versions_are_verified_concurrently() {
+	use frame_support::{pallet_prelude::ValidateUnsigned, unsigned::TransactionSource};
+	use sp_runtime::transaction_validity::InvalidTransaction;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let account = mapped_account(1);
+		set_balance(account.clone(), DOLLARS);
+
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		let call_data = codec::Encode::encode(&call);
+		let meta_call = |version, signature, tip| crate::Call::meta_call {
+			who: account.clone(),
+			call: Box::new(call.clone()),
+			nonce: 0,
+			signature,
+			tip,
+			version,
+		};
+
+		// The V0 payload doesn't sign the tip
+		let v0_hash = EvmAccountMapping::meta_call_message_hash(
+			PayloadVersion::V0,
+			&account,
+			&call_data,
+			0,
+			Some(CENTS),
+		);
+		assert_eq!(v0_hash, EvmAccountMapping::eip712_message_hash(account.clone(), &call_data, 0));
+		assert_ok!(EvmAccountMapping::validate_unsigned(
+			TransactionSource::External,
+			&meta_call(PayloadVersion::V0, sign(1, &v0_hash), Some(CENTS)),
+		));
+
+		let v1_hash = EvmAccountMapping::meta_call_message_hash(
+			PayloadVersion::V1,
+			&account,
+			&call_data,
+			0,
+			Some(CENTS),
+		);
+		assert_ne!(v1_hash, v0_hash);
+		assert_ok!(EvmAccountMapping::validate_unsigned(
+			TransactionSource::External,
+			&meta_call(PayloadVersion::V1, sign(1, &v1_hash), Some(CENTS)),
+		));
+		// The V1 payload signs the tip
+		assert_eq!(
+			EvmAccountMapping::validate_unsigned(
+				TransactionSource::External,
+				&meta_call(PayloadVersion::V1, sign(1, &v1_hash), Some(DOLLARS / 2)),
+			),
+			Err(InvalidTransaction::BadSigner.into())
+		);
+		// A payload is only valid for the version it was signed for
+		assert_eq!(
+			EvmAccountMapping::validate_unsigned(
+				TransactionSource::External,
+				&meta_call(PayloadVersion::V1, sign(1, &v0_hash), Some(CENTS)),
+			),
+			Err(InvalidTransaction::BadSigner.into())
+		);
+
+		assert_ok!(EvmAccountMapping::meta_call(
+			RuntimeOrigin::none(),
+			account.clone(),
+			Box::new(call.clone()),
+			0,
+			sign(1, &v1_hash),
+			Some(CENTS),
+			PayloadVersion::V1
+		));
+		assert_eq!(crate::AccountNonce::<Test>::get(&account), 1);
+	});
+}
+
+#[test]
+fn recovery_works() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let account = mapped_account(1);
+		let new_controller = AccountId::from([2u8; 32]);
+		let lost_key = sp_core::H160::repeat_byte(1);
+		set_balance(account.clone(), DOLLARS);
+
+		// Only the accounts known to be mapped are recovered
+		assert_noop!(
+			EvmAccountMapping::initiate_recovery(
+				RuntimeOrigin::root(),
+				account.clone(),
+				new_controller.clone()
+			),
+			Error::<Test>::NotMapped
+		);
+		EvmAccountMapping::note_evm_address(&account, lost_key);
+		crate::Controllers::<Test>::insert(&account, lost_key);
+
+		assert_noop!(
+			EvmAccountMapping::initiate_recovery(
+				RuntimeOrigin::signed(new_controller.clone()),
+				account.clone(),
+				new_controller.clone()
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			EvmAccountMapping::initiate_recovery(
+				RuntimeOrigin::root(),
+				account.clone(),
+				account.clone()
+			),
+			Error::<Test>::RecoveryToSelf
+		);
+		assert_ok!(EvmAccountMapping::initiate_recovery(
+			RuntimeOrigin::root(),
+			account.clone(),
+			new_controller.clone()
+		));
+		System::assert_last_event(
+			Event::RecoveryInitiated {
+				who: account.clone(),
+				new_controller: new_controller.clone(),
+				executable_at: 11,
+			}
+			.into(),
+		);
+		assert_noop!(
+			EvmAccountMapping::initiate_recovery(
+				RuntimeOrigin::root(),
+				account.clone(),
+				new_controller.clone()
+			),
+			Error::<Test>::RecoveryAlreadyInitiated
+		);
+
+		// The recovery is executable after the veto period only
+		assert_noop!(
+			EvmAccountMapping::execute_recovery(
+				RuntimeOrigin::signed(new_controller.clone()),
+				account.clone()
+			),
+			Error::<Test>::RecoveryNotExecutable
+		);
+		run_to_block(11);
+		assert_ok!(EvmAccountMapping::execute_recovery(
+			RuntimeOrigin::signed(new_controller.clone()),
+			account.clone()
+		));
+		System::assert_last_event(
+			Event::AccountRecovered {
+				who: account.clone(),
+				new_controller: new_controller.clone(),
+				amount: DOLLARS,
+			}
+			.into(),
+		);
+		assert_eq!(Balances::free_balance(&account), 0);
+		assert_eq!(Balances::free_balance(&new_controller), DOLLARS);
+		assert!(!crate::Recoveries::<Test>::contains_key(&account));
+
+		// The address of the lost key resolves to the new controller
+		assert_eq!(EvmAccountMapping::map_evm_address(&lost_key), Some(new_controller));
+		assert_eq!(EvmAccountMapping::evm_address_of_account(&account), None);
+		assert!(!crate::Controllers::<Test>::contains_key(&account));
+	});
+}
+
+#[test]
+fn original_key_vetoes_recovery() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let account = mapped_account(1);
+		let new_controller = AccountId::from([2u8; 32]);
+		set_balance(account.clone(), DOLLARS);
+
+		assert_noop!(
+			EvmAccountMapping::veto_recovery(RuntimeOrigin::signed(account.clone())),
+			Error::<Test>::NoRecovery
+		);
+		EvmAccountMapping::note_evm_address(&account, sp_core::H160::repeat_byte(1));
+		assert_ok!(EvmAccountMapping::initiate_recovery(
+			RuntimeOrigin::root(),
+			account.clone(),
+			new_controller.clone()
+		));
+
+		// The EVM key vetoes with a meta-transaction
+		let call = RuntimeCall::EvmAccountMapping(crate::Call::veto_recovery {});
+		let message_hash = EvmAccountMapping::eip712_message_hash(
+			account.clone(),
+			&codec::Encode::encode(&call),
+			0,
+		);
+		assert_ok!(EvmAccountMapping::meta_call(
+			RuntimeOrigin::none(),
+			account.clone(),
+			Box::new(call),
+			0,
+			sign(1, &message_hash),
+			None,
+			PayloadVersion::V0
+		));
+		System::assert_has_event(Event::RecoveryVetoed { who: account.clone() }.into());
+
+		run_to_block(11);
+		assert_noop!(
+			EvmAccountMapping::execute_recovery(
+				RuntimeOrigin::signed(new_controller.clone()),
+				account.clone()
+			),
+			Error::<Test>::NoRecovery
+		);
+	});
+}
+
+#[test]
+fn link_accounts_works() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let who = mapped_account(1);
+		let native = AccountId::from([2u8; 32]);
+		let message_hash = EvmAccountMapping::link_accounts_message_hash(&who, &native, 0);
+
+		// Signed for another native account
+		assert_noop!(
+			EvmAccountMapping::link_accounts(
+				RuntimeOrigin::signed(AccountId::from([3u8; 32])),
+				who.clone(),
+				0,
+				sign(1, &message_hash)
+			),
+			Error::<Test>::InvalidSignature
+		);
+		// Signed by another EVM key
+		assert_noop!(
+			EvmAccountMapping::link_accounts(
+				RuntimeOrigin::signed(native.clone()),
+				who.clone(),
+				0,
+				sign(2, &message_hash)
+			),
+			Error::<Test>::InvalidSignature
+		);
+
+		assert_ok!(EvmAccountMapping::link_accounts(
+			RuntimeOrigin::signed(native.clone()),
+			who.clone(),
+			0,
+			sign(1, &message_hash)
+		));
+		System::assert_last_event(
+			Event::AccountsLinked { who: who.clone(), account: native.clone() }.into(),
+		);
+		assert_eq!(crate::Links::<Test>::get(&who), Some(native.clone()));
+		assert_eq!(crate::ReverseLinks::<Test>::get(&native), Some(who.clone()));
+		assert_eq!(crate::AccountNonce::<Test>::get(&who), 1);
+
+		// The signature can't be replayed
+		assert_noop!(
+			EvmAccountMapping::link_accounts(
+				RuntimeOrigin::signed(native.clone()),
+				who.clone(),
+				0,
+				sign(1, &message_hash)
+			),
+			Error::<Test>::NonceError
+		);
+		let message_hash = EvmAccountMapping::link_accounts_message_hash(&who, &native, 1);
+		assert_noop!(
+			EvmAccountMapping::link_accounts(
+				RuntimeOrigin::signed(native.clone()),
+				who.clone(),
+				1,
+				sign(1, &message_hash)
+			),
+			Error::<Test>::AlreadyLinked
+		);
+
+		// Either side may unlink
+		assert_ok!(EvmAccountMapping::unlink_accounts(RuntimeOrigin::signed(native.clone())));
+		System::assert_last_event(
+			Event::AccountsUnlinked { who: who.clone(), account: native.clone() }.into(),
+		);
+		assert!(!crate::Links::<Test>::contains_key(&who));
+		assert!(!crate::ReverseLinks::<Test>::contains_key(&native));
+		assert_noop!(
+			EvmAccountMapping::unlink_accounts(RuntimeOrigin::signed(who.clone())),
+			Error::<Test>::NotLinked
+		);
+
+		assert_ok!(EvmAccountMapping::link_accounts(
+			RuntimeOrigin::signed(native.clone()),
+			who.clone(),
+			1,
+			sign(1, &message_hash)
+		));
+		assert_ok!(EvmAccountMapping::unlink_accounts(RuntimeOrigin::signed(who.clone())));
+		assert!(!crate::ReverseLinks::<Test>::contains_key(&native));
+	});
+}
+
+#[test]
+fn meta_call_as_linked_dispatches_as_the_native_account() {
+	use frame_support::{pallet_prelude::ValidateUnsigned, unsigned::TransactionSource};
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let who = mapped_account(1);
+		let native = AccountId::from([2u8; 32]);
+		set_balance(who.clone(), DOLLARS);
+		set_balance(native.clone(), DOLLARS);
+
+		let call = RuntimeCall::System(frame_system::Call::remark_with_event {
+			remark: b"Hello".to_vec(),
+		});
+		let message_hash = EvmAccountMapping::call_as_linked_message_hash(
+			&who,
+			&native,
+			&codec::Encode::encode(&call),
+			0,
+			None,
+		);
+		let meta_call = crate::Call::meta_call_as_linked {
+			who: who.clone(),
+			call: Box::new(call.clone()),
+			nonce: 0,
+			signature: sign(1, &message_hash),
+			tip: None,
+		};
+		assert_eq!(
+			EvmAccountMapping::validate_unsigned(TransactionSource::External, &meta_call),
+			Err(crate::InvalidMetaTransaction::NotLinked.into())
+		);
+
+		let link_hash = EvmAccountMapping::link_accounts_message_hash(&who, &native, 0);
+		assert_ok!(EvmAccountMapping::link_accounts(
+			RuntimeOrigin::signed(native.clone()),
+			who.clone(),
+			0,
+			sign(1, &link_hash)
+		));
+
+		// The nonce has been used by linking
+		let message_hash = EvmAccountMapping::call_as_linked_message_hash(
+			&who,
+			&native,
+			&codec::Encode::encode(&call),
+			1,
+			None,
+		);
+		let signature = sign(1, &message_hash);
+		assert_ok!(EvmAccountMapping::validate_unsigned(
+			TransactionSource::External,
+			&crate::Call::meta_call_as_linked {
+				who: who.clone(),
+				call: Box::new(call.clone()),
+				nonce: 1,
+				signature,
+				tip: None,
+			}
+		));
+		assert_ok!(EvmAccountMapping::meta_call_as_linked(
+			RuntimeOrigin::none(),
+			who.clone(),
+			Box::new(call),
+			1,
+			signature,
+			None
+		));
+		System::assert_has_event(
+			frame_system::Event::Remarked {
+				sender: native.clone(),
+				hash: sp_core::H256(sp_io::hashing::blake2_256(b"Hello")),
+			}
+			.into(),
+		);
+		// The mapped account pays the fees
+		assert_eq!(Balances::free_balance(&native), DOLLARS);
+		assert!(Balances::free_balance(&who) < DOLLARS);
+	});
+}
+
+#[test]
+fn grants_expire() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let granter = AccountId::from([1u8; 32]);
+		let grantee = AccountId::from([2u8; 32]);
+		let kind = crate::GrantKind::Delegation;
+
+		assert_noop!(
+			EvmAccountMapping::grant(
+				RuntimeOrigin::signed(granter.clone()),
+				grantee.clone(),
+				kind,
+				1
+			),
+			Error::<Test>::InvalidExpiry
+		);
+		assert_noop!(
+			EvmAccountMapping::revoke(
+				RuntimeOrigin::signed(granter.clone()),
+				grantee.clone(),
+				kind
+			),
+			Error::<Test>::NoGrant
+		);
+
+		assert_ok!(EvmAccountMapping::grant(
+			RuntimeOrigin::signed(granter.clone()),
+			grantee.clone(),
+			kind,
+			3
+		));
+		System::assert_last_event(
+			Event::Granted {
+				granter: granter.clone(),
+				grantee: grantee.clone(),
+				kind,
+				expires_at: 3,
+			}
+			.into(),
+		);
+		assert_eq!(
+			EvmAccountMapping::active_grant(&granter, &grantee, kind),
+			Some(crate::Grant { expires_at: 3 })
+		);
+		assert_eq!(EvmAccountMapping::active_grant(&grantee, &granter, kind), None);
+
+		// The grant is inactive as soon as it expires, even before it is swept
+		run_to_block(3);
+		assert!(crate::Grants::<Test>::contains_key((&granter, &grantee, kind)));
+		assert_eq!(EvmAccountMapping::active_grant(&granter, &grantee, kind), None);
+
+		// Renewing replaces the expiry
+		assert_ok!(EvmAccountMapping::grant(
+			RuntimeOrigin::signed(granter.clone()),
+			grantee.clone(),
+			kind,
+			5
+		));
+		assert!(!crate::GrantExpiries::<Test>::contains_key(3, (&granter, &grantee, kind)));
+		assert_eq!(
+			EvmAccountMapping::active_grant(&granter, &grantee, kind),
+			Some(crate::Grant { expires_at: 5 })
+		);
+
+		assert_ok!(EvmAccountMapping::revoke(
+			RuntimeOrigin::signed(granter.clone()),
+			grantee.clone(),
+			kind
+		));
+		System::assert_last_event(
+			Event::Revoked { granter: granter.clone(), grantee: grantee.clone(), kind }.into(),
+		);
+		assert_eq!(EvmAccountMapping::active_grant(&granter, &grantee, kind), None);
+		assert_eq!(crate::GrantExpiries::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn on_idle_sweeps_expired_grants_within_the_weight() {
+	use frame_support::{
+		traits::Hooks,
+		weights::{RuntimeDbWeight, Weight},
+	};
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		DbWeight::set(RuntimeDbWeight { read: 1, write: 1 });
+
+		let granter = AccountId::from([1u8; 32]);
+		let kind = crate::GrantKind::Delegation;
+		for i in 2..5u8 {
+			assert_ok!(EvmAccountMapping::grant(
+				RuntimeOrigin::signed(granter.clone()),
+				AccountId::from([i; 32]),
+				kind,
+				2
+			));
+		}
+		assert_eq!(crate::Grants::<Test>::iter().count(), 3);
+
+		// Nothing has expired yet
+		let weight = EvmAccountMapping::on_idle(1, Weight::from_parts(1_000, 0));
+		assert_eq!(weight, Weight::from_parts(3, 0));
+		assert_eq!(crate::Grants::<Test>::iter().count(), 3);
+		assert_eq!(crate::NextGrantSweep::<Test>::get(), Some(2));
+
+		// Not enough weight for the cursor
+		run_to_block(2);
+		assert_eq!(EvmAccountMapping::on_idle(2, Weight::from_parts(1, 0)), Weight::zero());
+		assert_eq!(crate::Grants::<Test>::iter().count(), 3);
+
+		// The cursor, the expiries of the block and two grants
+		let weight = EvmAccountMapping::on_idle(2, Weight::from_parts(17, 0));
+		assert_eq!(weight, Weight::from_parts(17, 0));
+		assert_eq!(crate::Grants::<Test>::iter().count(), 1);
+		assert_eq!(crate::NextGrantSweep::<Test>::get(), Some(2));
+
+		// The rest is swept in the next block
+		run_to_block(3);
+		EvmAccountMapping::on_idle(3, Weight::from_parts(1_000, 0));
+		assert_eq!(crate::Grants::<Test>::iter().count(), 0);
+		assert_eq!(crate::GrantExpiries::<Test>::iter().count(), 0);
+		assert_eq!(crate::NextGrantSweep::<Test>::get(), Some(4));
+	});
+}
+
+#[test]
+fn meta_call_with_asset_tip_works() {
+	use frame_support::{pallet_prelude::ValidateUnsigned, unsigned::TransactionSource};
+	use sp_runtime::transaction_validity::InvalidTransaction;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let who = mapped_account(1);
+		set_balance(who.clone(), DOLLARS);
+
+		let call = RuntimeCall::System(frame_system::Call::remark_with_event {
+			remark: b"Hello".to_vec(),
+		});
+		let call_data = codec::Encode::encode(&call);
+		let sign_tip = |tip: &crate::AssetTip<u32>| {
+			sign(1, &EvmAccountMapping::asset_tip_message_hash(&who, &call_data, 0, tip))
+		};
+
+		// The asset isn't supported by the tip handler
+		let tip = crate::AssetTip { asset_id: 2, amount: 2 * CENTS };
+		let meta_call = crate::Call::meta_call_with_asset_tip {
+			who: who.clone(),
+			call: Box::new(call.clone()),
+			nonce: 0,
+			signature: sign_tip(&tip),
+			tip,
+		};
+		assert_eq!(
+			EvmAccountMapping::validate_unsigned(TransactionSource::External, &meta_call),
+			Err(InvalidTransaction::Payment.into())
+		);
+
+		// The tip is signed
+		let tip = crate::AssetTip { asset_id: 1, amount: 2 * CENTS };
+		assert_noop!(
+			EvmAccountMapping::meta_call_with_asset_tip(
+				RuntimeOrigin::none(),
+				who.clone(),
+				Box::new(call.clone()),
+				0,
+				sign_tip(&crate::AssetTip { asset_id: 1, amount: CENTS }),
+				tip.clone()
+			),
+			Error::<Test>::InvalidSignature
+		);
+
+		let signature = sign_tip(&tip);
+		assert_ok!(EvmAccountMapping::validate_unsigned(
+			TransactionSource::External,
+			&crate::Call::meta_call_with_asset_tip {
+				who: who.clone(),
+				call: Box::new(call.clone()),
+				nonce: 0,
+				signature,
+				tip: tip.clone(),
+			}
+		));
+		assert_ok!(EvmAccountMapping::meta_call_with_asset_tip(
+			RuntimeOrigin::none(),
+			who.clone(),
+			Box::new(call),
+			0,
+			signature,
+			tip
+		));
+		System::assert_has_event(
+			Event::AssetTipPaid { who: who.clone(), asset_id: 1, amount: 2 * CENTS }.into(),
+		);
+		System::assert_has_event(
+			frame_system::Event::Remarked {
+				sender: who.clone(),
+				hash: sp_core::H256(sp_io::hashing::blake2_256(b"Hello")),
+			}
+			.into(),
+		);
+		assert_eq!(Balances::free_balance(&TIP_COLLECTOR), 2 * CENTS);
+		assert!(System::events().iter().any(|record| matches!(
+			&record.event,
+			RuntimeEvent::EvmAccountMapping(Event::MetaTransactionFeeSettled {
+				asset_tip: Some(crate::AssetTip { asset_id: 1, amount }),
+				..
+			}) if *amount == 2 * CENTS
+		)));
+	});
+}
+
+#[test]
+fn malleable_signatures_are_rejected() {
+	use frame_support::{pallet_prelude::ValidateUnsigned, unsigned::TransactionSource};
+	use sp_core::U256;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let (account, call, nonce, signature) = signed_remark();
+		let signature = signature.normalize();
+		set_balance(account.clone(), DOLLARS);
+		assert!(crate::is_canonical_signature(&signature));
+
+		// Negate `s` and flip the recovery id, the signature still recovers the same key
+		let curve_order = U256::from_big_endian(
+			&hex::decode("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141")
+				.expect("Valid"),
+		);
+		let mut malleated = signature;
+		(curve_order - U256::from_big_endian(&signature[32..64]))
+			.to_big_endian(&mut malleated[32..64]);
+		malleated[64] = if signature[64] == 27 { 28 } else { 27 };
+		let message_hash = EvmAccountMapping::eip712_message_hash(
+			account.clone(),
+			&codec::Encode::encode(&call),
+			nonce,
+		);
+		assert_eq!(
+			EvmAccountMapping::ecdsa_recover_public_key(&malleated, &message_hash),
+			EvmAccountMapping::ecdsa_recover_public_key(&signature, &message_hash)
+		);
+		assert!(!crate::is_canonical_signature(&malleated));
+
+		let meta_call = crate::Call::meta_call {
+			who: account.clone(),
+			call: Box::new(call.clone()),
+			nonce,
+			signature: malleated.into(),
+			tip: None,
+			version: PayloadVersion::V0,
+		};
+		assert_eq!(
+			EvmAccountMapping::validate_unsigned(TransactionSource::External, &meta_call),
+			Err(crate::InvalidMetaTransaction::MalleableSignature.into())
+		);
+		assert_noop!(
+			EvmAccountMapping::meta_call(
+				RuntimeOrigin::none(),
+				account.clone(),
+				Box::new(call.clone()),
+				nonce,
+				malleated.into(),
+				None,
+				PayloadVersion::V0
+			),
+			Error::<Test>::MalleableSignature
+		);
+
+		// Recovery ids beyond `27` and `28` are rejected as well
+		let mut malleated = signature;
+		malleated[64] += 2;
+		assert!(!crate::is_canonical_signature(&malleated));
+	});
+}
+
+#[test]
+fn ensure_evm_mapped_works() {
+	use frame_support::traits::EnsureOrigin;
+
+	new_test_ext().execute_with(|| {
+		let account = AccountId::from([1u8; 32]);
+		let evm_address = sp_core::H160::repeat_byte(1);
+		let origin: RuntimeOrigin =
+			crate::Origin::<Test>::EvmMapped { evm_address, account_id: account.clone() }.into();
+
+		assert_eq!(
+			crate::EnsureEvmMapped::<AccountId>::try_origin(origin).ok(),
+			Some((evm_address, account.clone()))
+		);
+		assert!(crate::EnsureEvmMapped::<AccountId>::try_origin(RuntimeOrigin::signed(account))
+			.is_err());
+		assert!(crate::EnsureEvmMapped::<AccountId>::try_origin(RuntimeOrigin::root()).is_err());
+	});
+}
+
+#[test]
+fn meta_call_dispatches_as_evm_mapped() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		DispatchAsEvmMapped::set(true);
+
+		let (account, call, nonce, signature) = signed_remark();
+		set_balance(account.clone(), DOLLARS);
+
+		assert_ok!(EvmAccountMapping::meta_call(
+			RuntimeOrigin::none(),
+			account.clone(),
+			Box::new(call),
+			nonce,
+			signature,
+			None,
+			PayloadVersion::V0
+		));
+		// `system.remarkWithEvent` requires a signed origin
+		assert!(System::events().iter().any(|record| matches!(
+			&record.event,
+			RuntimeEvent::EvmAccountMapping(Event::CallDone { who, call_result: Err(error), .. })
+				if who == &account && error.error == sp_runtime::DispatchError::BadOrigin
+		)));
+	});
+}
+
+#[test]
+fn evm_mapped_origin_calls_the_pallet() {
+	use crate::EvmIdentity;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		DispatchAsEvmMapped::set(true);
+
+		let account = mapped_account(1);
+		let relayer = AccountId::from([3u8; 32]);
+		set_balance(account.clone(), DOLLARS);
+		let meta_call = |call: RuntimeCall, nonce| {
+			let message_hash = EvmAccountMapping::eip712_message_hash(
+				account.clone(),
+				&codec::Encode::encode(&call),
+				nonce,
+			);
+			EvmAccountMapping::meta_call(
+				RuntimeOrigin::none(),
+				account.clone(),
+				Box::new(call),
+				nonce,
+				sign(1, &message_hash),
+				None,
+				PayloadVersion::V0,
+			)
+		};
+
+		// The EVM key vetoes a recovery
+		EvmAccountMapping::note_evm_address(&account, sp_core::H160::repeat_byte(1));
+		assert_ok!(EvmAccountMapping::initiate_recovery(
+			RuntimeOrigin::root(),
+			account.clone(),
+			relayer.clone()
+		));
+		assert_ok!(meta_call(RuntimeCall::EvmAccountMapping(crate::Call::veto_recovery {}), 0));
+		System::assert_has_event(Event::RecoveryVetoed { who: account.clone() }.into());
+
+		// And cancels a rotation of its controller
+		let new_controller = sp_core::H160::repeat_byte(7);
+		let rotation_hash =
+			EvmAccountMapping::rotate_controller_message_hash(&account, &new_controller, 1);
+		assert_ok!(EvmAccountMapping::request_controller_rotation(
+			RuntimeOrigin::signed(relayer),
+			account.clone(),
+			EvmIdentity::Address(new_controller),
+			1,
+			sign(1, &rotation_hash),
+		));
+		assert_ok!(meta_call(
+			RuntimeCall::EvmAccountMapping(crate::Call::cancel_controller_rotation {}),
+			2
+		));
+		System::assert_has_event(Event::ControllerRotationCancelled { who: account }.into());
+	});
+}
+
+#[test]
+fn meta_call_with_proxy_type_restricts_the_call() {
+	use frame_support::{pallet_prelude::ValidateUnsigned, unsigned::TransactionSource};
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let who = mapped_account(1);
+		let dest = AccountId::from([2u8; 32]);
+		set_balance(who.clone(), DOLLARS);
+
+		let transfer = RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive {
+			dest: dest.clone(),
+			value: 10 * CENTS,
+		});
+		let sign_call = |call: &RuntimeCall, nonce: u64, proxy_type: &ProxyType| {
+			let message_hash = EvmAccountMapping::proxy_type_message_hash(
+				&who,
+				&codec::Encode::encode(call),
+				nonce,
+				None,
+				proxy_type,
+			);
+			sign(1, &message_hash)
+		};
+
+		// `NonTransfer` forbids the transfer
+		let signature = sign_call(&transfer, 0, &ProxyType::NonTransfer);
+		assert_eq!(
+			EvmAccountMapping::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::meta_call_with_proxy_type {
+					who: who.clone(),
+					call: Box::new(transfer.clone()),
+					nonce: 0,
+					signature,
+					tip: None,
+					proxy_type: ProxyType::NonTransfer,
+				}
+			),
+			Err(crate::InvalidMetaTransaction::CallFiltered.into())
+		);
+		assert_noop!(
+			EvmAccountMapping::meta_call_with_proxy_type(
+				RuntimeOrigin::none(),
+				who.clone(),
+				Box::new(transfer.clone()),
+				0,
+				signature,
+				None,
+				ProxyType::NonTransfer
+			),
+			Error::<Test>::CallFiltered
+		);
+
+		// The proxy type is signed
+		assert_noop!(
+			EvmAccountMapping::meta_call_with_proxy_type(
+				RuntimeOrigin::none(),
+				who.clone(),
+				Box::new(transfer.clone()),
+				0,
+				sign_call(&transfer, 0, &ProxyType::NonTransfer),
+				None,
+				ProxyType::Any
+			),
+			Error::<Test>::InvalidSignature
+		);
+
+		let signature = sign_call(&transfer, 0, &ProxyType::Any);
+		assert_ok!(EvmAccountMapping::validate_unsigned(
+			TransactionSource::External,
+			&crate::Call::meta_call_with_proxy_type {
+				who: who.clone(),
+				call: Box::new(transfer.clone()),
+				nonce: 0,
+				signature,
+				tip: None,
+				proxy_type: ProxyType::Any,
+			}
+		));
+		assert_ok!(EvmAccountMapping::meta_call_with_proxy_type(
+			RuntimeOrigin::none(),
+			who.clone(),
+			Box::new(transfer),
+			0,
+			signature,
+			None,
+			ProxyType::Any
+		));
+		assert_eq!(Balances::free_balance(&dest), 10 * CENTS);
+
+		// Other calls pass `NonTransfer`
+		let remark = RuntimeCall::System(frame_system::Call::remark_with_event {
+			remark: b"Hello".to_vec(),
+		});
+		assert_ok!(EvmAccountMapping::meta_call_with_proxy_type(
+			RuntimeOrigin::none(),
+			who.clone(),
+			Box::new(remark.clone()),
+			1,
+			sign_call(&remark, 1, &ProxyType::NonTransfer),
+			None,
+			ProxyType::NonTransfer
+		));
+		System::assert_has_event(
+			frame_system::Event::Remarked {
+				sender: who.clone(),
+				hash: sp_core::H256(sp_io::hashing::blake2_256(b"Hello")),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn test_utils_sign_meta_calls() {
+	use crate::test_utils;
+	use frame_support::traits::Get;
+	use sp_core::Pair;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let keypair = sp_core::ecdsa::Pair::from_seed(&[1u8; 32]);
+		let who = test_utils::mapped_account::<crate::SubstrateAddressConverter>(&keypair)
+			.expect("Convertable");
+		assert_eq!(who, mapped_account(1));
+		set_balance(who.clone(), DOLLARS);
+
+		let call = RuntimeCall::System(frame_system::Call::remark_with_event {
+			remark: b"Hello".to_vec(),
+		});
+		let call_data = codec::Encode::encode(&call);
+		let ss58_who =
+			test_utils::ss58_address(&who, <Test as frame_system::Config>::SS58Prefix::get());
+		let domain = EvmAccountMapping::eip712_domain();
+		assert_eq!(
+			test_utils::meta_call_message_hash(
+				PayloadVersion::V1,
+				&ss58_who,
+				&call_data,
+				0,
+				10,
+				&domain
+			),
+			EvmAccountMapping::meta_call_message_hash(
+				PayloadVersion::V1,
+				&who,
+				&call_data,
+				0,
+				Some(10)
+			)
+		);
+
+		let signature = test_utils::sign_remote_call(&keypair, &ss58_who, &call_data, 0, &domain);
+		assert_ok!(EvmAccountMapping::meta_call(
+			RuntimeOrigin::none(),
+			who.clone(),
+			Box::new(call),
+			0,
+			signature.into(),
+			None,
+			PayloadVersion::V0
+		));
+		assert_eq!(crate::AccountNonce::<Test>::get(&who), 1);
+	});
+}
+
+#[test]
+fn test_vectors_match_the_pallet() {
+	use crate::test_vectors;
+
+	new_test_ext().execute_with(|| {
+		let domain_separator = EvmAccountMapping::eip712_domain().separator();
+		let vectors = test_vectors::meta_call_vectors();
+		assert_eq!(vectors.len(), 8);
+
+		for vector in vectors.iter().filter(|v| v.domain.separator() == domain_separator) {
+			let who = AccountId::from_ss58check(&vector.who).expect("Valid address");
+			let tip = match vector.version {
+				PayloadVersion::V0 => None,
+				PayloadVersion::V1 => Some(vector.tip),
+			};
+			assert_eq!(
+				EvmAccountMapping::meta_call_message_hash(
+					vector.version,
+					&who,
+					&vector.call_data,
+					vector.nonce,
+					tip
+				),
+				vector.message_hash
+			);
+			let public_key =
+				sp_io::crypto::secp256k1_ecdsa_recover(&vector.signature, &vector.message_hash)
+					.expect("Valid signature");
+			assert_eq!(crate::evm_address_of(&public_key), vector.signer);
+		}
+
+		let json = test_vectors::to_json(&vectors);
+		assert_eq!(json, test_vectors::to_json(&test_vectors::meta_call_vectors()));
+		assert!(json.starts_with(
+			r#"[{"domain":{"chainId":"0","name":"Substrate","salt":null,"verifyingContract":"0x0000000000000000000000000000000000000000","version":"1"},"expected":{"#
+		));
+		assert!(!json.contains(char::is_whitespace));
+	});
+}
+
+#[test]
+fn meta_call_as_sub_account_works() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let who = mapped_account(1);
+		set_balance(who.clone(), DOLLARS);
+
+		let call = RuntimeCall::System(frame_system::Call::remark_with_event {
+			remark: b"Hello".to_vec(),
+		});
+		let sign_call = |nonce: u64, index: u16| {
+			let message_hash = EvmAccountMapping::sub_account_message_hash(
+				&who,
+				&codec::Encode::encode(&call),
+				nonce,
+				None,
+				index,
+			);
+			sign(1, &message_hash)
+		};
+
+		// The index is signed
+		assert_noop!(
+			EvmAccountMapping::meta_call_as_sub_account(
+				RuntimeOrigin::none(),
+				who.clone(),
+				Box::new(call.clone()),
+				0,
+				sign_call(0, 3),
+				None,
+				4
+			),
+			Error::<Test>::InvalidSignature
+		);
+
+		assert_ok!(EvmAccountMapping::meta_call_as_sub_account(
+			RuntimeOrigin::none(),
+			who.clone(),
+			Box::new(call.clone()),
+			0,
+			sign_call(0, 3),
+			None,
+			3
+		));
+		let evm_address =
+			EvmAccountMapping::evm_address_of_account(&who).expect("Recorded by the meta-call");
+		let sub_account = EvmAccountMapping::sub_account_of(&evm_address, 3);
+		assert_ne!(sub_account, who);
+		assert_ne!(sub_account, EvmAccountMapping::sub_account_of(&evm_address, 4));
+		System::assert_has_event(
+			frame_system::Event::Remarked {
+				sender: sub_account,
+				hash: sp_core::H256(sp_io::hashing::blake2_256(b"Hello")),
+			}
+			.into(),
+		);
+		// The fees are paid by the mapped account
+		assert_eq!(crate::AccountNonce::<Test>::get(&who), 1);
+		assert!(Balances::free_balance(&who) < DOLLARS);
+	});
+}
+
+#[test]
+fn meta_call_with_relayer_works() {
+	use frame_support::{pallet_prelude::ValidateUnsigned, unsigned::TransactionSource};
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let who = mapped_account(1);
+		let relayer = AccountId::from([7u8; 32]);
+		set_balance(who.clone(), DOLLARS);
+
+		let call = RuntimeCall::System(frame_system::Call::remark_with_event {
+			remark: b"Hello".to_vec(),
+		});
+		let message_hash = EvmAccountMapping::relayer_message_hash(
+			&who,
+			&codec::Encode::encode(&call),
+			0,
+			None,
+			&relayer,
+		);
+		let meta_call = crate::Call::meta_call_with_relayer {
+			who: who.clone(),
+			call: Box::new(call.clone()),
+			nonce: 0,
+			signature: sign(1, &message_hash),
+			tip: None,
+			relayer: relayer.clone(),
+		};
+
+		// The meta-transaction is kept in the pool of the node it's submitted to
+		let valid = EvmAccountMapping::validate_unsigned(TransactionSource::External, &meta_call)
+			.expect("Valid");
+		assert!(!valid.propagate);
+
+		// Only the designated relayer includes the meta-transaction
+		BlockAuthor::set(Some(AccountId::from([8u8; 32])));
+		assert_eq!(
+			EvmAccountMapping::pre_dispatch(&meta_call),
+			Err(crate::InvalidMetaTransaction::NotDesignatedRelayer.into())
+		);
+		assert_noop!(
+			EvmAccountMapping::meta_call_with_relayer(
+				RuntimeOrigin::none(),
+				who.clone(),
+				Box::new(call.clone()),
+				0,
+				sign(1, &message_hash),
+				None,
+				relayer.clone()
+			),
+			Error::<Test>::NotDesignatedRelayer
+		);
+
+		BlockAuthor::set(Some(relayer.clone()));
+		assert_ok!(EvmAccountMapping::pre_dispatch(&meta_call));
+		assert_ok!(EvmAccountMapping::meta_call_with_relayer(
+			RuntimeOrigin::none(),
+			who.clone(),
+			Box::new(call),
+			0,
+			sign(1, &message_hash),
+			None,
+			relayer
+		));
+		assert_eq!(crate::AccountNonce::<Test>::get(&who), 1);
+	});
+}
+
+#[test]
+fn meta_call_with_mortality_works() {
+	use frame_support::{pallet_prelude::ValidateUnsigned, unsigned::TransactionSource};
+	use sp_runtime::traits::Dispatchable;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(5);
+
+		let who = mapped_account(1);
+		set_balance(who.clone(), DOLLARS);
+
+		let call = RuntimeCall::System(frame_system::Call::remark_with_event {
+			remark: b"Hello".to_vec(),
+		});
+		let meta_call = |nonce: u64, valid_from: u64, valid_until: u64| {
+			let message_hash = EvmAccountMapping::mortality_message_hash(
+				&who,
+				&codec::Encode::encode(&call),
+				nonce,
+				None,
+				valid_from,
+				valid_until,
+			);
+			crate::Call::<Test>::meta_call_with_mortality {
+				who: who.clone(),
+				call: Box::new(call.clone()),
+				nonce,
+				signature: sign(1, &message_hash),
+				tip: None,
+				valid_from,
+				valid_until,
+			}
+		};
+
+		// Not valid yet
+		assert_eq!(
+			EvmAccountMapping::validate_unsigned(TransactionSource::External, &meta_call(0, 7, 8)),
+			Err(sp_runtime::transaction_validity::InvalidTransaction::Future.into())
+		);
+		assert_noop!(
+			RuntimeCall::from(meta_call(0, 7, 8)).dispatch(RuntimeOrigin::none()),
+			Error::<Test>::OutsideValidityWindow
+		);
+
+		run_to_block(7);
+		let valid =
+			EvmAccountMapping::validate_unsigned(TransactionSource::External, &meta_call(0, 7, 8))
+				.expect("Valid");
+		// The meta-transaction leaves the pool after its validity window
+		assert_eq!(valid.longevity, 2);
+		assert_ok!(RuntimeCall::from(meta_call(0, 7, 8)).dispatch(RuntimeOrigin::none()));
+		assert_eq!(crate::AccountNonce::<Test>::get(&who), 1);
+
+		// Expired
+		assert_eq!(
+			EvmAccountMapping::validate_unsigned(TransactionSource::External, &meta_call(1, 2, 4)),
+			Err(crate::InvalidMetaTransaction::Expired.into())
+		);
+		assert_noop!(
+			RuntimeCall::from(meta_call(1, 2, 4)).dispatch(RuntimeOrigin::none()),
+			Error::<Test>::OutsideValidityWindow
+		);
+	});
+}
+
+#[test]
+fn meta_batch_modes_apply() {
+	use crate::BatchMode;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let who = mapped_account(1);
+		set_balance(who.clone(), DOLLARS);
+
+		let remark = |remark: &[u8]| {
+			RuntimeCall::System(frame_system::Call::remark_with_event { remark: remark.to_vec() })
+		};
+		let remarked = |remark: &[u8]| -> RuntimeEvent {
+			frame_system::Event::Remarked {
+				sender: who.clone(),
+				hash: sp_core::H256(sp_io::hashing::blake2_256(remark)),
+			}
+			.into()
+		};
+		// The second call fails with `BadOrigin`
+		let calls = vec![
+			remark(b"first"),
+			RuntimeCall::System(frame_system::Call::set_heap_pages { pages: 1 }),
+			remark(b"last"),
+		];
+		let meta_batch = |nonce: u64, mode: BatchMode| {
+			let message_hash =
+				EvmAccountMapping::batch_message_hash(&who, &calls, nonce, None, mode);
+			EvmAccountMapping::meta_batch(
+				RuntimeOrigin::none(),
+				who.clone(),
+				calls.clone(),
+				nonce,
+				sign(1, &message_hash),
+				None,
+				mode,
+			)
+		};
+
+		// The mode is signed
+		let message_hash =
+			EvmAccountMapping::batch_message_hash(&who, &calls, 0, None, BatchMode::StopOnError);
+		assert_noop!(
+			EvmAccountMapping::meta_batch(
+				RuntimeOrigin::none(),
+				who.clone(),
+				calls.clone(),
+				0,
+				sign(1, &message_hash),
+				None,
+				BatchMode::ContinueOnError
+			),
+			Error::<Test>::InvalidSignature
+		);
+
+		assert_ok!(meta_batch(0, BatchMode::StopOnError));
+		System::assert_has_event(remarked(b"first"));
+		System::assert_has_event(
+			Event::BatchInterrupted {
+				who: who.clone(),
+				index: 1,
+				error: sp_runtime::DispatchError::BadOrigin,
+			}
+			.into(),
+		);
+		assert!(!System::events().iter().any(|record| record.event == remarked(b"last")));
+
+		System::reset_events();
+		assert_ok!(meta_batch(1, BatchMode::AllOrNothing));
+		assert!(!System::events().iter().any(|record| record.event == remarked(b"first")));
+		assert!(!crate::Receipts::<Test>::get(&who, 1).expect("Stored").success);
+
+		System::reset_events();
+		assert_ok!(meta_batch(2, BatchMode::ContinueOnError));
+		System::assert_has_event(remarked(b"first"));
+		System::assert_has_event(
+			Event::BatchCallFailed {
+				who: who.clone(),
+				index: 1,
+				error: sp_runtime::DispatchError::BadOrigin,
+			}
+			.into(),
+		);
+		System::assert_has_event(remarked(b"last"));
+		assert_eq!(crate::AccountNonce::<Test>::get(&who), 3);
+	});
+}
+
+#[test]
+fn meta_call_with_weight_limit_works() {
+	use frame_support::{
+		dispatch::GetDispatchInfo, pallet_prelude::ValidateUnsigned, unsigned::TransactionSource,
+		weights::Weight,
+	};
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let who = mapped_account(1);
+		set_balance(who.clone(), DOLLARS);
+
+		let call = RuntimeCall::System(frame_system::Call::remark_with_event {
+			remark: b"Hello".to_vec(),
+		});
+		let weight = call.get_dispatch_info().weight;
+		let sign_call = |max_weight: Weight| {
+			let message_hash = EvmAccountMapping::weight_limit_message_hash(
+				&who,
+				&codec::Encode::encode(&call),
+				0,
+				None,
+				max_weight,
+			);
+			sign(1, &message_hash)
+		};
+		let meta_call = |max_weight: Weight| crate::Call::<Test>::meta_call_with_weight_limit {
+			who: who.clone(),
+			call: Box::new(call.clone()),
+			nonce: 0,
+			signature: sign_call(max_weight),
+			tip: None,
+			max_weight,
+		};
+
+		// The call is heavier than the signer allows, nothing is charged
+		let max_weight = weight.saturating_sub(Weight::from_parts(1, 0));
+		assert_eq!(
+			EvmAccountMapping::validate_unsigned(
+				TransactionSource::External,
+				&meta_call(max_weight)
+			),
+			Err(crate::InvalidMetaTransaction::WeightLimitExceeded.into())
+		);
+		assert_noop!(
+			EvmAccountMapping::meta_call_with_weight_limit(
+				RuntimeOrigin::none(),
+				who.clone(),
+				Box::new(call.clone()),
+				0,
+				sign_call(max_weight),
+				None,
+				max_weight
+			),
+			Error::<Test>::WeightLimitExceeded
+		);
+		assert_eq!(Balances::free_balance(&who), DOLLARS);
+
+		assert_ok!(EvmAccountMapping::validate_unsigned(
+			TransactionSource::External,
+			&meta_call(weight)
+		));
+		assert_ok!(EvmAccountMapping::meta_call_with_weight_limit(
+			RuntimeOrigin::none(),
+			who.clone(),
+			Box::new(call.clone()),
+			0,
+			sign_call(weight),
+			None,
+			weight
+		));
+		assert_eq!(crate::AccountNonce::<Test>::get(&who), 1);
+	});
+}
+
+#[test]
+fn deposits_are_held_and_released() {
+	use frame_support::{
+		traits::{fungible::InspectHold, Hooks},
+		weights::Weight,
+	};
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		LinkDeposit::set(DOLLARS);
+		GrantDeposit::set(2 * DOLLARS);
+		let link_reason = RuntimeHoldReason::EvmAccountMapping(crate::HoldReason::LinkDeposit);
+		let grant_reason = RuntimeHoldReason::EvmAccountMapping(crate::HoldReason::GrantDeposit);
+
+		let who = mapped_account(1);
+		let account = AccountId::from([2u8; 32]);
+
+		// Not enough to hold the deposit
+		let nonce = 0u64;
+		let message_hash = EvmAccountMapping::link_accounts_message_hash(&who, &account, nonce);
+		assert!(EvmAccountMapping::link_accounts(
+			RuntimeOrigin::signed(account.clone()),
+			who.clone(),
+			nonce,
+			sign(1, &message_hash)
+		)
+		.is_err());
+
+		set_balance(account.clone(), 10 * DOLLARS);
+		assert_ok!(EvmAccountMapping::link_accounts(
+			RuntimeOrigin::signed(account.clone()),
+			who.clone(),
+			nonce,
+			sign(1, &message_hash)
+		));
+		assert_eq!(Balances::balance_on_hold(&link_reason, &account), DOLLARS);
+
+		assert_ok!(EvmAccountMapping::unlink_accounts(RuntimeOrigin::signed(account.clone())));
+		assert_eq!(Balances::balance_on_hold(&link_reason, &account), 0);
+
+		// Replacing a grant holds nothing more
+		let grantee = AccountId::from([3u8; 32]);
+		let kind = crate::GrantKind::Delegation;
+		for expires_at in [5, 6] {
+			assert_ok!(EvmAccountMapping::grant(
+				RuntimeOrigin::signed(account.clone()),
+				grantee.clone(),
+				kind,
+				expires_at
+			));
+		}
+		assert_eq!(Balances::balance_on_hold(&grant_reason, &account), 2 * DOLLARS);
+
+		assert_ok!(EvmAccountMapping::revoke(
+			RuntimeOrigin::signed(account.clone()),
+			grantee.clone(),
+			kind
+		));
+		assert_eq!(Balances::balance_on_hold(&grant_reason, &account), 0);
+
+		// An expired grant is released by the sweep
+		assert_ok!(EvmAccountMapping::grant(
+			RuntimeOrigin::signed(account.clone()),
+			grantee.clone(),
+			kind,
+			2
+		));
+		assert_eq!(Balances::balance_on_hold(&grant_reason, &account), 2 * DOLLARS);
+		run_to_block(2);
+		EvmAccountMapping::on_idle(2, Weight::MAX);
+		assert!(!crate::Grants::<Test>::contains_key((&account, &grantee, kind)));
+		assert_eq!(Balances::balance_on_hold(&grant_reason, &account), 0);
+	});
+}
+
+#[test]
+fn call_filter_overrides_apply() {
+	use crate::{CallFilterRule, CallSelector};
+	use frame_support::{pallet_prelude::ValidateUnsigned, unsigned::TransactionSource};
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let dest = AccountId::from([2u8; 32]);
+		let transfer_all = RuntimeCall::Balances(pallet_balances::Call::transfer_all {
+			dest: dest.clone(),
+			keep_alive: true,
+		});
+		let transfer = RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive {
+			dest,
+			value: CENTS,
+		});
+		let encoded = codec::Encode::encode(&transfer_all);
+		let (balances, transfer_all_index) = (encoded[0], encoded[1]);
+		let set_rule = |selector, rule| {
+			EvmAccountMapping::set_call_filter_rule(RuntimeOrigin::root(), selector, rule)
+		};
+
+		assert_noop!(
+			EvmAccountMapping::set_call_filter_rule(
+				RuntimeOrigin::signed(AccountId::from([1u8; 32])),
+				CallSelector::Pallet(balances),
+				Some(CallFilterRule::Deny)
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+
+		// Allowing a call loosens `CallFilter`
+		assert!(!EvmAccountMapping::call_allowed(&transfer_all));
+		let selector = CallSelector::Call(balances, transfer_all_index);
+		assert_ok!(set_rule(selector, Some(CallFilterRule::Allow)));
+		System::assert_last_event(RuntimeEvent::EvmAccountMapping(Event::CallFilterUpdated {
+			selector,
+			rule: Some(CallFilterRule::Allow),
+		}));
+		assert!(EvmAccountMapping::call_allowed(&transfer_all));
+
+		// The rule of a call takes precedence over the rule of its pallet
+		assert_ok!(set_rule(CallSelector::Pallet(balances), Some(CallFilterRule::Deny)));
+		assert!(EvmAccountMapping::call_allowed(&transfer_all));
+		assert!(!EvmAccountMapping::call_allowed(&transfer));
+
+		assert_ok!(set_rule(selector, None));
+		assert!(!EvmAccountMapping::call_allowed(&transfer_all));
+
+		// Without rules `CallFilter` applies again
+		assert_ok!(set_rule(CallSelector::Pallet(balances), None));
+		assert!(EvmAccountMapping::call_allowed(&transfer));
+		assert_eq!(crate::CallFilterOverrides::<Test>::iter().count(), 0);
+
+		// Denied calls are rejected by the transaction pool
+		let (account, call, nonce, signature) = signed_remark();
+		assert_ok!(set_rule(CallSelector::Pallet(0), Some(CallFilterRule::Deny)));
+		assert_eq!(
+			EvmAccountMapping::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::meta_call {
+					who: account,
+					call: Box::new(call),
+					nonce,
+					signature,
+					tip: None,
+					version: PayloadVersion::V0,
+				},
+			),
+			Err(crate::InvalidMetaTransaction::CallFiltered.into())
+		);
+	});
+}
+
+#[test]
+fn future_nonces_wait_in_the_pool() {
+	use frame_support::{pallet_prelude::ValidateUnsigned, unsigned::TransactionSource};
+	use sp_runtime::{traits::Dispatchable, transaction_validity::InvalidTransaction};
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let account = mapped_account(1);
+		set_balance(account.clone(), DOLLARS);
+
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		let meta_call = |nonce| {
+			let message_hash = EvmAccountMapping::eip712_message_hash(
+				account.clone(),
+				&codec::Encode::encode(&call),
+				nonce,
+			);
+			crate::Call::meta_call {
+				who: account.clone(),
+				call: Box::new(call.clone()),
+				nonce,
+				signature: sign(1, &message_hash),
+				tip: None,
+				version: PayloadVersion::V0,
+			}
+		};
+		let validate = |nonce| {
+			EvmAccountMapping::validate_unsigned(TransactionSource::External, &meta_call(nonce))
+		};
+		let tag = |nonce: u64| {
+			codec::Encode::encode(&("EVMAccountMapping", codec::Encode::encode(&(&account, nonce))))
+		};
+
+		// A future nonce within the gap requires the previous one
+		let valid = validate(2).expect("Valid");
+		assert_eq!(valid.requires, vec![tag(1)]);
+		assert_eq!(valid.provides, vec![tag(2)]);
+		assert_eq!(crate::AccountNonce::<Test>::get(&account), 0);
+
+		// Beyond the gap
+		assert_eq!(validate(3), Err(InvalidTransaction::Future.into()));
+
+		// Only the next nonce may be included
+		assert_eq!(
+			EvmAccountMapping::pre_dispatch(&meta_call(2)),
+			Err(InvalidTransaction::Future.into())
+		);
+		for nonce in 0..2 {
+			let valid = validate(nonce).expect("Valid");
+			assert_eq!(valid.requires, if nonce == 0 { vec![] } else { vec![tag(nonce - 1)] });
+			assert_ok!(EvmAccountMapping::pre_dispatch(&meta_call(nonce)));
+			assert_ok!(RuntimeCall::from(meta_call(nonce)).dispatch(RuntimeOrigin::none()));
+		}
+
+		let valid = validate(2).expect("Valid");
+		assert!(valid.requires.is_empty());
+		assert_ok!(EvmAccountMapping::pre_dispatch(&meta_call(2)));
+	});
+}
+
+#[test]
+fn compact_signatures_are_accepted() {
+	use crate::Secp256K1Signature;
+	use frame_support::{pallet_prelude::ValidateUnsigned, unsigned::TransactionSource};
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let account = mapped_account(1);
+		set_balance(account.clone(), DOLLARS);
+
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		let message_hash = EvmAccountMapping::eip712_message_hash(
+			account.clone(),
+			&codec::Encode::encode(&call),
+			0,
+		);
+		let standard = sign(1, &message_hash).normalize();
+
+		// The recovery id is folded into the highest bit of `s`
+		let mut compact = [0u8; 64];
+		compact.copy_from_slice(&standard[..64]);
+		compact[32] |= (standard[64] % 27) << 7;
+		let signature = Secp256K1Signature::try_from(&compact[..]).expect("64 bytes");
+		assert_eq!(signature, Secp256K1Signature::Compact(compact));
+		assert_eq!(signature.normalize()[..64], standard[..64]);
+		assert_eq!(signature.normalize()[64], standard[64] % 27);
+		assert_eq!(
+			Secp256K1Signature::try_from(&standard[..]),
+			Ok(Secp256K1Signature::Standard(standard))
+		);
+		assert!(Secp256K1Signature::try_from(&standard[..63]).is_err());
+
+		let meta_call = crate::Call::meta_call {
+			who: account.clone(),
+			call: Box::new(call.clone()),
+			nonce: 0,
+			signature: signature.into(),
+			tip: None,
+			version: PayloadVersion::V0,
+		};
+		assert_ok!(EvmAccountMapping::validate_unsigned(TransactionSource::External, &meta_call));
+		assert_ok!(EvmAccountMapping::meta_call(
+			RuntimeOrigin::none(),
+			account.clone(),
+			Box::new(call),
+			0,
+			signature.into(),
+			None,
+			PayloadVersion::V0
+		));
+		assert_eq!(crate::AccountNonce::<Test>::get(&account), 1);
+	});
+}
+
+#[test]
+fn batch_payload_is_a_call_hashes_array() {
+	use crate::{
+		eip712::typed_data::{encode_type, hash_struct, Member, Types, Value},
+		BatchMode,
+	};
+	use sp_io::hashing::keccak_256;
+
+	new_test_ext().execute_with(|| {
+		let who = mapped_account(1);
+		let calls = vec![
+			RuntimeCall::System(frame_system::Call::remark { remark: b"first".to_vec() }),
+			RuntimeCall::System(frame_system::Call::remark { remark: b"last".to_vec() }),
+		];
+
+		let types = Types::from([(
+			"SubstrateCalls",
+			vec![
+				Member::new("who", "string"),
+				Member::new("callHashes", "bytes32[]"),
+				Member::new("nonce", "uint64"),
+				Member::new("tip", "uint128"),
+				Member::new("mode", "uint8"),
+			],
+		)]);
+		assert_eq!(encode_type("SubstrateCalls", &types), Ok(crate::SUBSTRATE_CALLS_TYPE.to_vec()));
+		let call_hashes = calls
+			.iter()
+			.map(|call| Value::FixedBytes(keccak_256(&codec::Encode::encode(call)).to_vec()))
+			.collect();
+		let struct_hash = hash_struct(
+			"SubstrateCalls",
+			&[
+				Value::String(who.to_ss58check().into_bytes()),
+				Value::Array(call_hashes),
+				Value::Uint(3u64.into()),
+				Value::Uint(CENTS.into()),
+				Value::Uint((BatchMode::AllOrNothing as u8).into()),
+			],
+			&types,
+		)
+		.expect("Valid typed data");
+
+		assert_eq!(
+			EvmAccountMapping::batch_message_hash(
+				&who,
+				&calls,
+				3,
+				Some(CENTS),
+				BatchMode::AllOrNothing
+			),
+			EvmAccountMapping::eip712_typed_data_hash(&struct_hash)
+		);
+	});
+}
+
+#[test]
+fn fund_mapped_account_works() {
+	use crate::EvmIdentity;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let from = AccountId::from([2u8; 32]);
+		set_balance(from.clone(), 10 * DOLLARS);
+
+		let message_hash = [0u8; 32];
+		let public_key = EvmAccountMapping::ecdsa_recover_public_key(
+			&sign(1, &message_hash).normalize(),
+			&message_hash,
+		)
+		.expect("Recoverable");
+		let evm_address = crate::evm_address_of(&public_key);
+		let account = mapped_account(1);
+
+		// The account of the address isn't known yet
+		assert_noop!(
+			EvmAccountMapping::fund_mapped_account(
+				RuntimeOrigin::signed(from.clone()),
+				EvmIdentity::Address(evm_address),
+				DOLLARS
+			),
+			Error::<Test>::UnknownEvmAddress
+		);
+
+		assert_ok!(EvmAccountMapping::fund_mapped_account(
+			RuntimeOrigin::signed(from.clone()),
+			EvmIdentity::PublicKey(public_key),
+			DOLLARS
+		));
+		System::assert_last_event(
+			Event::MappedAccountFunded {
+				from: from.clone(),
+				evm_address,
+				account: account.clone(),
+				amount: DOLLARS,
+			}
+			.into(),
+		);
+		assert_eq!(Balances::free_balance(&account), DOLLARS);
+
+		// The mapping is recorded
+		assert_eq!(
+			EvmAccountMapping::mapped_account_of(&EvmIdentity::Address(evm_address)),
+			Some((evm_address, account.clone()))
+		);
+		assert_ok!(EvmAccountMapping::fund_mapped_account(
+			RuntimeOrigin::signed(from),
+			EvmIdentity::Address(evm_address),
+			DOLLARS
+		));
+		assert_eq!(Balances::free_balance(&account), 2 * DOLLARS);
+	});
+}
+
+#[test]
+fn evm_address_mapping_resolves_recorded_accounts() {
+	use sp_runtime::traits::Convert;
+	type Mapping = crate::EvmAddressMapping<Test, Fallback>;
+	struct Fallback;
+	impl Convert<crate::EvmAddress, AccountId> for Fallback {
+		fn convert(evm_address: crate::EvmAddress) -> AccountId {
+			crate::EvmTransparentConverter::try_convert_evm_address(&evm_address)
+				.expect("Always converts")
+		}
+	}
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let (account, call, nonce, signature) = signed_remark();
+		let message_hash = EvmAccountMapping::eip712_message_hash(
+			account.clone(),
+			&codec::Encode::encode(&call),
+			nonce,
+		);
+		let public_key =
+			EvmAccountMapping::ecdsa_recover_public_key(&signature.normalize(), &message_hash)
+				.expect("Recoverable");
+		let evm_address = crate::evm_address_of(&public_key);
+		set_balance(account.clone(), DOLLARS);
+
+		// An unknown address resolves with the fallback
+		assert_eq!(Mapping::convert(evm_address), Fallback::convert(evm_address));
+
+		assert_ok!(EvmAccountMapping::meta_call(
+			RuntimeOrigin::none(),
+			account.clone(),
+			Box::new(call),
+			nonce,
+			signature,
+			None,
+			PayloadVersion::V0
+		));
+		assert_eq!(Mapping::convert(evm_address), account);
+	});
+}
+
+#[cfg(feature = "tx-extension")]
+#[test]
+fn check_meta_transaction_extension_works() {
+	use crate::CheckMetaTransaction;
+	use frame_support::dispatch::GetDispatchInfo;
+	use sp_runtime::{
+		traits::{Dispatchable, TransactionExtension, TxBaseImplication},
+		transaction_validity::TransactionSource,
+	};
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let (account, call, nonce, signature) = signed_remark();
+		set_balance(account.clone(), DOLLARS);
+		let meta_call = RuntimeCall::from(crate::Call::meta_call {
+			who: account.clone(),
+			call: Box::new(call),
+			nonce,
+			signature,
+			tip: None,
+			version: PayloadVersion::V0,
+		});
+		let info = meta_call.get_dispatch_info();
+		let len = codec::Encode::encoded_size(&meta_call);
+		let extension = CheckMetaTransaction::<Test>::new();
+		let validate = |origin: RuntimeOrigin, source| {
+			extension.validate(origin, &meta_call, &info, len, (), &TxBaseImplication(()), source)
+		};
+
+		// The transaction pool doesn't write the storage
+		let (valid, signer, _) =
+			validate(RuntimeOrigin::none(), TransactionSource::External).expect("Valid");
+		assert!(valid.priority > 0);
+		assert!(signer.is_some());
+		assert!(!crate::PreDispatchedSigner::<Test>::exists());
+
+		// Other origins are left to the other extensions
+		let (_, signer, _) = validate(RuntimeOrigin::signed(account), TransactionSource::External)
+			.expect("Passes through");
+		assert_eq!(signer, None);
+
+		// In a block the signer is recorded for the dispatch
+		let (_, signer, origin) =
+			validate(RuntimeOrigin::none(), TransactionSource::InBlock).expect("Valid");
+		assert_ok!(extension.clone().prepare(signer, &origin, &meta_call, &info, len));
+		assert!(crate::PreDispatchedSigner::<Test>::exists());
+
+		let result = meta_call.clone().dispatch(origin.clone()).map(|_| ()).map_err(|e| e.error);
+		assert_ok!(result);
+		assert_ok!(CheckMetaTransaction::<Test>::post_dispatch_details(
+			true,
+			&info,
+			&Default::default(),
+			len,
+			&result,
+		));
+		assert_eq!(EvmAccountMapping::rejection_stats(), Default::default());
+
+		// The dispatch errors are counted after the dispatch, whose storage is reverted
+		let result = meta_call.clone().dispatch(origin).map(|_| ()).map_err(|e| e.error);
+		assert_eq!(result, Err(Error::<Test>::NonceError.into()));
+		assert_ok!(CheckMetaTransaction::<Test>::post_dispatch_details(
+			true,
+			&info,
+			&Default::default(),
+			len,
+			&result,
+		));
+		assert_eq!(EvmAccountMapping::rejection_stats().stale_nonce, 1);
+	});
+}
+
+#[test]
+fn meta_transactions_per_block_are_capped() {
+	use frame_support::pallet_prelude::ValidateUnsigned;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		MaxMetaTxPerBlock::set(1);
+
+		let (account, call, nonce, signature) = signed_remark();
+		set_balance(account.clone(), DOLLARS);
+		let meta_call = crate::Call::meta_call {
+			who: account,
+			call: Box::new(call),
+			nonce,
+			signature,
+			tip: None,
+			version: PayloadVersion::V0,
+		};
+
+		assert_ok!(EvmAccountMapping::pre_dispatch(&meta_call));
+		assert_eq!(crate::MetaTxCount::<Test>::get(), 1);
+		assert_eq!(
+			EvmAccountMapping::pre_dispatch(&meta_call),
+			Err(sp_runtime::transaction_validity::InvalidTransaction::ExhaustsResources.into())
+		);
+
+		// The count is reset in the next block
+		run_to_block(2);
+		assert_eq!(crate::MetaTxCount::<Test>::get(), 0);
+		assert_ok!(EvmAccountMapping::pre_dispatch(&meta_call));
+	});
+}
+
+#[test]
+fn tips_are_paid_to_the_block_author() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let (account, call, nonce, signature) = signed_remark();
+		let author = mapped_account(2);
+		set_balance(account.clone(), DOLLARS);
+		set_balance(author.clone(), DOLLARS);
+		TipToBlockAuthor::set(true);
+		BlockAuthor::set(Some(author.clone()));
+
+		assert_ok!(EvmAccountMapping::meta_call(
+			RuntimeOrigin::none(),
+			account.clone(),
+			Box::new(call),
+			nonce,
+			signature,
+			Some(CENTS),
+			PayloadVersion::V0
+		));
+		System::assert_has_event(
+			Event::TipPaidToAuthor { who: account, author: author.clone(), tip: CENTS }.into(),
+		);
+		assert_eq!(Balances::free_balance(&author), DOLLARS + CENTS);
+		// The fee handler doesn't get the tip
+		assert_eq!(TipUnbalancedAmount::get(), 0);
+		assert!(FeeUnbalancedAmount::get() > 0);
+	});
+}
+
+#[test]
+fn eip191_signatures_are_accepted() {
+	use crate::MetaSignature;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let account = mapped_account(1);
+		set_balance(account.clone(), DOLLARS);
+
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		let message_hash = EvmAccountMapping::eip712_message_hash(
+			account.clone(),
+			&codec::Encode::encode(&call),
+			0,
+		);
+		// `personal_sign` of the EIP-712 hash
+		let digest = sp_io::hashing::keccak_256(
+			&[&b"\x19Ethereum Signed Message:\n32"[..], &message_hash[..]].concat(),
+		);
+		let signature = sign(1, &digest).normalize();
+		let personal_signature = MetaSignature::Eip191Secp256k1(signature.into());
+		assert_eq!(personal_signature.signed_digest(&message_hash), digest);
+
+		// The same signature doesn't verify as another scheme
+		assert_noop!(
+			EvmAccountMapping::meta_call(
+				RuntimeOrigin::none(),
+				account.clone(),
+				Box::new(call.clone()),
+				0,
+				MetaSignature::Eip712Secp256k1(signature.into()),
+				None,
+				PayloadVersion::V0
+			),
+			Error::<Test>::InvalidSignature
+		);
+		assert_ok!(EvmAccountMapping::meta_call(
+			RuntimeOrigin::none(),
+			account.clone(),
+			Box::new(call),
+			0,
+			personal_signature,
+			None,
+			PayloadVersion::V0
+		));
+		assert_eq!(crate::AccountNonce::<Test>::get(&account), 1);
+	});
+}
+
+#[test]
+fn holding_fee_adapter_settles_from_the_hold() {
+	use crate::{HoldingFeeAdapter, OnChargeMetaTransaction};
+	use frame_support::{
+		dispatch::{GetDispatchInfo, PostDispatchInfo},
+		traits::fungible::InspectHold,
+	};
+	type Fees = HoldingFeeAdapter<Balances, ()>;
+
+	new_test_ext().execute_with(|| {
+		let who = mapped_account(1);
+		set_balance(who.clone(), DOLLARS);
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		let info = call.get_dispatch_info();
+		let reason = RuntimeHoldReason::EvmAccountMapping(crate::HoldReason::TransactionFee);
+
+		// The estimated fee is held during the execution
+		let held = <Fees as OnChargeMetaTransaction<Test>>::withdraw_fee(
+			&who,
+			&call,
+			&info,
+			10 * CENTS,
+			CENTS,
+		)
+		.expect("Enough balance");
+		assert_eq!(Balances::balance_on_hold(&reason, &who), 10 * CENTS);
+		assert_eq!(Balances::free_balance(&who), DOLLARS - 10 * CENTS);
+
+		// The actual fee is taken from the hold, the rest is released
+		assert_ok!(<Fees as OnChargeMetaTransaction<Test>>::correct_and_deposit_fee(
+			&who,
+			&info,
+			&PostDispatchInfo::default(),
+			4 * CENTS,
+			CENTS,
+			held,
+		));
+		assert_eq!(Balances::balance_on_hold(&reason, &who), 0);
+		assert_eq!(Balances::free_balance(&who), DOLLARS - 4 * CENTS);
+	});
+}
+
+#[test]
+fn nonces_of_reaped_accounts_are_purged() {
+	use frame_support::{pallet_prelude::ValidateUnsigned, unsigned::TransactionSource};
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let who = mapped_account(1);
+		set_balance(who.clone(), DOLLARS);
+		crate::AccountNonce::<Test>::insert(&who, 3);
+
+		// The immortal payloads signed before could be replayed from a purged nonce
+		PurgeNonceOnReap::set(true);
+		set_balance(who.clone(), 0);
+		assert_eq!(crate::AccountNonce::<Test>::get(&who), 3);
+		assert_noop!(
+			EvmAccountMapping::purge_nonce(RuntimeOrigin::root(), who.clone()),
+			Error::<Test>::ImmortalPayloads
+		);
+
+		// Only the purge is gated, the immortal meta-transactions are still accepted
+		MetaMortality::set(true);
+		PurgeNonceOnReap::set(false);
+		set_balance(who.clone(), DOLLARS);
+		let (account, call, nonce, signature) = signed_remark();
+		set_balance(account.clone(), DOLLARS);
+		let meta_call = crate::Call::<Test>::meta_call {
+			who: account,
+			call: Box::new(call),
+			nonce,
+			signature,
+			tip: None,
+			version: PayloadVersion::V0,
+		};
+		assert_ok!(EvmAccountMapping::validate_unsigned(TransactionSource::External, &meta_call));
+
+		// The nonce outlives the account unless enabled
+		assert_noop!(
+			EvmAccountMapping::purge_nonce(RuntimeOrigin::root(), who.clone()),
+			Error::<Test>::AccountInUse
+		);
+		set_balance(who.clone(), 0);
+		assert_eq!(crate::AccountNonce::<Test>::get(&who), 3);
+
+		assert_noop!(
+			EvmAccountMapping::purge_nonce(RuntimeOrigin::signed(who.clone()), who.clone()),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(EvmAccountMapping::purge_nonce(RuntimeOrigin::root(), who.clone()));
+		System::assert_last_event(Event::NoncePurged { who: who.clone(), nonce: 3 }.into());
+		assert!(!crate::AccountNonce::<Test>::contains_key(&who));
+		assert_noop!(
+			EvmAccountMapping::purge_nonce(RuntimeOrigin::root(), who.clone()),
+			Error::<Test>::NoNonce
+		);
+
+		// The nonce is removed with the account
+		PurgeNonceOnReap::set(true);
+		set_balance(who.clone(), DOLLARS);
+		crate::AccountNonce::<Test>::insert(&who, 3);
+		set_balance(who.clone(), 0);
+		assert!(!crate::AccountNonce::<Test>::contains_key(&who));
+	});
+}
+
+#[test]
+fn permit_approves_the_spender() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let owner = mapped_account(1);
+		let spender = mapped_account(2);
+		let relayer = mapped_account(3);
+		let message_hash = EvmAccountMapping::permit_message_hash(&owner, &7, &spender, 100, 0, 5);
+
+		// Signed by another key
+		assert_noop!(
+			EvmAccountMapping::permit(
+				RuntimeOrigin::signed(relayer.clone()),
+				owner.clone(),
+				7,
+				spender.clone(),
+				100,
+				0,
+				5,
+				sign(2, &message_hash),
+			),
+			Error::<Test>::InvalidSignature
+		);
+
+		assert_ok!(EvmAccountMapping::permit(
+			RuntimeOrigin::signed(relayer.clone()),
+			owner.clone(),
+			7,
+			spender.clone(),
+			100,
+			0,
+			5,
+			sign(1, &message_hash),
+		));
+		System::assert_last_event(
+			Event::PermitApproved {
+				owner: owner.clone(),
+				asset_id: 7,
+				spender: spender.clone(),
+				amount: 100,
+			}
+			.into(),
+		);
+		assert_eq!(LastApproval::get(), Some((7, owner.clone(), spender.clone(), 100)));
+		assert_eq!(crate::PermitNonces::<Test>::get(&owner), 1);
+
+		// The permit can't be replayed
+		assert_noop!(
+			EvmAccountMapping::permit(
+				RuntimeOrigin::signed(relayer.clone()),
+				owner.clone(),
+				7,
+				spender.clone(),
+				100,
+				0,
+				5,
+				sign(1, &message_hash),
+			),
+			Error::<Test>::NonceError
+		);
+
+		// Nor used after its deadline
+		let message_hash = EvmAccountMapping::permit_message_hash(&owner, &7, &spender, 100, 1, 5);
+		run_to_block(6);
+		assert_noop!(
+			EvmAccountMapping::permit(
+				RuntimeOrigin::signed(relayer),
+				owner,
+				7,
+				spender,
+				100,
+				1,
+				5,
+				sign(1, &message_hash),
+			),
+			Error::<Test>::PermitExpired
+		);
+	});
+}
+
+#[test]
+fn para_id_separates_domains() {
+	new_test_ext().execute_with(|| {
+		let account = mapped_account(1);
+		let struct_hash = sp_io::hashing::keccak_256(b"Claim(string who)");
+
+		EIP712ParaId::set(Some(2000));
+		let domain = EvmAccountMapping::eip712_domain();
+		assert_eq!(domain.salt, Some(crate::parachain_eip712_salt(2000, None)));
+		let signature = sign(1, &EvmAccountMapping::eip712_typed_data_hash(&struct_hash));
+		assert!(EvmAccountMapping::verify_signed_by(&account, &struct_hash, &signature));
+
+		// Another parachain with the same domain
+		EIP712ParaId::set(Some(2001));
+		assert!(!EvmAccountMapping::verify_signed_by(&account, &struct_hash, &signature));
+
+		// The configured salt is still mixed in
+		EIP712Salt::set(Some([0x5a; 32]));
+		assert_eq!(
+			EvmAccountMapping::eip712_domain().salt,
+			Some(crate::parachain_eip712_salt(2001, Some([0x5a; 32])))
+		);
+		assert_ne!(
+			crate::parachain_eip712_salt(2001, Some([0x5a; 32])),
+			crate::parachain_eip712_salt(2001, None)
+		);
+	});
+}
+
+#[test]
+fn controller_rotation_works() {
+	use crate::EvmIdentity;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let who = mapped_account(1);
+		let relayer = mapped_account(3);
+		let message_hash = [0u8; 32];
+		let public_key = EvmAccountMapping::ecdsa_recover_public_key(
+			&sign(2, &message_hash).normalize(),
+			&message_hash,
+		)
+		.expect("Recoverable");
+		let new_controller = crate::evm_address_of(&public_key);
+		let rotation_hash =
+			EvmAccountMapping::rotate_controller_message_hash(&who, &new_controller, 0);
+
+		// Only the current key authorizes a rotation
+		assert_noop!(
+			EvmAccountMapping::request_controller_rotation(
+				RuntimeOrigin::signed(relayer.clone()),
+				who.clone(),
+				EvmIdentity::PublicKey(public_key),
+				0,
+				sign(2, &rotation_hash),
+			),
+			Error::<Test>::InvalidSignature
+		);
+		assert_ok!(EvmAccountMapping::request_controller_rotation(
+			RuntimeOrigin::signed(relayer.clone()),
+			who.clone(),
+			EvmIdentity::PublicKey(public_key),
+			0,
+			sign(1, &rotation_hash),
+		));
+		System::assert_last_event(
+			Event::ControllerRotationRequested {
+				who: who.clone(),
+				new_controller,
+				executable_at: 11,
+			}
+			.into(),
+		);
+		assert_eq!(crate::AccountNonce::<Test>::get(&who), 1);
+
+		// The current key may cancel it
+		assert_ok!(EvmAccountMapping::cancel_controller_rotation(RuntimeOrigin::signed(
+			who.clone()
+		)));
+		System::assert_last_event(Event::ControllerRotationCancelled { who: who.clone() }.into());
+		assert_noop!(
+			EvmAccountMapping::execute_controller_rotation(
+				RuntimeOrigin::signed(relayer.clone()),
+				who.clone()
+			),
+			Error::<Test>::NoRotation
+		);
+
+		let rotation_hash =
+			EvmAccountMapping::rotate_controller_message_hash(&who, &new_controller, 1);
+		assert_ok!(EvmAccountMapping::request_controller_rotation(
+			RuntimeOrigin::signed(relayer.clone()),
+			who.clone(),
+			EvmIdentity::Address(new_controller),
+			1,
+			sign(1, &rotation_hash),
+		));
+
+		// The rotation is executable after the cancellation period only
+		assert_noop!(
+			EvmAccountMapping::execute_controller_rotation(
+				RuntimeOrigin::signed(relayer.clone()),
+				who.clone()
+			),
+			Error::<Test>::RotationNotExecutable
+		);
+		run_to_block(11);
+		assert_ok!(EvmAccountMapping::execute_controller_rotation(
+			RuntimeOrigin::signed(relayer),
+			who.clone()
+		));
+		System::assert_last_event(
+			Event::ControllerRotated { who: who.clone(), new_controller }.into(),
+		);
+		assert_eq!(EvmAccountMapping::evm_address_of_account(&who), Some(new_controller));
+		assert_eq!(EvmAccountMapping::map_evm_address(&new_controller), Some(who.clone()));
+
+		// Only the new key signs for the account
+		let struct_hash = sp_io::hashing::keccak_256(b"Claim(string who)");
+		let message_hash = EvmAccountMapping::eip712_typed_data_hash(&struct_hash);
+		assert!(EvmAccountMapping::verify_signed_by(&who, &struct_hash, &sign(2, &message_hash)));
+		assert!(!EvmAccountMapping::verify_signed_by(&who, &struct_hash, &sign(1, &message_hash)));
+	});
+}
+
+#[test]
+fn rotated_controller_keeps_its_mapping() {
+	use crate::EvmIdentity;
+	use codec::Encode;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let who = mapped_account(1);
+		let own_account = mapped_account(2);
+		let relayer = mapped_account(3);
+		let public_key_of = |seed| {
+			let message_hash = [0u8; 32];
+			EvmAccountMapping::ecdsa_recover_public_key(
+				&sign(seed, &message_hash).normalize(),
+				&message_hash,
+			)
+			.expect("Recoverable")
+		};
+
+		// A key can't control another account once its own account exists
+		let public_key = public_key_of(4);
+		set_balance(mapped_account(4), DOLLARS);
+		let rotation_hash = EvmAccountMapping::rotate_controller_message_hash(
+			&who,
+			&crate::evm_address_of(&public_key),
+			0,
+		);
+		assert_noop!(
+			EvmAccountMapping::request_controller_rotation(
+				RuntimeOrigin::signed(relayer.clone()),
+				who.clone(),
+				EvmIdentity::PublicKey(public_key),
+				0,
+				sign(1, &rotation_hash),
+			),
+			Error::<Test>::ControllerInUse
+		);
+
+		let public_key = public_key_of(2);
+		let new_controller = crate::evm_address_of(&public_key);
+		let rotation_hash =
+			EvmAccountMapping::rotate_controller_message_hash(&who, &new_controller, 0);
+		assert_ok!(EvmAccountMapping::request_controller_rotation(
+			RuntimeOrigin::signed(relayer.clone()),
+			who.clone(),
+			EvmIdentity::PublicKey(public_key),
+			0,
+			sign(1, &rotation_hash),
+		));
+		run_to_block(11);
+		assert_ok!(EvmAccountMapping::execute_controller_rotation(
+			RuntimeOrigin::signed(relayer),
+			who.clone()
+		));
+
+		// The new key sends a meta-transaction for its own account
+		set_balance(own_account.clone(), DOLLARS);
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![1] });
+		let signature = sign(
+			2,
+			&EvmAccountMapping::meta_call_message_hash(
+				PayloadVersion::V1,
+				&own_account,
+				&call.encode(),
+				0,
+				None,
+			),
+		);
+		assert_ok!(EvmAccountMapping::meta_call(
+			RuntimeOrigin::none(),
+			own_account.clone(),
+			Box::new(call),
+			0,
+			signature,
+			None,
+			PayloadVersion::V1
+		));
+
+		// The reverse mapping still resolves to the rotated account
+		assert_eq!(EvmAccountMapping::map_evm_address(&new_controller), Some(who.clone()));
+		assert_eq!(EvmAccountMapping::evm_address_of_account(&who), Some(new_controller));
+		assert_eq!(EvmAccountMapping::evm_address_of_account(&own_account), None);
+	});
+}
+
+#[test]
+fn admin_pushes_allowed_calls_as_operational() {
+	use crate::CallSelector;
+	use frame_support::dispatch::{DispatchClass, GetDispatchInfo};
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let (account, call, nonce, signature) = signed_remark();
+		set_balance(account.clone(), DOLLARS);
+		let encoded = codec::Encode::encode(&call);
+		let selector = CallSelector::Call(encoded[0], encoded[1]);
+		let meta_call = |origin| {
+			EvmAccountMapping::meta_call_operational(
+				origin,
+				account.clone(),
+				Box::new(call.clone()),
+				nonce,
+				signature,
+				None,
+				PayloadVersion::V0,
+			)
+		};
+
+		assert_eq!(
+			crate::Call::<Test>::meta_call_operational {
+				who: account.clone(),
+				call: Box::new(call.clone()),
+				nonce,
+				signature,
+				tip: None,
+				version: PayloadVersion::V0,
+			}
+			.get_dispatch_info()
+			.class,
+			DispatchClass::Operational
+		);
+
+		// The call has to be allowed
+		assert_noop!(meta_call(RuntimeOrigin::root()), Error::<Test>::OperationalCallNotAllowed);
+		assert_noop!(
+			EvmAccountMapping::set_operational_call(
+				RuntimeOrigin::signed(account.clone()),
+				selector,
+				true
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(EvmAccountMapping::set_operational_call(RuntimeOrigin::root(), selector, true));
+		System::assert_last_event(Event::OperationalCallUpdated { selector, allowed: true }.into());
+
+		// By the admin only
+		assert_noop!(
+			meta_call(RuntimeOrigin::signed(account.clone())),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(meta_call(RuntimeOrigin::root()));
+		assert_eq!(crate::AccountNonce::<Test>::get(&account), nonce + 1);
+		assert_noop!(meta_call(RuntimeOrigin::root()), Error::<Test>::NonceError);
+
+		assert_ok!(EvmAccountMapping::set_operational_call(RuntimeOrigin::root(), selector, false));
+		assert!(!crate::OperationalCalls::<Test>::contains_key(selector));
+	});
+}
+
+#[test]
+fn rejections_are_counted_per_block() {
+	use crate::RejectionStats;
+	use sp_runtime::traits::Dispatchable;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let who = mapped_account(1);
+		set_balance(who.clone(), DOLLARS);
+		// More than the balance of the account
+		let call = RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive {
+			dest: AccountId::from([2u8; 32]),
+			value: 10 * DOLLARS,
+		});
+		let meta_call = |nonce, seed| {
+			let message_hash = EvmAccountMapping::meta_call_message_hash(
+				PayloadVersion::V0,
+				&who,
+				&codec::Encode::encode(&call),
+				nonce,
+				None,
+			);
+			RuntimeCall::from(crate::Call::<Test>::meta_call {
+				who: who.clone(),
+				call: Box::new(call.clone()),
+				nonce,
+				signature: sign(seed, &message_hash),
+				tip: None,
+				version: PayloadVersion::V0,
+			})
+		};
+
+		// The failed inner call is counted with the executed meta-transaction
+		assert_ok!(meta_call(0, 1).dispatch(RuntimeOrigin::none()));
+		assert_eq!(
+			EvmAccountMapping::rejection_stats(),
+			RejectionStats { call_failed: 1, ..Default::default() }
+		);
+
+		// A failed dispatch is reverted with its count
+		assert!(meta_call(1, 2).dispatch(RuntimeOrigin::none()).is_err());
+		assert_eq!(
+			EvmAccountMapping::rejection_stats(),
+			RejectionStats { call_failed: 1, ..Default::default() }
+		);
+
+		// The counters are reset each block
+		run_to_block(2);
+		assert_eq!(EvmAccountMapping::rejection_stats(), RejectionStats::default());
+	});
+}
+
+#[test]
+fn tip_in_basis_points_of_the_inclusion_fee() {
+	use codec::Encode;
+	use frame_support::{
+		dispatch::GetDispatchInfo, pallet_prelude::ValidateUnsigned, unsigned::TransactionSource,
+	};
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let who = mapped_account(1);
+		set_balance(who.clone(), DOLLARS);
+
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: b"Hello".to_vec() });
+		let inclusion_fee = TransactionPayment::compute_fee(
+			call.encoded_size() as u32,
+			&call.get_dispatch_info(),
+			0,
+		);
+		assert!(inclusion_fee > 2);
+		let sign_call = |nonce, max_fee| {
+			let message_hash = EvmAccountMapping::tip_bps_message_hash(
+				&who,
+				&call.encode(),
+				nonce,
+				5_000,
+				max_fee,
+			);
+			sign(1, &message_hash)
+		};
+
+		// The inclusion fee alone exceeds the maximum fee, nothing is charged
+		let max_fee = inclusion_fee - 1;
+		assert_eq!(
+			EvmAccountMapping::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::<Test>::meta_call_with_tip_bps {
+					who: who.clone(),
+					call: Box::new(call.clone()),
+					nonce: 0,
+					signature: sign_call(0, max_fee),
+					tip_bps: 5_000,
+					max_fee,
+				}
+			),
+			Err(crate::InvalidMetaTransaction::FeeLimitExceeded.into())
+		);
+		assert_noop!(
+			EvmAccountMapping::meta_call_with_tip_bps(
+				RuntimeOrigin::none(),
+				who.clone(),
+				Box::new(call.clone()),
+				0,
+				sign_call(0, max_fee),
+				5_000,
+				max_fee
+			),
+			Error::<Test>::FeeLimitExceeded
+		);
+
+		// Half of the inclusion fee is tipped
+		let max_fee = 2 * inclusion_fee;
+		assert_ok!(EvmAccountMapping::meta_call_with_tip_bps(
+			RuntimeOrigin::none(),
+			who.clone(),
+			Box::new(call.clone()),
+			0,
+			sign_call(0, max_fee),
+			5_000,
+			max_fee
+		));
+		let tip = inclusion_fee / 2;
+		System::assert_has_event(
+			Event::TransactionFeePaid { who: who.clone(), actual_fee: inclusion_fee + tip, tip }
+				.into(),
+		);
+
+		// The tip is capped by the maximum fee
+		let max_fee = inclusion_fee + 1;
+		assert_ok!(EvmAccountMapping::meta_call_with_tip_bps(
+			RuntimeOrigin::none(),
+			who.clone(),
+			Box::new(call.clone()),
+			1,
+			sign_call(1, max_fee),
+			5_000,
+			max_fee
+		));
+		System::assert_has_event(
+			Event::TransactionFeePaid { who: who.clone(), actual_fee: inclusion_fee + 1, tip: 1 }
+				.into(),
+		);
+		assert_eq!(crate::AccountNonce::<Test>::get(&who), 2);
+
+		// The cap applies to the adjusted fee, the tips above 1 cost a surcharge
+		RemarkFeeSurcharge::set(Some((inclusion_fee + 1, inclusion_fee)));
+		let max_fee = inclusion_fee + inclusion_fee / 2;
+		assert_eq!(EvmAccountMapping::tip_of_bps(&call, 5_000, max_fee), Some(1));
+		System::reset_events();
+		assert_ok!(EvmAccountMapping::meta_call_with_tip_bps(
+			RuntimeOrigin::none(),
+			who.clone(),
+			Box::new(call.clone()),
+			2,
+			sign_call(2, max_fee),
+			5_000,
+			max_fee
+		));
+		System::assert_has_event(
+			Event::TransactionFeePaid { who: who.clone(), actual_fee: inclusion_fee + 1, tip: 1 }
+				.into(),
+		);
+		assert_eq!(crate::AccountNonce::<Test>::get(&who), 3);
+	});
+}
+
+#[test]
+fn sponsorships_pay_the_fees_of_their_targets() {
+	use crate::{CallSelector, SponsorshipTarget};
+	use codec::Encode;
+	use frame_support::{
+		dispatch::GetDispatchInfo, pallet_prelude::ValidateUnsigned, unsigned::TransactionSource,
+	};
+	use sp_runtime::transaction_validity::InvalidTransaction;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let sponsor = AccountId::from([9u8; 32]);
+		set_balance(sponsor.clone(), DOLLARS);
+		// The signer has no balance
+		let who = mapped_account(1);
+		let message_hash = [0u8; 32];
+		let public_key = EvmAccountMapping::ecdsa_recover_public_key(
+			&sign(1, &message_hash).normalize(),
+			&message_hash,
+		)
+		.expect("Recoverable");
+		let target = SponsorshipTarget::Signer(crate::evm_address_of(&public_key));
+
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: b"Hello".to_vec() });
+		let fees = TransactionPayment::compute_fee(
+			call.encoded_size() as u32,
+			&call.get_dispatch_info(),
+			0,
+		) + 1000;
+		let meta_call = |nonce: u64| {
+			let message_hash = EvmAccountMapping::meta_call_message_hash(
+				PayloadVersion::V0,
+				&who,
+				&call.encode(),
+				nonce,
+				None,
+			);
+			crate::Call::<Test>::meta_call {
+				who: who.clone(),
+				call: Box::new(call.clone()),
+				nonce,
+				signature: sign(1, &message_hash),
+				tip: None,
+				version: PayloadVersion::V0,
+			}
+		};
+		let dispatch = |nonce: u64| {
+			let crate::Call::meta_call { who, call, nonce, signature, tip, version } =
+				meta_call(nonce)
+			else {
+				unreachable!()
+			};
+			EvmAccountMapping::meta_call(
+				RuntimeOrigin::none(),
+				who,
+				call,
+				nonce,
+				signature,
+				tip,
+				version,
+			)
+		};
+
+		assert_eq!(
+			EvmAccountMapping::validate_unsigned(TransactionSource::External, &meta_call(0)),
+			Err(InvalidTransaction::Payment.into())
+		);
+
+		// The budget covers one meta-transaction
+		assert_ok!(EvmAccountMapping::sponsor(
+			RuntimeOrigin::signed(sponsor.clone()),
+			target,
+			2 * fees - 1,
+			10
+		));
+		assert_ok!(EvmAccountMapping::validate_unsigned(
+			TransactionSource::External,
+			&meta_call(0)
+		));
+		assert_ok!(dispatch(0));
+		assert_eq!(Balances::free_balance(&sponsor), DOLLARS - fees);
+		assert_eq!(crate::Sponsorships::<Test>::get(target).map(|s| s.budget), Some(fees - 1));
+
+		// What is left of the budget doesn't cover the next one, which the signer pays
+		assert_eq!(
+			EvmAccountMapping::validate_unsigned(TransactionSource::External, &meta_call(1)),
+			Err(InvalidTransaction::Payment.into())
+		);
+		set_balance(who.clone(), DOLLARS);
+		assert_ok!(dispatch(1));
+		System::assert_has_event(
+			Event::SponsorshipDepleted { sponsor: sponsor.clone(), target, budget: fees - 1 }
+				.into(),
+		);
+		assert_eq!(Balances::free_balance(&sponsor), DOLLARS - fees);
+		assert_eq!(Balances::free_balance(&who), DOLLARS - fees);
+		assert!(!crate::Sponsorships::<Test>::contains_key(target));
+
+		// Only the sponsor ends a sponsorship before it expires
+		let other = AccountId::from([8u8; 32]);
+		let target = SponsorshipTarget::Call(CallSelector::Pallet(0));
+		assert_ok!(EvmAccountMapping::sponsor(
+			RuntimeOrigin::signed(sponsor.clone()),
+			target,
+			DOLLARS,
+			5
+		));
+		assert_noop!(
+			EvmAccountMapping::sponsor(RuntimeOrigin::signed(other.clone()), target, DOLLARS, 5),
+			Error::<Test>::SponsorshipTaken
+		);
+		assert_noop!(
+			EvmAccountMapping::end_sponsorship(RuntimeOrigin::signed(other.clone()), target),
+			Error::<Test>::NotSponsor
+		);
+		run_to_block(5);
+		assert_eq!(EvmAccountMapping::active_sponsorship_of(&target), None);
+		assert_ok!(EvmAccountMapping::end_sponsorship(RuntimeOrigin::signed(other), target));
+		System::assert_last_event(Event::SponsorshipEnded { sponsor, target }.into());
+	});
+}
+
+#[test]
+fn fee_payer_is_resolved_from_sponsorships() {
+	use crate::{CallSelector, SponsorshipTarget};
+	use codec::Encode;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let (who, call, ..) = signed_remark();
+		let call_data = call.encode();
+		let sponsor = AccountId::from([9u8; 32]);
+		let other = AccountId::from([8u8; 32]);
+		set_balance(sponsor.clone(), DOLLARS);
+		set_balance(other.clone(), DOLLARS);
+		assert_eq!(EvmAccountMapping::fee_payer_of(&who, &call_data, 1000), Some(who.clone()));
+		assert_eq!(EvmAccountMapping::fee_payer_of(&who, &[0xff, 0xff], 1000), None);
+
+		assert_ok!(EvmAccountMapping::sponsor(
+			RuntimeOrigin::signed(sponsor.clone()),
+			SponsorshipTarget::Call(CallSelector::Pallet(0)),
+			2000,
+			10
+		));
+		assert_eq!(EvmAccountMapping::fee_payer_of(&who, &call_data, 1000), Some(sponsor));
+		// What is left of the budget doesn't cover the fees
+		assert_eq!(EvmAccountMapping::fee_payer_of(&who, &call_data, 3000), Some(who.clone()));
+
+		// The sponsorship of the signer comes first once its address is recorded
+		let evm_address = sp_core::H160::repeat_byte(1);
+		assert_ok!(EvmAccountMapping::sponsor(
+			RuntimeOrigin::signed(other.clone()),
+			SponsorshipTarget::Signer(evm_address),
+			DOLLARS,
+			10
+		));
+		crate::EvmAddresses::<Test>::insert(&who, evm_address);
+		assert_eq!(EvmAccountMapping::fee_payer_of(&who, &call_data, 3000), Some(other));
+	});
+}
+
+#[test]
+fn sponsorship_deposits_are_held_and_released() {
+	use crate::{CallSelector, SponsorshipTarget};
+	use frame_support::traits::fungible::InspectHold;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		SponsorshipDeposit::set(DOLLARS);
+		let reason = RuntimeHoldReason::EvmAccountMapping(crate::HoldReason::SponsorshipDeposit);
+
+		let sponsor = AccountId::from([9u8; 32]);
+		let other = AccountId::from([8u8; 32]);
+		let target = SponsorshipTarget::Call(CallSelector::Pallet(0));
+
+		// Not enough to hold the deposit
+		assert!(EvmAccountMapping::sponsor(
+			RuntimeOrigin::signed(sponsor.clone()),
+			target,
+			DOLLARS,
+			5
+		)
+		.is_err());
+
+		set_balance(sponsor.clone(), 10 * DOLLARS);
+		set_balance(other.clone(), 10 * DOLLARS);
+		// Replacing a sponsorship holds nothing more
+		for expires_at in [5, 3] {
+			assert_ok!(EvmAccountMapping::sponsor(
+				RuntimeOrigin::signed(sponsor.clone()),
+				target,
+				DOLLARS,
+				expires_at
+			));
+		}
+		assert_eq!(Balances::balance_on_hold(&reason, &sponsor), DOLLARS);
+
+		assert_ok!(EvmAccountMapping::end_sponsorship(
+			RuntimeOrigin::signed(sponsor.clone()),
+			target
+		));
+		assert_eq!(Balances::balance_on_hold(&reason, &sponsor), 0);
+
+		// Taking over an expired sponsorship releases the deposit of its sponsor
+		assert_ok!(EvmAccountMapping::sponsor(
+			RuntimeOrigin::signed(sponsor.clone()),
+			target,
+			DOLLARS,
+			3
+		));
+		run_to_block(3);
+		assert_ok!(EvmAccountMapping::sponsor(
+			RuntimeOrigin::signed(other.clone()),
+			target,
+			DOLLARS,
+			5
+		));
+		assert_eq!(Balances::balance_on_hold(&reason, &sponsor), 0);
+		assert_eq!(Balances::balance_on_hold(&reason, &other), DOLLARS);
+	});
+}
+
+#[test]
+fn meta_calls_may_be_signed_for_extra_verifying_contracts() {
+	use codec::Encode;
+	use frame_support::{pallet_prelude::ValidateUnsigned, unsigned::TransactionSource};
+	use sp_runtime::transaction_validity::InvalidTransaction;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let staging = sp_core::H160::repeat_byte(0x5a);
+		EIP712ExtraVerifyingContracts::set(vec![staging]);
+		let account = mapped_account(1);
+		set_balance(account.clone(), DOLLARS);
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![1] });
+		let message_hash = |nonce| {
+			EvmAccountMapping::meta_call_message_hash(
+				PayloadVersion::V1,
+				&account,
+				&call.encode(),
+				nonce,
+				None,
+			)
+		};
+		let meta_call = |nonce, signature| crate::Call::meta_call {
+			who: account.clone(),
+			call: Box::new(call.clone()),
+			nonce,
+			signature,
+			tip: None,
+			version: PayloadVersion::V1,
+		};
+		let signed_within = |contract| {
+			System::events().iter().any(|record| {
+				matches!(
+					&record.event,
+					RuntimeEvent::EvmAccountMapping(Event::CallDone { verifying_contract, .. })
+						if *verifying_contract == contract
+				)
+			})
+		};
+
+		// Signed within the domain of an unknown verifying contract
+		let unknown = sp_core::H160::repeat_byte(0x11);
+		let signature =
+			sign(1, &EvmAccountMapping::with_verifying_contract(unknown, || message_hash(0)));
+		assert_eq!(
+			EvmAccountMapping::validate_unsigned(
+				TransactionSource::External,
+				&meta_call(0, signature)
+			),
+			Err(InvalidTransaction::BadSigner.into())
+		);
+
+		let signature =
+			sign(1, &EvmAccountMapping::with_verifying_contract(staging, || message_hash(0)));
+		assert_ok!(EvmAccountMapping::validate_unsigned(
+			TransactionSource::External,
+			&meta_call(0, signature)
+		));
+		assert_ok!(EvmAccountMapping::pre_dispatch(&meta_call(0, signature)));
+		assert_ok!(EvmAccountMapping::meta_call(
+			RuntimeOrigin::none(),
+			account.clone(),
+			Box::new(call.clone()),
+			0,
+			signature,
+			None,
+			PayloadVersion::V1
+		));
+		assert!(signed_within(staging));
+
+		// Without `pre_dispatch` the signer is recovered within the same domains
+		let signature =
+			sign(1, &EvmAccountMapping::with_verifying_contract(unknown, || message_hash(1)));
+		assert_noop!(
+			EvmAccountMapping::meta_call(
+				RuntimeOrigin::none(),
+				account.clone(),
+				Box::new(call.clone()),
+				1,
+				signature,
+				None,
+				PayloadVersion::V1
+			),
+			Error::<Test>::InvalidSignature
+		);
+		System::reset_events();
+		let signature =
+			sign(1, &EvmAccountMapping::with_verifying_contract(staging, || message_hash(1)));
+		assert_ok!(EvmAccountMapping::meta_call(
+			RuntimeOrigin::none(),
+			account.clone(),
+			Box::new(call.clone()),
+			1,
+			signature,
+			None,
+			PayloadVersion::V1
+		));
+		assert!(signed_within(staging));
+		assert_ok!(EvmAccountMapping::meta_call(
+			RuntimeOrigin::none(),
+			account.clone(),
+			Box::new(call.clone()),
+			2,
+			sign(1, &message_hash(2)),
+			None,
+			PayloadVersion::V1
+		));
+		assert!(signed_within(EIP712VerifyingContractAddress::get()));
+	});
+}
+
+#[test]
+fn operational_meta_calls_may_be_signed_for_extra_verifying_contracts() {
+	use crate::CallSelector;
+	use codec::Encode;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let staging = sp_core::H160::repeat_byte(0x5a);
+		EIP712ExtraVerifyingContracts::set(vec![staging]);
+		let account = mapped_account(1);
+		set_balance(account.clone(), DOLLARS);
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![1] });
+		let encoded = call.encode();
+		assert_ok!(EvmAccountMapping::set_operational_call(
+			RuntimeOrigin::root(),
+			CallSelector::Call(encoded[0], encoded[1]),
+			true
+		));
+
+		// Dispatched by the admin, the signer is only recovered at the dispatch
+		let signature = sign(
+			1,
+			&EvmAccountMapping::with_verifying_contract(staging, || {
+				EvmAccountMapping::meta_call_message_hash(
+					PayloadVersion::V1,
+					&account,
+					&encoded,
+					0,
+					None,
+				)
+			}),
+		);
+		assert_ok!(EvmAccountMapping::meta_call_operational(
+			RuntimeOrigin::root(),
+			account.clone(),
+			Box::new(call),
+			0,
+			signature,
+			None,
+			PayloadVersion::V1
+		));
+		assert!(System::events().iter().any(|record| matches!(
+			&record.event,
+			RuntimeEvent::EvmAccountMapping(Event::CallDone { verifying_contract, .. })
+				if *verifying_contract == staging
+		)));
+	});
+}
+
+#[test]
+fn windowed_replay_protection_executes_payloads_in_any_order() {
+	use codec::Encode;
+	use frame_support::{pallet_prelude::ValidateUnsigned, unsigned::TransactionSource};
+	use sp_runtime::transaction_validity::InvalidTransaction;
+
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		UseWindowedReplayProtection::set(true);
+		let account = mapped_account(1);
+		set_balance(account.clone(), DOLLARS);
+		let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![1] });
+		let meta_call = |nonce| {
+			let signature = sign(
+				1,
+				&EvmAccountMapping::meta_call_message_hash(
+					PayloadVersion::V1,
+					&account,
+					&call.encode(),
+					nonce,
+					None,
+				),
+			);
+			crate::Call::<Test>::meta_call {
+				who: account.clone(),
+				call: Box::new(call.clone()),
+				nonce,
+				signature,
+				tip: None,
+				version: PayloadVersion::V1,
+			}
+		};
+		let dispatch = |nonce| {
+			let crate::Call::meta_call { signature, .. } = meta_call(nonce) else { unreachable!() };
+			EvmAccountMapping::meta_call(
+				RuntimeOrigin::none(),
+				account.clone(),
+				Box::new(call.clone()),
+				nonce,
+				signature,
+				None,
+				PayloadVersion::V1,
+			)
+		};
+
+		// The nonce is the last block the payload may be executed in
+		assert_eq!(
+			EvmAccountMapping::validate_unsigned(TransactionSource::External, &meta_call(0)),
+			Err(InvalidTransaction::Stale.into())
+		);
+		assert_eq!(
+			EvmAccountMapping::validate_unsigned(TransactionSource::External, &meta_call(7)),
+			Err(InvalidTransaction::Future.into())
+		);
+		let valid =
+			EvmAccountMapping::validate_unsigned(TransactionSource::External, &meta_call(3))
+				.unwrap();
+		assert_eq!(valid.requires, Vec::<Vec<u8>>::new());
+		assert_eq!(valid.longevity, 3);
+
+		// Executed payloads are not replayed until they expire
+		assert_ok!(dispatch(3));
+		assert_eq!(
+			EvmAccountMapping::validate_unsigned(TransactionSource::External, &meta_call(3)),
+			Err(InvalidTransaction::Stale.into())
+		);
+		assert_noop!(dispatch(3), Error::<Test>::NonceError);
+
+		// Out of order
+		assert_ok!(dispatch(2));
+		assert_eq!(crate::AccountNonce::<Test>::get(&account), 0);
+		assert_eq!(crate::ExecutedPayloads::<Test>::iter().count(), 2);
+
+		run_to_block(3);
+		assert_eq!(crate::ExecutedPayloads::<Test>::iter().count(), 1);
+		run_to_block(4);
+		assert_eq!(crate::ExecutedPayloads::<Test>::iter().count(), 0);
+		assert_eq!(
+			EvmAccountMapping::validate_unsigned(TransactionSource::External, &meta_call(3)),
+			Err(InvalidTransaction::Stale.into())
+		);
+	});
+}
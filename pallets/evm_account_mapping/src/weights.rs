@@ -15,15 +15,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! Autogenerated weights for pallet_evm_account_mapping
+//! Weights for pallet_evm_account_mapping
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-11-29, STEPS: `50`, REPEAT: `50`, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! WORST CASE MAP SIZE: `1000000`
-//! CPU: `<UNKNOWN>`
-//! EXECUTION: ``, WASM-EXECUTION: `Compiled`, CHAIN: `Some("dev")`, DB CACHE: `1024`
+//! ESTIMATES: the file was generated by the benchmark CLI on 2023-11-29, the weights of the
+//! extrinsics and storage accesses added since are written by hand and haven't been measured.
+//! Regenerate it from the benchmarks in `benchmarking.rs` before relying on the numbers.
 
-// Executed Command:
+// Command to regenerate:
 //    ./target/production/node-template
 //    benchmark
 //    pallet
@@ -53,6 +51,29 @@ use core::marker::PhantomData;
 /// Weight functions needed for pallet_evm_account_mapping.
 pub trait WeightInfo {
     fn meta_call() -> Weight;
+    fn block_address() -> Weight;
+    fn unblock_address() -> Weight;
+    fn meta_transfer() -> Weight;
+    fn initiate_recovery() -> Weight;
+    fn veto_recovery() -> Weight;
+    fn execute_recovery() -> Weight;
+    fn link_accounts() -> Weight;
+    fn unlink_accounts() -> Weight;
+    fn meta_call_as_linked() -> Weight;
+    fn grant() -> Weight;
+    fn revoke() -> Weight;
+    fn set_call_filter_rule() -> Weight;
+    fn fund_mapped_account() -> Weight;
+    fn purge_nonce() -> Weight;
+    fn permit() -> Weight;
+    fn request_controller_rotation() -> Weight;
+    fn cancel_controller_rotation() -> Weight;
+    fn execute_controller_rotation() -> Weight;
+    fn set_operational_call() -> Weight;
+    fn meta_call_operational() -> Weight;
+    fn sponsor() -> Weight;
+    fn end_sponsorship() -> Weight;
+    fn meta_typed_call_unconvertible() -> Weight;
 }
 
 /// Weights for pallet_evm_account_mapping using the Substrate node and recommended hardware.
@@ -64,15 +85,392 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     /// Proof: `EvmAccountMapping::AccountNonce` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
     /// Storage: `TransactionPayment::NextFeeMultiplier` (r:1 w:0)
     /// Proof: `TransactionPayment::NextFeeMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::EvmAddresses` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::EvmAddresses` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::MappedAccounts` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::MappedAccounts` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::PreDispatchedSigner` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::PreDispatchedSigner` (`max_values`: Some(1), `max_size`: Some(137), added: 632, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::Blocked` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::Blocked` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::CallRateLimits` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::CallRateLimits` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::CallFilterOverrides` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::CallFilterOverrides` (`max_values`: None, `max_size`: Some(20), added: 2495, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::OperationalCalls` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::OperationalCalls` (`max_values`: None, `max_size`: Some(19), added: 2494, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::Sponsorships` (r:3 w:1)
+    /// Proof: `EvmAccountMapping::Sponsorships` (`max_values`: None, `max_size`: Some(89), added: 2564, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::SignerSponsorships` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::SignerSponsorships` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::FreeCallsUsed` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::FreeCallsUsed` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+    /// Storage: `Balances::Holds` (r:1 w:1)
+    /// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::Receipts` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::Receipts` (`max_values`: None, `max_size`: Some(92), added: 2567, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::ReceiptExpiries` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::ReceiptExpiries` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
     fn meta_call() -> Weight {
         // Proof Size summary in bytes:
         //   Measured:  `176`
         //   Estimated: `3593`
         // Minimum execution time: 23_000_000 picoseconds.
         Weight::from_parts(24_000_000, 3593)
+            .saturating_add(T::DbWeight::get().reads(16_u64))
+            .saturating_add(T::DbWeight::get().writes(12_u64))
+    }
+    /// Storage: `EvmAccountMapping::Blocked` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Blocked` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+    fn block_address() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `6`
+        //   Estimated: `3501`
+        // Minimum execution time: 9_000_000 picoseconds.
+        Weight::from_parts(10_000_000, 3501)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    /// Storage: `EvmAccountMapping::Blocked` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Blocked` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+    fn unblock_address() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `42`
+        //   Estimated: `3501`
+        // Minimum execution time: 10_000_000 picoseconds.
+        Weight::from_parts(11_000_000, 3501)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    /// Storage: `System::Account` (r:2 w:2)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::AccountNonce` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::AccountNonce` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+    /// Storage: `TransactionPayment::NextFeeMultiplier` (r:1 w:0)
+    /// Proof: `TransactionPayment::NextFeeMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::EvmAddresses` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::EvmAddresses` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::MappedAccounts` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::MappedAccounts` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::PreDispatchedSigner` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::PreDispatchedSigner` (`max_values`: Some(1), `max_size`: Some(137), added: 632, mode: `MaxEncodedLen`)
+    fn meta_transfer() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `279`
+        //   Estimated: `6196`
+        // Minimum execution time: 58_000_000 picoseconds.
+        Weight::from_parts(60_000_000, 6196)
+            .saturating_add(T::DbWeight::get().reads(6_u64))
+            .saturating_add(T::DbWeight::get().writes(6_u64))
+    }
+    /// Storage: `EvmAccountMapping::Recoveries` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Recoveries` (`max_values`: None, `max_size`: Some(84), added: 2559, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::EvmAddresses` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::EvmAddresses` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    fn initiate_recovery() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `115`
+        //   Estimated: `3549`
+        // Minimum execution time: 14_000_000 picoseconds.
+        Weight::from_parts(15_000_000, 3549)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    /// Storage: `EvmAccountMapping::Recoveries` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Recoveries` (`max_values`: None, `max_size`: Some(84), added: 2559, mode: `MaxEncodedLen`)
+    fn veto_recovery() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `90`
+        //   Estimated: `3549`
+        // Minimum execution time: 11_000_000 picoseconds.
+        Weight::from_parts(12_000_000, 3549)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    /// Storage: `EvmAccountMapping::Recoveries` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Recoveries` (`max_values`: None, `max_size`: Some(84), added: 2559, mode: `MaxEncodedLen`)
+    /// Storage: `System::Account` (r:2 w:2)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::EvmAddresses` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::EvmAddresses` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::MappedAccounts` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::MappedAccounts` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::Controllers` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::Controllers` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::ControllerRotations` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::ControllerRotations` (`max_values`: None, `max_size`: Some(76), added: 2551, mode: `MaxEncodedLen`)
+    fn execute_recovery() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `354`
+        //   Estimated: `6196`
+        // Minimum execution time: 60_000_000 picoseconds.
+        Weight::from_parts(62_000_000, 6196)
+            .saturating_add(T::DbWeight::get().reads(4_u64))
+            .saturating_add(T::DbWeight::get().writes(7_u64))
+    }
+    /// Storage: `EvmAccountMapping::AccountNonce` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::AccountNonce` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::Blocked` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::Blocked` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::Links` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Links` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::ReverseLinks` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::ReverseLinks` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+    /// Storage: `Balances::Holds` (r:1 w:1)
+    /// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+    /// Storage: `System::Account` (r:1 w:1)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    fn link_accounts() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `6`
+        //   Estimated: `3545`
+        // Minimum execution time: 68_000_000 picoseconds.
+        Weight::from_parts(70_000_000, 3545)
+            .saturating_add(T::DbWeight::get().reads(6_u64))
+            .saturating_add(T::DbWeight::get().writes(5_u64))
+    }
+    /// Storage: `EvmAccountMapping::Links` (r:2 w:1)
+    /// Proof: `EvmAccountMapping::Links` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::ReverseLinks` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::ReverseLinks` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+    /// Storage: `Balances::Holds` (r:1 w:1)
+    /// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+    /// Storage: `System::Account` (r:1 w:1)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    fn unlink_accounts() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `176`
+        //   Estimated: `6100`
+        // Minimum execution time: 17_000_000 picoseconds.
+        Weight::from_parts(18_000_000, 6100)
+            .saturating_add(T::DbWeight::get().reads(5_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+    }
+    /// Storage: `EvmAccountMapping::Links` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::Links` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+    /// Storage: `System::Account` (r:1 w:1)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::AccountNonce` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::AccountNonce` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+    /// Storage: `TransactionPayment::NextFeeMultiplier` (r:1 w:0)
+    /// Proof: `TransactionPayment::NextFeeMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::EvmAddresses` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::EvmAddresses` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::MappedAccounts` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::MappedAccounts` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::PreDispatchedSigner` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::PreDispatchedSigner` (`max_values`: Some(1), `max_size`: Some(137), added: 632, mode: `MaxEncodedLen`)
+    fn meta_call_as_linked() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `254`
+        //   Estimated: `3593`
+        // Minimum execution time: 27_000_000 picoseconds.
+        Weight::from_parts(28_000_000, 3593)
+            .saturating_add(T::DbWeight::get().reads(6_u64))
+            .saturating_add(T::DbWeight::get().writes(5_u64))
+    }
+    /// Storage: `EvmAccountMapping::Grants` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Grants` (`max_values`: None, `max_size`: Some(109), added: 2584, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::GrantExpiries` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::GrantExpiries` (`max_values`: None, `max_size`: Some(93), added: 2568, mode: `MaxEncodedLen`)
+    /// Storage: `Balances::Holds` (r:1 w:1)
+    /// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+    /// Storage: `System::Account` (r:1 w:1)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    fn grant() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `6`
+        //   Estimated: `3574`
+        // Minimum execution time: 13_000_000 picoseconds.
+        Weight::from_parts(14_000_000, 3574)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+    }
+    /// Storage: `EvmAccountMapping::Grants` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Grants` (`max_values`: None, `max_size`: Some(109), added: 2584, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::GrantExpiries` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::GrantExpiries` (`max_values`: None, `max_size`: Some(93), added: 2568, mode: `MaxEncodedLen`)
+    /// Storage: `Balances::Holds` (r:1 w:1)
+    /// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+    /// Storage: `System::Account` (r:1 w:1)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    fn revoke() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `171`
+        //   Estimated: `3574`
+        // Minimum execution time: 14_000_000 picoseconds.
+        Weight::from_parts(15_000_000, 3574)
             .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+    }
+    /// Storage: `EvmAccountMapping::CallFilterOverrides` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::CallFilterOverrides` (`max_values`: None, `max_size`: Some(20), added: 2495, mode: `MaxEncodedLen`)
+    fn set_call_filter_rule() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `0`
+        //   Estimated: `0`
+        // Minimum execution time: 8_000_000 picoseconds.
+        Weight::from_parts(9_000_000, 0)
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    /// Storage: `EvmAccountMapping::EvmAddresses` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::EvmAddresses` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `System::Account` (r:2 w:2)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::MappedAccounts` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::MappedAccounts` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    fn fund_mapped_account() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `103`
+        //   Estimated: `6196`
+        // Minimum execution time: 61_000_000 picoseconds.
+        Weight::from_parts(63_000_000, 6196)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+    }
+    /// Storage: `System::Account` (r:1 w:0)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::Links` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::Links` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::AccountNonce` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::AccountNonce` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+    fn purge_nonce() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `6`
+        //   Estimated: `3593`
+        // Minimum execution time: 12_000_000 picoseconds.
+        Weight::from_parts(13_000_000, 3593)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    /// Storage: `EvmAccountMapping::PermitNonces` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::PermitNonces` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::Blocked` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::Blocked` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+    fn permit() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `6`
+        //   Estimated: `3521`
+        // Minimum execution time: 55_000_000 picoseconds.
+        Weight::from_parts(56_000_000, 3521)
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    /// Storage: `EvmAccountMapping::AccountNonce` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::AccountNonce` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::Controllers` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::Controllers` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::Blocked` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::Blocked` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::ControllerRotations` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::ControllerRotations` (`max_values`: None, `max_size`: Some(76), added: 2551, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::MappedAccounts` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::MappedAccounts` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    fn request_controller_rotation() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `250`
+        //   Estimated: `3541`
+        // Minimum execution time: 58_000_000 picoseconds.
+        Weight::from_parts(59_000_000, 3541)
+            .saturating_add(T::DbWeight::get().reads(5_u64))
             .saturating_add(T::DbWeight::get().writes(2_u64))
     }
+    /// Storage: `EvmAccountMapping::ControllerRotations` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::ControllerRotations` (`max_values`: None, `max_size`: Some(76), added: 2551, mode: `MaxEncodedLen`)
+    fn cancel_controller_rotation() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `6`
+        //   Estimated: `3541`
+        // Minimum execution time: 11_000_000 picoseconds.
+        Weight::from_parts(12_000_000, 3541)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    /// Storage: `EvmAccountMapping::ControllerRotations` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::ControllerRotations` (`max_values`: None, `max_size`: Some(76), added: 2551, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::MappedAccounts` (r:1 w:2)
+    /// Proof: `EvmAccountMapping::MappedAccounts` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::EvmAddresses` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::EvmAddresses` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::Controllers` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::Controllers` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    fn execute_controller_rotation() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `180`
+        //   Estimated: `3541`
+        // Minimum execution time: 19_000_000 picoseconds.
+        Weight::from_parts(20_000_000, 3541)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(5_u64))
+    }
+    /// Storage: `EvmAccountMapping::OperationalCalls` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::OperationalCalls` (`max_values`: None, `max_size`: Some(19), added: 2494, mode: `MaxEncodedLen`)
+    fn set_operational_call() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `0`
+        //   Estimated: `0`
+        // Minimum execution time: 9_000_000 picoseconds.
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    /// Storage: `EvmAccountMapping::OperationalCalls` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::OperationalCalls` (`max_values`: None, `max_size`: Some(19), added: 2494, mode: `MaxEncodedLen`)
+    /// Storage: `System::Account` (r:1 w:1)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::AccountNonce` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::AccountNonce` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+    /// Storage: `TransactionPayment::NextFeeMultiplier` (r:1 w:0)
+    /// Proof: `TransactionPayment::NextFeeMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::EvmAddresses` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::EvmAddresses` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::MappedAccounts` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::MappedAccounts` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::PreDispatchedSigner` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::PreDispatchedSigner` (`max_values`: Some(1), `max_size`: Some(137), added: 632, mode: `MaxEncodedLen`)
+    fn meta_call_operational() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `176`
+        //   Estimated: `3593`
+        // Minimum execution time: 26_000_000 picoseconds.
+        Weight::from_parts(27_000_000, 3593)
+            .saturating_add(T::DbWeight::get().reads(6_u64))
+            .saturating_add(T::DbWeight::get().writes(5_u64))
+    }
+    /// Storage: `EvmAccountMapping::Sponsorships` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Sponsorships` (`max_values`: None, `max_size`: Some(89), added: 2564, mode: `MaxEncodedLen`)
+    /// Storage: `Balances::Holds` (r:2 w:2)
+    /// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+    /// Storage: `System::Account` (r:2 w:2)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    fn sponsor() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `310`
+        //   Estimated: `6196`
+        // Minimum execution time: 30_000_000 picoseconds.
+        Weight::from_parts(31_000_000, 6196)
+            .saturating_add(T::DbWeight::get().reads(5_u64))
+            .saturating_add(T::DbWeight::get().writes(5_u64))
+    }
+    /// Storage: `EvmAccountMapping::Sponsorships` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Sponsorships` (`max_values`: None, `max_size`: Some(89), added: 2564, mode: `MaxEncodedLen`)
+    /// Storage: `Balances::Holds` (r:1 w:1)
+    /// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+    /// Storage: `System::Account` (r:1 w:1)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    fn end_sponsorship() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `230`
+        //   Estimated: `3593`
+        // Minimum execution time: 20_000_000 picoseconds.
+        Weight::from_parts(21_000_000, 3593)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+    }
+    /// PLACEHOLDER, not benchmarked yet: regenerate with the `meta_typed_call_unconvertible`
+    /// benchmark. The conversion failure path doesn't access the storage.
+    fn meta_typed_call_unconvertible() -> Weight {
+        Weight::from_parts(6_000_000, 0)
+    }
 }
 
 // For backwards compatibility and tests.
@@ -83,13 +481,390 @@ impl WeightInfo for () {
     /// Proof: `EvmAccountMapping::AccountNonce` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
     /// Storage: `TransactionPayment::NextFeeMultiplier` (r:1 w:0)
     /// Proof: `TransactionPayment::NextFeeMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::EvmAddresses` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::EvmAddresses` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::MappedAccounts` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::MappedAccounts` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::PreDispatchedSigner` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::PreDispatchedSigner` (`max_values`: Some(1), `max_size`: Some(137), added: 632, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::Blocked` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::Blocked` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::CallRateLimits` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::CallRateLimits` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::CallFilterOverrides` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::CallFilterOverrides` (`max_values`: None, `max_size`: Some(20), added: 2495, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::OperationalCalls` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::OperationalCalls` (`max_values`: None, `max_size`: Some(19), added: 2494, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::Sponsorships` (r:3 w:1)
+    /// Proof: `EvmAccountMapping::Sponsorships` (`max_values`: None, `max_size`: Some(89), added: 2564, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::SignerSponsorships` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::SignerSponsorships` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::FreeCallsUsed` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::FreeCallsUsed` (`max_values`: None, `max_size`: Some(52), added: 2527, mode: `MaxEncodedLen`)
+    /// Storage: `Balances::Holds` (r:1 w:1)
+    /// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::Receipts` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::Receipts` (`max_values`: None, `max_size`: Some(92), added: 2567, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::ReceiptExpiries` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::ReceiptExpiries` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
     fn meta_call() -> Weight {
         // Proof Size summary in bytes:
         //   Measured:  `176`
         //   Estimated: `3593`
         // Minimum execution time: 23_000_000 picoseconds.
         Weight::from_parts(24_000_000, 3593)
+            .saturating_add(RocksDbWeight::get().reads(16_u64))
+            .saturating_add(RocksDbWeight::get().writes(12_u64))
+    }
+    /// Storage: `EvmAccountMapping::Blocked` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Blocked` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+    fn block_address() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `6`
+        //   Estimated: `3501`
+        // Minimum execution time: 9_000_000 picoseconds.
+        Weight::from_parts(10_000_000, 3501)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    /// Storage: `EvmAccountMapping::Blocked` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Blocked` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+    fn unblock_address() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `42`
+        //   Estimated: `3501`
+        // Minimum execution time: 10_000_000 picoseconds.
+        Weight::from_parts(11_000_000, 3501)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    /// Storage: `System::Account` (r:2 w:2)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::AccountNonce` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::AccountNonce` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+    /// Storage: `TransactionPayment::NextFeeMultiplier` (r:1 w:0)
+    /// Proof: `TransactionPayment::NextFeeMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::EvmAddresses` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::EvmAddresses` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::MappedAccounts` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::MappedAccounts` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::PreDispatchedSigner` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::PreDispatchedSigner` (`max_values`: Some(1), `max_size`: Some(137), added: 632, mode: `MaxEncodedLen`)
+    fn meta_transfer() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `279`
+        //   Estimated: `6196`
+        // Minimum execution time: 58_000_000 picoseconds.
+        Weight::from_parts(60_000_000, 6196)
+            .saturating_add(RocksDbWeight::get().reads(6_u64))
+            .saturating_add(RocksDbWeight::get().writes(6_u64))
+    }
+    /// Storage: `EvmAccountMapping::Recoveries` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Recoveries` (`max_values`: None, `max_size`: Some(84), added: 2559, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::EvmAddresses` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::EvmAddresses` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    fn initiate_recovery() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `115`
+        //   Estimated: `3549`
+        // Minimum execution time: 14_000_000 picoseconds.
+        Weight::from_parts(15_000_000, 3549)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    /// Storage: `EvmAccountMapping::Recoveries` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Recoveries` (`max_values`: None, `max_size`: Some(84), added: 2559, mode: `MaxEncodedLen`)
+    fn veto_recovery() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `90`
+        //   Estimated: `3549`
+        // Minimum execution time: 11_000_000 picoseconds.
+        Weight::from_parts(12_000_000, 3549)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    /// Storage: `EvmAccountMapping::Recoveries` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Recoveries` (`max_values`: None, `max_size`: Some(84), added: 2559, mode: `MaxEncodedLen`)
+    /// Storage: `System::Account` (r:2 w:2)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::EvmAddresses` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::EvmAddresses` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::MappedAccounts` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::MappedAccounts` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::Controllers` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::Controllers` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::ControllerRotations` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::ControllerRotations` (`max_values`: None, `max_size`: Some(76), added: 2551, mode: `MaxEncodedLen`)
+    fn execute_recovery() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `354`
+        //   Estimated: `6196`
+        // Minimum execution time: 60_000_000 picoseconds.
+        Weight::from_parts(62_000_000, 6196)
+            .saturating_add(RocksDbWeight::get().reads(4_u64))
+            .saturating_add(RocksDbWeight::get().writes(7_u64))
+    }
+    /// Storage: `EvmAccountMapping::AccountNonce` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::AccountNonce` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::Blocked` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::Blocked` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::Links` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Links` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::ReverseLinks` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::ReverseLinks` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+    /// Storage: `Balances::Holds` (r:1 w:1)
+    /// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+    /// Storage: `System::Account` (r:1 w:1)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    fn link_accounts() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `6`
+        //   Estimated: `3545`
+        // Minimum execution time: 68_000_000 picoseconds.
+        Weight::from_parts(70_000_000, 3545)
+            .saturating_add(RocksDbWeight::get().reads(6_u64))
+            .saturating_add(RocksDbWeight::get().writes(5_u64))
+    }
+    /// Storage: `EvmAccountMapping::Links` (r:2 w:1)
+    /// Proof: `EvmAccountMapping::Links` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::ReverseLinks` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::ReverseLinks` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+    /// Storage: `Balances::Holds` (r:1 w:1)
+    /// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+    /// Storage: `System::Account` (r:1 w:1)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    fn unlink_accounts() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `176`
+        //   Estimated: `6100`
+        // Minimum execution time: 17_000_000 picoseconds.
+        Weight::from_parts(18_000_000, 6100)
+            .saturating_add(RocksDbWeight::get().reads(5_u64))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
+    }
+    /// Storage: `EvmAccountMapping::Links` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::Links` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+    /// Storage: `System::Account` (r:1 w:1)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::AccountNonce` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::AccountNonce` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+    /// Storage: `TransactionPayment::NextFeeMultiplier` (r:1 w:0)
+    /// Proof: `TransactionPayment::NextFeeMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::EvmAddresses` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::EvmAddresses` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::MappedAccounts` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::MappedAccounts` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::PreDispatchedSigner` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::PreDispatchedSigner` (`max_values`: Some(1), `max_size`: Some(137), added: 632, mode: `MaxEncodedLen`)
+    fn meta_call_as_linked() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `254`
+        //   Estimated: `3593`
+        // Minimum execution time: 27_000_000 picoseconds.
+        Weight::from_parts(28_000_000, 3593)
+            .saturating_add(RocksDbWeight::get().reads(6_u64))
+            .saturating_add(RocksDbWeight::get().writes(5_u64))
+    }
+    /// Storage: `EvmAccountMapping::Grants` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Grants` (`max_values`: None, `max_size`: Some(109), added: 2584, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::GrantExpiries` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::GrantExpiries` (`max_values`: None, `max_size`: Some(93), added: 2568, mode: `MaxEncodedLen`)
+    /// Storage: `Balances::Holds` (r:1 w:1)
+    /// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+    /// Storage: `System::Account` (r:1 w:1)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    fn grant() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `6`
+        //   Estimated: `3574`
+        // Minimum execution time: 13_000_000 picoseconds.
+        Weight::from_parts(14_000_000, 3574)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
+    }
+    /// Storage: `EvmAccountMapping::Grants` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Grants` (`max_values`: None, `max_size`: Some(109), added: 2584, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::GrantExpiries` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::GrantExpiries` (`max_values`: None, `max_size`: Some(93), added: 2568, mode: `MaxEncodedLen`)
+    /// Storage: `Balances::Holds` (r:1 w:1)
+    /// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+    /// Storage: `System::Account` (r:1 w:1)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    fn revoke() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `171`
+        //   Estimated: `3574`
+        // Minimum execution time: 14_000_000 picoseconds.
+        Weight::from_parts(15_000_000, 3574)
             .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
+    }
+    /// Storage: `EvmAccountMapping::CallFilterOverrides` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::CallFilterOverrides` (`max_values`: None, `max_size`: Some(20), added: 2495, mode: `MaxEncodedLen`)
+    fn set_call_filter_rule() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `0`
+        //   Estimated: `0`
+        // Minimum execution time: 8_000_000 picoseconds.
+        Weight::from_parts(9_000_000, 0)
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    /// Storage: `EvmAccountMapping::EvmAddresses` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::EvmAddresses` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `System::Account` (r:2 w:2)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::MappedAccounts` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::MappedAccounts` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    fn fund_mapped_account() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `103`
+        //   Estimated: `6196`
+        // Minimum execution time: 61_000_000 picoseconds.
+        Weight::from_parts(63_000_000, 6196)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
+    }
+    /// Storage: `System::Account` (r:1 w:0)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::Links` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::Links` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::AccountNonce` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::AccountNonce` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+    fn purge_nonce() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `6`
+        //   Estimated: `3593`
+        // Minimum execution time: 12_000_000 picoseconds.
+        Weight::from_parts(13_000_000, 3593)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    /// Storage: `EvmAccountMapping::PermitNonces` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::PermitNonces` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::Blocked` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::Blocked` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+    fn permit() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `6`
+        //   Estimated: `3521`
+        // Minimum execution time: 55_000_000 picoseconds.
+        Weight::from_parts(56_000_000, 3521)
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    /// Storage: `EvmAccountMapping::AccountNonce` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::AccountNonce` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::Controllers` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::Controllers` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::Blocked` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::Blocked` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::ControllerRotations` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::ControllerRotations` (`max_values`: None, `max_size`: Some(76), added: 2551, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::MappedAccounts` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::MappedAccounts` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    fn request_controller_rotation() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `250`
+        //   Estimated: `3541`
+        // Minimum execution time: 58_000_000 picoseconds.
+        Weight::from_parts(59_000_000, 3541)
+            .saturating_add(RocksDbWeight::get().reads(5_u64))
             .saturating_add(RocksDbWeight::get().writes(2_u64))
     }
+    /// Storage: `EvmAccountMapping::ControllerRotations` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::ControllerRotations` (`max_values`: None, `max_size`: Some(76), added: 2551, mode: `MaxEncodedLen`)
+    fn cancel_controller_rotation() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `6`
+        //   Estimated: `3541`
+        // Minimum execution time: 11_000_000 picoseconds.
+        Weight::from_parts(12_000_000, 3541)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    /// Storage: `EvmAccountMapping::ControllerRotations` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::ControllerRotations` (`max_values`: None, `max_size`: Some(76), added: 2551, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::MappedAccounts` (r:1 w:2)
+    /// Proof: `EvmAccountMapping::MappedAccounts` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::EvmAddresses` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::EvmAddresses` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::Controllers` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::Controllers` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    fn execute_controller_rotation() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `180`
+        //   Estimated: `3541`
+        // Minimum execution time: 19_000_000 picoseconds.
+        Weight::from_parts(20_000_000, 3541)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(5_u64))
+    }
+    /// Storage: `EvmAccountMapping::OperationalCalls` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::OperationalCalls` (`max_values`: None, `max_size`: Some(19), added: 2494, mode: `MaxEncodedLen`)
+    fn set_operational_call() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `0`
+        //   Estimated: `0`
+        // Minimum execution time: 9_000_000 picoseconds.
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    /// Storage: `EvmAccountMapping::OperationalCalls` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::OperationalCalls` (`max_values`: None, `max_size`: Some(19), added: 2494, mode: `MaxEncodedLen`)
+    /// Storage: `System::Account` (r:1 w:1)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::AccountNonce` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::AccountNonce` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+    /// Storage: `TransactionPayment::NextFeeMultiplier` (r:1 w:0)
+    /// Proof: `TransactionPayment::NextFeeMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::EvmAddresses` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::EvmAddresses` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::MappedAccounts` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::MappedAccounts` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::PreDispatchedSigner` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::PreDispatchedSigner` (`max_values`: Some(1), `max_size`: Some(137), added: 632, mode: `MaxEncodedLen`)
+    fn meta_call_operational() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `176`
+        //   Estimated: `3593`
+        // Minimum execution time: 26_000_000 picoseconds.
+        Weight::from_parts(27_000_000, 3593)
+            .saturating_add(RocksDbWeight::get().reads(6_u64))
+            .saturating_add(RocksDbWeight::get().writes(5_u64))
+    }
+    /// Storage: `EvmAccountMapping::Sponsorships` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Sponsorships` (`max_values`: None, `max_size`: Some(89), added: 2564, mode: `MaxEncodedLen`)
+    /// Storage: `Balances::Holds` (r:2 w:2)
+    /// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+    /// Storage: `System::Account` (r:2 w:2)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    fn sponsor() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `310`
+        //   Estimated: `6196`
+        // Minimum execution time: 30_000_000 picoseconds.
+        Weight::from_parts(31_000_000, 6196)
+            .saturating_add(RocksDbWeight::get().reads(5_u64))
+            .saturating_add(RocksDbWeight::get().writes(5_u64))
+    }
+    /// Storage: `EvmAccountMapping::Sponsorships` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Sponsorships` (`max_values`: None, `max_size`: Some(89), added: 2564, mode: `MaxEncodedLen`)
+    /// Storage: `Balances::Holds` (r:1 w:1)
+    /// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+    /// Storage: `System::Account` (r:1 w:1)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    fn end_sponsorship() -> Weight {
+        // Proof Size summary in bytes:
+        //   Measured:  `230`
+        //   Estimated: `3593`
+        // Minimum execution time: 20_000_000 picoseconds.
+        Weight::from_parts(21_000_000, 3593)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+    }
+    /// PLACEHOLDER, not benchmarked yet: regenerate with the `meta_typed_call_unconvertible`
+    /// benchmark. The conversion failure path doesn't access the storage.
+    fn meta_typed_call_unconvertible() -> Weight {
+        Weight::from_parts(6_000_000, 0)
+    }
 }
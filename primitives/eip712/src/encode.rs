@@ -16,7 +16,7 @@
 // limitations under the License.
 
 /// Port from https://github.com/roberts-ivanovs/eth-encode-packed-rs
-use ethabi::ethereum_types::{Address, U256};
+use sp_core::{H160 as Address, U256};
 
 pub struct TakeLastXBytes(pub usize);
 
@@ -31,9 +31,33 @@ pub enum SolidityDataType<'a> {
 }
 
 pub mod abi {
-	use super::SolidityDataType;
+	use super::{Address, SolidityDataType, U256};
 	use sp_std::{vec, vec::Vec};
 
+	/// A static ABI token, the subset of `ethabi::Token` which the struct hashes are built from.
+	pub enum Token<'a> {
+		/// `bytes1` to `bytes32`, right padded with zeros.
+		FixedBytes(&'a [u8]),
+		/// `uint8` to `uint256`.
+		Uint(U256),
+		/// `address`, left padded with zeros.
+		Address(Address),
+	}
+
+	/// ABI encode `tokens`, the same as `ethabi::encode` does for static tokens: each token takes
+	/// one 32-byte word.
+	pub fn encode(tokens: &[Token]) -> Vec<u8> {
+		let mut res = vec![0u8; tokens.len() * 32];
+		for (token, word) in tokens.iter().zip(res.chunks_exact_mut(32)) {
+			match token {
+				Token::FixedBytes(b) => word[..b.len()].copy_from_slice(b),
+				Token::Uint(n) => n.to_big_endian(word),
+				Token::Address(a) => word[12..].copy_from_slice(a.as_bytes()),
+			}
+		}
+		res
+	}
+
 	/// Pack a single `SolidityDataType` into bytes
 	#[allow(clippy::needless_lifetimes)]
 	fn pack<'a>(data_type: &'a SolidityDataType) -> Vec<u8> {
@@ -0,0 +1,180 @@
+// This file is part of EVM Account Mapping Pallet.
+
+// Copyright (C) HashForest Technology Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A `no_std` implementation of [EIP-712](https://eips.ethereum.org/EIPS/eip-712) hashing of typed
+//! structured data.
+//!
+//! - [`EIP712Domain`] computes the domain separator.
+//! - [`StructBuilder`] hashes a struct of a known type field by field.
+//! - [`typed_data`] encodes arbitrary types with nested structs and arrays.
+//! - [`typed_data_hash`] combines a domain separator and a struct hash into the hash a wallet
+//!   signs.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod encode;
+pub mod typed_data;
+
+#[cfg(test)]
+mod tests;
+
+pub use typed_data::typed_data_hash;
+
+use sp_core::{H160 as Address, U256};
+use sp_io::hashing::keccak_256;
+use sp_std::vec::Vec;
+
+/// Pre-computed value of the following statement:
+///
+/// `keccak_256("EIP712Domain(string name,string version,uint256 chainId,address
+/// verifyingContract)")`
+pub const EIP712_DOMAIN_TYPE_HASH: [u8; 32] = [
+	139, 115, 195, 198, 155, 184, 254, 61, 81, 46, 204, 76, 247, 89, 204, 121, 35, 159, 123, 23,
+	155, 15, 250, 202, 169, 167, 93, 82, 43, 57, 64, 15,
+];
+
+/// Pre-computed value of the following statement:
+///
+/// `keccak_256("EIP712Domain(string name,string version,uint256 chainId,address
+/// verifyingContract,bytes32 salt)")`
+pub const EIP712_DOMAIN_TYPE_HASH_WITH_SALT: [u8; 32] = [
+	216, 124, 214, 239, 121, 212, 226, 185, 94, 21, 206, 138, 191, 115, 45, 181, 30, 199, 113, 241,
+	202, 46, 220, 207, 34, 164, 108, 114, 154, 197, 100, 114,
+];
+
+/// Eip712 Domain attributes used in determining the domain separator;
+/// Unused fields are left out of the struct type.
+#[derive(Debug, Default, Clone)]
+pub struct EIP712Domain {
+	///  The user readable name of signing domain, i.e. the name of the DApp or the protocol.
+	pub name: Vec<u8>,
+
+	/// The current major version of the signing domain. Signatures from different versions are not
+	/// compatible.
+	pub version: Vec<u8>,
+
+	/// The EIP-155 chain id. The user-agent should refuse signing if it does not match the
+	/// currently active chain.
+	pub chain_id: U256,
+
+	/// The address of the contract that will verify the signature.
+	pub verifying_contract: Address,
+
+	/// A disambiguating salt for the protocol. This can be used as a domain separator of last
+	/// resort.
+	pub salt: Option<[u8; 32]>,
+}
+
+impl EIP712Domain {
+	/// Compute the domain separator, the struct hash of the domain.
+	pub fn separator(&self) -> [u8; 32] {
+		let domain_type_hash = if self.salt.is_some() {
+			EIP712_DOMAIN_TYPE_HASH_WITH_SALT
+		} else {
+			EIP712_DOMAIN_TYPE_HASH
+		};
+
+		let builder = StructBuilder::new(domain_type_hash)
+			.string(&self.name)
+			.string(&self.version)
+			.uint(self.chain_id)
+			.address(self.verifying_contract);
+		// Add the salt to the struct to be hashed if it exists;
+		match &self.salt {
+			Some(salt) => builder.fixed_bytes(salt),
+			None => builder,
+		}
+		.finish()
+	}
+}
+
+/// Hash a struct of a known type, appending its fields in the order of the type definition.
+///
+/// ```
+/// # use substrate_eip712::StructBuilder;
+/// let struct_hash = StructBuilder::from_type(b"Mail(string contents,uint64 nonce)")
+/// 	.string(b"Hello, Bob!")
+/// 	.uint(1u64.into())
+/// 	.finish();
+/// ```
+#[derive(Debug, Clone)]
+pub struct StructBuilder {
+	encoded: Vec<u8>,
+}
+
+impl StructBuilder {
+	/// Start hashing a struct with the hash of its encoded type.
+	pub fn new(type_hash: [u8; 32]) -> Self {
+		let mut encoded = Vec::with_capacity(32 * 8);
+		encoded.extend_from_slice(&type_hash);
+		StructBuilder { encoded }
+	}
+
+	/// Start hashing a struct of the encoded type, e.g. `Mail(string contents,uint64 nonce)`.
+	pub fn from_type(encoded_type: &[u8]) -> Self {
+		Self::new(keccak_256(encoded_type))
+	}
+
+	/// Append an already encoded 32-byte field, e.g. the hash of a nested struct or an array.
+	pub fn word(mut self, word: [u8; 32]) -> Self {
+		self.encoded.extend_from_slice(&word);
+		self
+	}
+
+	/// Append a `string` field.
+	pub fn string(self, value: &[u8]) -> Self {
+		self.word(keccak_256(value))
+	}
+
+	/// Append a `bytes` field.
+	pub fn bytes(self, value: &[u8]) -> Self {
+		self.word(keccak_256(value))
+	}
+
+	/// Append a `bytes1` to `bytes32` field, `value` must not be longer than 32 bytes.
+	pub fn fixed_bytes(self, value: &[u8]) -> Self {
+		let mut word = [0u8; 32];
+		word[..value.len()].copy_from_slice(value);
+		self.word(word)
+	}
+
+	/// Append a `uint8` to `uint256` field.
+	pub fn uint(self, value: U256) -> Self {
+		let mut word = [0u8; 32];
+		value.to_big_endian(&mut word);
+		self.word(word)
+	}
+
+	/// Append an `address` field.
+	pub fn address(self, value: Address) -> Self {
+		let mut word = [0u8; 32];
+		word[12..].copy_from_slice(value.as_bytes());
+		self.word(word)
+	}
+
+	/// Append a `bool` field.
+	pub fn bool(self, value: bool) -> Self {
+		let mut word = [0u8; 32];
+		word[31] = value as u8;
+		self.word(word)
+	}
+
+	/// The struct hash, `hashStruct` of EIP-712.
+	pub fn finish(self) -> [u8; 32] {
+		keccak_256(&self.encoded)
+	}
+}
@@ -0,0 +1,246 @@
+// This file is part of EVM Account Mapping Pallet.
+
+// Copyright (C) HashForest Technology Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	typed_data::{encode_type, hash_struct, type_hash, Error, Member, Types, Value},
+	typed_data_hash, EIP712Domain, StructBuilder,
+};
+use sp_core::{H160, U256};
+
+/// The domain of the EIP-712 example, which the vectors below are signed within.
+fn ether_mail_domain_separator() -> [u8; 32] {
+	EIP712Domain {
+		name: b"Ether Mail".to_vec(),
+		version: b"1".to_vec(),
+		chain_id: U256::from(1u64),
+		verifying_contract: H160::repeat_byte(0xcc),
+		salt: None,
+	}
+	.separator()
+}
+
+/// An `address` value from its hex representation.
+fn address(hex_address: &str) -> Value {
+	Value::Address(H160::from_slice(&hex::decode(hex_address).expect("Decodable")))
+}
+
+#[test]
+fn eip712_encodes_nested_structs() {
+	let types = Types::from([
+		("Person", vec![Member::new("name", "string"), Member::new("wallet", "address")]),
+		(
+			"Mail",
+			vec![
+				Member::new("from", "Person"),
+				Member::new("to", "Person"),
+				Member::new("contents", "string"),
+			],
+		),
+	]);
+	let mail = vec![
+		Value::Struct(vec![
+			Value::String(b"Cow".to_vec()),
+			address("cd2a3d9f938e13cd947ec05abc7fe734df8dd826"),
+		]),
+		Value::Struct(vec![
+			Value::String(b"Bob".to_vec()),
+			address("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"),
+		]),
+		Value::String(b"Hello, Bob!".to_vec()),
+	];
+
+	assert_eq!(
+		encode_type("Mail", &types),
+		Ok(b"Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+			.to_vec())
+	);
+	assert_eq!(
+		hex::encode(type_hash("Mail", &types).unwrap()),
+		"a0cedeb2dc280ba39b857546d74f5549c3a1d7bdc2dd96bf881f76108e23dac2"
+	);
+	let struct_hash = hash_struct("Mail", &mail, &types).unwrap();
+	assert_eq!(
+		hex::encode(struct_hash),
+		"c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+	);
+	assert_eq!(
+		hex::encode(ether_mail_domain_separator()),
+		"f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+	);
+	assert_eq!(
+		hex::encode(typed_data_hash(&ether_mail_domain_separator(), &struct_hash)),
+		"be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+	);
+
+	// The fields must match the members of the type
+	assert_eq!(hash_struct("Mail", &mail[..2], &types), Err(Error::MemberCountMismatch));
+	assert_eq!(hash_struct("Letter", &mail, &types), Err(Error::UnknownType));
+}
+
+#[test]
+fn eip712_encodes_arrays() {
+	let types = Types::from([
+		("Person", vec![Member::new("name", "string"), Member::new("wallets", "address[]")]),
+		(
+			"Mail",
+			vec![
+				Member::new("from", "Person"),
+				Member::new("to", "Person[]"),
+				Member::new("contents", "string"),
+			],
+		),
+		("Group", vec![Member::new("name", "string"), Member::new("members", "Person[]")]),
+	]);
+	let mail = vec![
+		Value::Struct(vec![
+			Value::String(b"Cow".to_vec()),
+			Value::Array(vec![
+				address("cd2a3d9f938e13cd947ec05abc7fe734df8dd826"),
+				address("deadbeefdeadbeefdeadbeefdeadbeefdeadbeef"),
+			]),
+		]),
+		Value::Array(vec![Value::Struct(vec![
+			Value::String(b"Bob".to_vec()),
+			Value::Array(vec![
+				address("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"),
+				address("b0bdabea57b0bdabea57b0bdabea57b0bdabea57"),
+				address("b0b0b0b0b0b0b000000000000000000000000000"),
+			]),
+		])]),
+		Value::String(b"Hello, Bob!".to_vec()),
+	];
+
+	assert_eq!(
+		encode_type("Mail", &types),
+		Ok(b"Mail(Person from,Person[] to,string contents)Person(string name,address[] wallets)"
+			.to_vec())
+	);
+	assert_eq!(
+		hex::encode(type_hash("Person", &types).unwrap()),
+		"fabfe1ed996349fc6027709802be19d047da1aa5d6894ff5f6486d92db2e6860"
+	);
+	let struct_hash = hash_struct("Mail", &mail, &types).unwrap();
+	assert_eq!(
+		hex::encode(struct_hash),
+		"eb4221181ff3f1a83ea7313993ca9218496e424604ba9492bb4052c03d5c3df8"
+	);
+	assert_eq!(
+		hex::encode(typed_data_hash(&ether_mail_domain_separator(), &struct_hash)),
+		"a85c2e2b118698e88db68a8105b794a8cc7cec074e89ef991cb4f5f533819cc2"
+	);
+}
+
+#[test]
+fn eip712_encodes_atomic_types() {
+	let types = Types::from([
+		(
+			"Batch",
+			vec![
+				Member::new("calls", "Call[2]"),
+				Member::new("flags", "bool[]"),
+				Member::new("salt", "bytes32"),
+				Member::new("tag", "bytes4"),
+				Member::new("delta", "int256"),
+				Member::new("level", "uint8"),
+			],
+		),
+		("Call", vec![Member::new("target", "address"), Member::new("data", "bytes")]),
+	]);
+	let calls = Value::Array(vec![
+		Value::Struct(vec![
+			address("1111111111111111111111111111111111111111"),
+			Value::Bytes(vec![0xde, 0xad, 0xbe, 0xef]),
+		]),
+		Value::Struct(vec![
+			address("2222222222222222222222222222222222222222"),
+			Value::Bytes(vec![]),
+		]),
+	]);
+	let mut batch = vec![
+		calls.clone(),
+		Value::Array(vec![Value::Bool(true), Value::Bool(false), Value::Bool(true)]),
+		Value::FixedBytes(vec![0xab; 32]),
+		Value::FixedBytes(vec![0x12, 0x34, 0x56, 0x78]),
+		// -1 in two's complement
+		Value::Int(U256::MAX),
+		Value::Uint(255.into()),
+	];
+
+	assert_eq!(
+		encode_type("Batch", &types),
+		Ok(b"Batch(Call[2] calls,bool[] flags,bytes32 salt,bytes4 tag,int256 delta,uint8 level)Call(address target,bytes data)".to_vec())
+	);
+	assert_eq!(
+		hex::encode(type_hash("Batch", &types).unwrap()),
+		"22865f19700c1d01b69c597bf01351a6d44aa708bace8b1ffd07b220f42a4d6d"
+	);
+	assert_eq!(
+		hex::encode(hash_struct("Batch", &batch, &types).unwrap()),
+		"1712c2a661b8a86b7996420e9f4e6ee5c246782ffca730cb10dba0a4e0d171e1"
+	);
+
+	// A fixed-size array must have the declared length
+	batch[0] = Value::Array(vec![]);
+	assert_eq!(hash_struct("Batch", &batch, &types), Err(Error::ArrayLengthMismatch));
+	batch[0] = calls;
+	// `bytes4` doesn't take 3 bytes
+	batch[3] = Value::FixedBytes(vec![0x12, 0x34, 0x56]);
+	assert_eq!(hash_struct("Batch", &batch, &types), Err(Error::TypeMismatch));
+}
+
+#[test]
+fn struct_builder_hashes_known_types() {
+	let person = |name: &[u8], wallet: &str| {
+		StructBuilder::from_type(b"Person(string name,address wallet)")
+			.string(name)
+			.address(H160::from_slice(&hex::decode(wallet).expect("Decodable")))
+			.finish()
+	};
+	let struct_hash = StructBuilder::from_type(
+		b"Mail(Person from,Person to,string contents)Person(string name,address wallet)",
+	)
+	.word(person(b"Cow", "cd2a3d9f938e13cd947ec05abc7fe734df8dd826"))
+	.word(person(b"Bob", "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"))
+	.string(b"Hello, Bob!")
+	.finish();
+
+	assert_eq!(
+		hex::encode(struct_hash),
+		"c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+	);
+}
+
+#[test]
+fn abi_encodes_static_tokens() {
+	use crate::encode::abi::{encode, Token};
+
+	let wallet = H160::repeat_byte(0xcc);
+	let encoded = encode(&[
+		Token::FixedBytes(&[0xab; 4]),
+		Token::Uint(U256::from(0x0102u64)),
+		Token::Address(wallet),
+	]);
+
+	assert_eq!(
+		hex::encode(encoded),
+		concat!(
+			"abababab00000000000000000000000000000000000000000000000000000000",
+			"0000000000000000000000000000000000000000000000000000000000000102",
+			"000000000000000000000000cccccccccccccccccccccccccccccccccccccccc",
+		)
+	);
+}
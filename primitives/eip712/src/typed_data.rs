@@ -0,0 +1,211 @@
+// This file is part of EVM Account Mapping Pallet.
+
+// Copyright (C) HashForest Technology Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! EIP-712 typed data: struct types with nested structs and arrays, and the values of them.
+
+use sp_core::{H160 as Address, U256};
+use sp_io::hashing::keccak_256;
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+/// Errors of encoding EIP-712 typed data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+	/// A referenced struct type is not defined.
+	UnknownType,
+	/// A value doesn't match the type of its member.
+	TypeMismatch,
+	/// A struct value doesn't have the same number of fields as its type has members.
+	MemberCountMismatch,
+	/// A fixed-size array value doesn't have the declared length.
+	ArrayLengthMismatch,
+}
+
+/// A member of an EIP-712 struct type, e.g. `string name`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Member<'a> {
+	/// The name of the member.
+	pub name: &'a str,
+	/// The type of the member, e.g. `uint256`, `Person` or `address[]`.
+	pub ty: &'a str,
+}
+
+impl<'a> Member<'a> {
+	/// A member called `name` of type `ty`.
+	pub const fn new(name: &'a str, ty: &'a str) -> Self {
+		Member { name, ty }
+	}
+}
+
+/// The struct types referenced by typed data, keyed by the name of the type.
+pub type Types<'a> = BTreeMap<&'a str, Vec<Member<'a>>>;
+
+/// A value of typed data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+	/// A value of `uint8` to `uint256`.
+	Uint(U256),
+	/// A value of `int8` to `int256` in two's complement.
+	Int(U256),
+	/// A value of `bool`.
+	Bool(bool),
+	/// A value of `address`.
+	Address(Address),
+	/// A value of `bytes1` to `bytes32`.
+	FixedBytes(Vec<u8>),
+	/// A value of `bytes`.
+	Bytes(Vec<u8>),
+	/// A value of `string`, encoded as UTF-8.
+	String(Vec<u8>),
+	/// A value of a dynamic `T[]` or a fixed-size `T[n]` array.
+	Array(Vec<Value>),
+	/// A value of a struct type, one field per member in the order of the type definition.
+	Struct(Vec<Value>),
+}
+
+/// Split `T[]` or `T[n]` into `T` and the length of the array, if `ty` is an array type.
+fn split_array_type(ty: &str) -> Option<(&str, Option<usize>)> {
+	let inner = ty.strip_suffix(']')?;
+	let open = inner.rfind('[')?;
+	let length = &inner[open + 1..];
+	let length = if length.is_empty() { None } else { Some(length.parse().ok()?) };
+	Some((&inner[..open], length))
+}
+
+/// The struct type a member type refers to, removing any array suffix.
+fn base_type(mut ty: &str) -> &str {
+	while let Some((inner, _)) = split_array_type(ty) {
+		ty = inner;
+	}
+	ty
+}
+
+/// Collect `primary` and every struct type it references into `found`.
+fn collect_dependencies<'a>(
+	primary: &'a str,
+	types: &Types<'a>,
+	found: &mut Vec<&'a str>,
+) -> Result<(), Error> {
+	if found.contains(&primary) {
+		return Ok(())
+	}
+	let members = types.get(primary).ok_or(Error::UnknownType)?;
+	found.push(primary);
+	for member in members {
+		let ty = base_type(member.ty);
+		if types.contains_key(ty) {
+			collect_dependencies(ty, types, found)?;
+		}
+	}
+	Ok(())
+}
+
+/// `encodeType` of EIP-712: the primary type followed by the referenced struct types, sorted by
+/// name.
+pub fn encode_type(primary: &str, types: &Types) -> Result<Vec<u8>, Error> {
+	let mut dependencies = Vec::new();
+	collect_dependencies(primary, types, &mut dependencies)?;
+	dependencies[1..].sort_unstable();
+
+	let mut encoded = Vec::new();
+	for name in dependencies {
+		encoded.extend_from_slice(name.as_bytes());
+		encoded.push(b'(');
+		for (i, member) in types[name].iter().enumerate() {
+			if i > 0 {
+				encoded.push(b',');
+			}
+			encoded.extend_from_slice(member.ty.as_bytes());
+			encoded.push(b' ');
+			encoded.extend_from_slice(member.name.as_bytes());
+		}
+		encoded.push(b')');
+	}
+	Ok(encoded)
+}
+
+/// `typeHash` of EIP-712.
+pub fn type_hash(primary: &str, types: &Types) -> Result<[u8; 32], Error> {
+	Ok(keccak_256(&encode_type(primary, types)?))
+}
+
+/// Encode `value` of type `ty` into its 32-byte EIP-712 representation.
+fn encode_value(ty: &str, value: &Value, types: &Types) -> Result<[u8; 32], Error> {
+	if let Some((inner, length)) = split_array_type(ty) {
+		let Value::Array(items) = value else { return Err(Error::TypeMismatch) };
+		if matches!(length, Some(length) if length != items.len()) {
+			return Err(Error::ArrayLengthMismatch)
+		}
+		let mut encoded = Vec::with_capacity(items.len() * 32);
+		for item in items {
+			encoded.extend_from_slice(&encode_value(inner, item, types)?);
+		}
+		return Ok(keccak_256(&encoded))
+	}
+	if types.contains_key(ty) {
+		let Value::Struct(fields) = value else { return Err(Error::TypeMismatch) };
+		return hash_struct(ty, fields, types)
+	}
+
+	let mut word = [0u8; 32];
+	match value {
+		Value::Uint(n) if ty.starts_with("uint") => n.to_big_endian(&mut word),
+		Value::Int(n) if ty.starts_with("int") => n.to_big_endian(&mut word),
+		Value::Bool(b) if ty == "bool" => word[31] = *b as u8,
+		Value::Address(address) if ty == "address" =>
+			word[12..].copy_from_slice(address.as_bytes()),
+		Value::String(s) if ty == "string" => word = keccak_256(s),
+		Value::Bytes(b) if ty == "bytes" => word = keccak_256(b),
+		Value::FixedBytes(b) if ty.starts_with("bytes") => {
+			let length = ty[5..].parse::<usize>().map_err(|_| Error::TypeMismatch)?;
+			if b.len() != length || length > 32 {
+				return Err(Error::TypeMismatch)
+			}
+			word[..length].copy_from_slice(b);
+		},
+		_ => return Err(Error::TypeMismatch),
+	}
+	Ok(word)
+}
+
+/// `encodeData` of EIP-712: the type hash followed by the encoded fields.
+pub fn encode_data(primary: &str, fields: &[Value], types: &Types) -> Result<Vec<u8>, Error> {
+	let members = types.get(primary).ok_or(Error::UnknownType)?;
+	if members.len() != fields.len() {
+		return Err(Error::MemberCountMismatch)
+	}
+
+	let mut encoded = Vec::with_capacity((fields.len() + 1) * 32);
+	encoded.extend_from_slice(&type_hash(primary, types)?);
+	for (member, field) in members.iter().zip(fields) {
+		encoded.extend_from_slice(&encode_value(member.ty, field, types)?);
+	}
+	Ok(encoded)
+}
+
+/// `hashStruct` of EIP-712.
+pub fn hash_struct(primary: &str, fields: &[Value], types: &Types) -> Result<[u8; 32], Error> {
+	Ok(keccak_256(&encode_data(primary, fields, types)?))
+}
+
+/// The hash a wallet signs for a struct hash within the domain of `domain_separator`.
+pub fn typed_data_hash(domain_separator: &[u8; 32], struct_hash: &[u8; 32]) -> [u8; 32] {
+	let mut encoded = Vec::with_capacity(66);
+	encoded.extend_from_slice(b"\x19\x01");
+	encoded.extend_from_slice(domain_separator);
+	encoded.extend_from_slice(struct_hash);
+	keccak_256(&encoded)
+}
@@ -0,0 +1,150 @@
+// This file is part of EVM Account Mapping Pallet.
+
+// Copyright (C) HashForest Technology Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Building, validating and submitting `meta_call` extrinsics.
+
+use crate::{metrics::Metrics, queue::Queue};
+use anyhow::{anyhow, Result};
+use codec::Encode;
+use pallet_evm_account_mapping::{MetaSignature, Nonce, PayloadVersion};
+use sp_core::crypto::AccountId32;
+use sp_runtime::transaction_validity::{
+	TransactionSource, TransactionValidity, TransactionValidityError,
+};
+use std::{sync::Arc, time::Duration};
+use subxt::{tx::SubmittableExtrinsic, utils::H256, Metadata, OnlineClient, PolkadotConfig};
+
+pub type Client = OnlineClient<PolkadotConfig>;
+
+const PALLET: &str = "EvmAccountMapping";
+
+/// The version byte of an unsigned extrinsic.
+const UNSIGNED_EXTRINSIC_VERSION: u8 = 4;
+
+/// A `meta_call` signed by an EVM wallet.
+pub struct MetaCall {
+	pub who: AccountId32,
+	/// The SCALE encoded call to dispatch.
+	pub call_data: Vec<u8>,
+	pub nonce: Nonce,
+	pub signature: MetaSignature,
+	pub tip: Option<u128>,
+}
+
+impl MetaCall {
+	/// The tip is only signed since [`PayloadVersion::V1`], so a tipped payload is assumed to be
+	/// of that version.
+	pub fn version(&self) -> PayloadVersion {
+		if self.tip.is_some() {
+			PayloadVersion::V1
+		} else {
+			PayloadVersion::V0
+		}
+	}
+
+	/// The SCALE encoded unsigned `meta_call` extrinsic.
+	pub fn extrinsic(&self, metadata: &Metadata) -> Result<Vec<u8>> {
+		let pallet = metadata
+			.pallet_by_name(PALLET)
+			.ok_or_else(|| anyhow!("The runtime has no pallet {PALLET}"))?;
+		let call = pallet
+			.call_variant_by_name("meta_call")
+			.ok_or_else(|| anyhow!("The runtime has no call {PALLET}.meta_call"))?;
+
+		let mut extrinsic = vec![UNSIGNED_EXTRINSIC_VERSION, pallet.index(), call.index];
+		self.who.encode_to(&mut extrinsic);
+		// The inner call is a `Box<RuntimeCall>`, its encoding is the call data itself
+		extrinsic.extend_from_slice(&self.call_data);
+		self.nonce.encode_to(&mut extrinsic);
+		self.signature.encode_to(&mut extrinsic);
+		self.tip.encode_to(&mut extrinsic);
+		self.version().encode_to(&mut extrinsic);
+
+		// Extrinsics are length prefixed
+		Ok(extrinsic.encode())
+	}
+}
+
+/// Validate `extrinsic` against the latest finalized block as the transaction pool does, which
+/// runs `validate_unsigned` of the pallet.
+pub async fn validate(api: &Client, extrinsic: &[u8]) -> Result<TransactionValidity> {
+	let block_hash = api.blocks().at_latest().await?.hash();
+
+	let mut params = TransactionSource::External.encode();
+	params.extend_from_slice(extrinsic);
+	block_hash.encode_to(&mut params);
+
+	let validity = api
+		.runtime_api()
+		.at(block_hash)
+		.call_raw::<TransactionValidity>(
+			"TaggedTransactionQueue_validate_transaction",
+			Some(&params),
+		)
+		.await?;
+	Ok(validity)
+}
+
+/// Submit the queued extrinsics one by one, forever.
+///
+/// The extrinsics which became invalid while queued are dropped, the failed submissions are
+/// retried after `retry_delay`.
+pub async fn submit_queued(
+	api: Client,
+	queue: Arc<Queue>,
+	metrics: Arc<Metrics>,
+	retry_delay: Duration,
+) {
+	loop {
+		let entry = queue.front().await;
+		match submit(&api, &entry.extrinsic).await {
+			Ok(Some(hash)) => {
+				log::info!("Submitted #{} as {hash:?}", entry.id);
+				metrics.submitted.inc();
+			},
+			Ok(None) => {
+				log::warn!("Dropped #{}, it is no longer valid", entry.id);
+				metrics.dropped.inc();
+			},
+			Err(e) => {
+				log::warn!("Failed to submit #{}, retrying: {e:?}", entry.id);
+				metrics.failures.inc();
+				tokio::time::sleep(retry_delay).await;
+				continue
+			},
+		}
+
+		if let Err(e) = queue.remove(entry.id) {
+			log::error!("Failed to remove #{} from the queue: {e:?}", entry.id);
+		}
+		metrics.queue_length.set(queue.len() as i64);
+	}
+}
+
+/// Submit `extrinsic` to the transaction pool, returns `None` if it is no longer valid.
+async fn submit(api: &Client, extrinsic: &[u8]) -> Result<Option<H256>> {
+	match validate(api, extrinsic).await? {
+		Ok(_) => {},
+		Err(TransactionValidityError::Invalid(_)) => return Ok(None),
+		Err(TransactionValidityError::Unknown(e)) =>
+			return Err(anyhow!("The validity is unknown: {e:?}")),
+	}
+
+	let hash = SubmittableExtrinsic::from_bytes(api.clone(), extrinsic.to_vec())
+		.submit()
+		.await?;
+	Ok(Some(hash))
+}
@@ -0,0 +1,92 @@
+// This file is part of EVM Account Mapping Pallet.
+
+// Copyright (C) HashForest Technology Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! A relayer submitting `meta_call` extrinsics of the EVM Account Mapping pallet.
+//!
+//! Wallets POST signed payloads to `/relay`, which are validated against the latest finalized
+//! block the same way the transaction pool does, then queued on disk and submitted in order.
+//! Requests are rate limited per IP and per account, the metrics are served at `/metrics`.
+
+mod chain;
+mod metrics;
+mod queue;
+mod rate_limit;
+mod server;
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use std::{net::SocketAddr, path::PathBuf, sync::Arc, time::Duration};
+use subxt::{OnlineClient, PolkadotConfig};
+
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Cli {
+	/// The WebSocket endpoint of the node.
+	#[arg(long, default_value = "ws://127.0.0.1:9944")]
+	url: String,
+
+	/// The address the HTTP server listens on.
+	#[arg(long, default_value = "127.0.0.1:8080")]
+	listen: SocketAddr,
+
+	/// The directory the pending extrinsics are persisted in.
+	#[arg(long, default_value = "relayer-queue")]
+	queue_dir: PathBuf,
+
+	/// The maximum number of requests per IP within `--rate-limit-window`.
+	#[arg(long, default_value_t = 60)]
+	ip_rate_limit: u32,
+
+	/// The maximum number of requests per account within `--rate-limit-window`.
+	#[arg(long, default_value_t = 10)]
+	account_rate_limit: u32,
+
+	/// The rate limit window in seconds.
+	#[arg(long, default_value_t = 60)]
+	rate_limit_window: u64,
+
+	/// The delay in seconds before retrying a failed submission.
+	#[arg(long, default_value_t = 6)]
+	retry_delay: u64,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+	env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+	let cli = Cli::parse();
+
+	let api = OnlineClient::<PolkadotConfig>::from_url(&cli.url)
+		.await
+		.with_context(|| format!("Failed to connect to {}", cli.url))?;
+	let queue = Arc::new(
+		queue::Queue::open(&cli.queue_dir)
+			.with_context(|| format!("Failed to open the queue in {}", cli.queue_dir.display()))?,
+	);
+	let metrics = Arc::new(metrics::Metrics::new()?);
+	metrics.queue_length.set(queue.len() as i64);
+
+	let window = Duration::from_secs(cli.rate_limit_window);
+	let state = Arc::new(server::State {
+		api: api.clone(),
+		queue: queue.clone(),
+		metrics: metrics.clone(),
+		ip_limiter: rate_limit::RateLimiter::new(cli.ip_rate_limit, window),
+		account_limiter: rate_limit::RateLimiter::new(cli.account_rate_limit, window),
+	});
+
+	tokio::spawn(chain::submit_queued(api, queue, metrics, Duration::from_secs(cli.retry_delay)));
+	server::serve(cli.listen, state).await
+}
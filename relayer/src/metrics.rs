@@ -0,0 +1,69 @@
+// This file is part of EVM Account Mapping Pallet.
+
+// Copyright (C) HashForest Technology Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Prometheus metrics of the relayer.
+
+use prometheus::{Encoder, IntCounter, IntCounterVec, IntGauge, Opts, Registry, TextEncoder};
+
+pub struct Metrics {
+	registry: Registry,
+	/// The relay requests by response status.
+	pub requests: IntCounterVec,
+	/// The extrinsics accepted by the transaction pool.
+	pub submitted: IntCounter,
+	/// The queued extrinsics dropped because they became invalid.
+	pub dropped: IntCounter,
+	/// The failed submission attempts, which are retried.
+	pub failures: IntCounter,
+	/// The extrinsics waiting for submission.
+	pub queue_length: IntGauge,
+}
+
+impl Metrics {
+	pub fn new() -> prometheus::Result<Self> {
+		let registry = Registry::new_custom(Some("evm_account_mapping_relayer".into()), None)?;
+		let requests = IntCounterVec::new(
+			Opts::new("requests_total", "The relay requests by response status"),
+			&["status"],
+		)?;
+		let submitted =
+			IntCounter::new("submitted_total", "The extrinsics accepted by the transaction pool")?;
+		let dropped = IntCounter::new(
+			"dropped_total",
+			"The queued extrinsics dropped because they became invalid",
+		)?;
+		let failures = IntCounter::new(
+			"submission_failures_total",
+			"The failed submission attempts, which are retried",
+		)?;
+		let queue_length = IntGauge::new("queue_length", "The extrinsics waiting for submission")?;
+
+		registry.register(Box::new(requests.clone()))?;
+		registry.register(Box::new(submitted.clone()))?;
+		registry.register(Box::new(dropped.clone()))?;
+		registry.register(Box::new(failures.clone()))?;
+		registry.register(Box::new(queue_length.clone()))?;
+
+		Ok(Self { registry, requests, submitted, dropped, failures, queue_length })
+	}
+
+	/// The metrics in the Prometheus text format.
+	pub fn render(&self) -> prometheus::Result<Vec<u8>> {
+		let mut buffer = Vec::new();
+		TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+		Ok(buffer)
+	}
+}
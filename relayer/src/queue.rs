@@ -0,0 +1,120 @@
+// This file is part of EVM Account Mapping Pallet.
+
+// Copyright (C) HashForest Technology Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! A queue of the extrinsics pending submission, persisted on disk so that the accepted requests
+//! survive restarts.
+//!
+//! Every extrinsic is a `<id>.tx` file in the queue directory, the ids are increasing so that the
+//! extrinsics are submitted in the order they were accepted.
+
+use std::{
+	collections::VecDeque,
+	fs, io,
+	path::{Path, PathBuf},
+	sync::Mutex,
+};
+use tokio::sync::Notify;
+
+#[derive(Clone)]
+pub struct Entry {
+	pub id: u64,
+	/// The SCALE encoded extrinsic.
+	pub extrinsic: Vec<u8>,
+}
+
+pub struct Queue {
+	dir: PathBuf,
+	inner: Mutex<Inner>,
+	notify: Notify,
+}
+
+struct Inner {
+	pending: VecDeque<Entry>,
+	next_id: u64,
+}
+
+impl Queue {
+	/// Open the queue in `dir`, the extrinsics left by the previous run are loaded.
+	pub fn open(dir: &Path) -> io::Result<Self> {
+		fs::create_dir_all(dir)?;
+
+		let mut pending = Vec::new();
+		for file in fs::read_dir(dir)? {
+			let path = file?.path();
+			if path.extension().map_or(true, |extension| extension != "tx") {
+				continue
+			}
+			let Some(id) = path.file_stem().and_then(|stem| stem.to_str()?.parse().ok()) else {
+				continue
+			};
+			pending.push(Entry { id, extrinsic: fs::read(&path)? });
+		}
+		pending.sort_by_key(|entry| entry.id);
+		let next_id = pending.last().map_or(0, |entry| entry.id + 1);
+
+		Ok(Self {
+			dir: dir.to_path_buf(),
+			inner: Mutex::new(Inner { pending: pending.into(), next_id }),
+			notify: Notify::new(),
+		})
+	}
+
+	pub fn len(&self) -> usize {
+		self.lock().pending.len()
+	}
+
+	/// Persist `extrinsic` and append it to the queue.
+	pub fn push(&self, extrinsic: Vec<u8>) -> io::Result<()> {
+		let mut inner = self.lock();
+		let id = inner.next_id;
+
+		// Write to a temporary file first so that a crash never leaves a truncated extrinsic
+		let tmp = self.dir.join(format!("{id}.tmp"));
+		fs::write(&tmp, &extrinsic)?;
+		fs::rename(&tmp, self.path(id))?;
+
+		inner.next_id += 1;
+		inner.pending.push_back(Entry { id, extrinsic });
+		drop(inner);
+
+		self.notify.notify_one();
+		Ok(())
+	}
+
+	/// The oldest entry, waits until there is one.
+	pub async fn front(&self) -> Entry {
+		loop {
+			if let Some(entry) = self.lock().pending.front().cloned() {
+				return entry
+			}
+			self.notify.notified().await;
+		}
+	}
+
+	/// Remove the entry of `id` once it has been handled.
+	pub fn remove(&self, id: u64) -> io::Result<()> {
+		self.lock().pending.retain(|entry| entry.id != id);
+		fs::remove_file(self.path(id))
+	}
+
+	fn path(&self, id: u64) -> PathBuf {
+		self.dir.join(format!("{id}.tx"))
+	}
+
+	fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+		self.inner.lock().expect("Never poisoned")
+	}
+}
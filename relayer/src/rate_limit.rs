@@ -0,0 +1,52 @@
+// This file is part of EVM Account Mapping Pallet.
+
+// Copyright (C) HashForest Technology Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Fixed window rate limiting.
+
+use std::{
+	collections::HashMap,
+	hash::Hash,
+	sync::Mutex,
+	time::{Duration, Instant},
+};
+
+/// Allows at most `limit` hits per key within every `window`.
+pub struct RateLimiter<K> {
+	limit: u32,
+	window: Duration,
+	hits: Mutex<HashMap<K, (Instant, u32)>>,
+}
+
+impl<K: Eq + Hash> RateLimiter<K> {
+	pub fn new(limit: u32, window: Duration) -> Self {
+		Self { limit, window, hits: Default::default() }
+	}
+
+	/// Record a hit of `key`, returns `false` if the limit of the current window is exceeded.
+	pub fn check(&self, key: K) -> bool {
+		let now = Instant::now();
+		let mut hits = self.hits.lock().expect("Never poisoned");
+		// Forget the expired windows so that the map doesn't grow with every key seen
+		hits.retain(|_, (start, _)| now.duration_since(*start) < self.window);
+
+		let (_, count) = hits.entry(key).or_insert((now, 0));
+		if *count >= self.limit {
+			return false
+		}
+		*count += 1;
+		true
+	}
+}
@@ -0,0 +1,201 @@
+// This file is part of EVM Account Mapping Pallet.
+
+// Copyright (C) HashForest Technology Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! The HTTP server of the relayer.
+
+use crate::{
+	chain::{self, Client, MetaCall},
+	metrics::Metrics,
+	queue::Queue,
+	rate_limit::RateLimiter,
+};
+use anyhow::Result;
+use hyper::{
+	body::HttpBody,
+	header::CONTENT_TYPE,
+	server::conn::AddrStream,
+	service::{make_service_fn, service_fn},
+	Body, Method, Request, Response, Server, StatusCode,
+};
+use pallet_evm_account_mapping::{MetaSignature, Nonce, Secp256K1Signature};
+use serde::Deserialize;
+use sp_core::{
+	crypto::{AccountId32, Ss58Codec},
+	hashing::blake2_256,
+};
+use std::{
+	convert::Infallible,
+	net::{IpAddr, SocketAddr},
+	sync::Arc,
+};
+
+/// The maximum size of a request body, larger requests are rejected.
+const MAX_BODY_SIZE: u64 = 64 * 1024;
+
+pub struct State {
+	pub api: Client,
+	pub queue: Arc<Queue>,
+	pub metrics: Arc<Metrics>,
+	pub ip_limiter: RateLimiter<IpAddr>,
+	pub account_limiter: RateLimiter<AccountId32>,
+}
+
+/// The JSON body of `/relay`, the byte strings are `0x` prefixed hex strings and the tip is a
+/// decimal string.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct RelayRequest {
+	/// The SS58 address of the mapped account.
+	who: String,
+	call_data: String,
+	nonce: Nonce,
+	signature: String,
+	/// How the payload is signed, `eip712` (the default) or `eip191` for `personal_sign`.
+	scheme: Option<String>,
+	tip: Option<String>,
+}
+
+impl RelayRequest {
+	fn into_meta_call(self) -> Result<MetaCall, Rejection> {
+		let who = AccountId32::from_ss58check(&self.who)
+			.map_err(|_| Rejection::bad_request("Invalid `who`"))?;
+		let call_data = decode_hex(&self.call_data)
+			.ok_or_else(|| Rejection::bad_request("Invalid `callData`"))?;
+		let signature = decode_hex(&self.signature)
+			.and_then(|signature| Secp256K1Signature::try_from(&signature[..]).ok())
+			.ok_or_else(|| Rejection::bad_request("Invalid `signature`"))?;
+		let signature = match self.scheme.as_deref() {
+			None | Some("eip712") => MetaSignature::Eip712Secp256k1(signature),
+			Some("eip191") => MetaSignature::Eip191Secp256k1(signature),
+			Some(_) => return Err(Rejection::bad_request("Invalid `scheme`")),
+		};
+		let tip = self
+			.tip
+			.map(|tip| tip.parse())
+			.transpose()
+			.map_err(|_| Rejection::bad_request("Invalid `tip`"))?;
+
+		Ok(MetaCall { who, call_data, nonce: self.nonce, signature, tip })
+	}
+}
+
+/// A request rejected with an HTTP error.
+struct Rejection {
+	status: StatusCode,
+	message: String,
+}
+
+impl Rejection {
+	fn new(status: StatusCode, message: impl Into<String>) -> Self {
+		Self { status, message: message.into() }
+	}
+
+	fn bad_request(message: impl Into<String>) -> Self {
+		Self::new(StatusCode::BAD_REQUEST, message)
+	}
+
+	fn internal(error: impl std::fmt::Debug) -> Self {
+		log::error!("Failed to handle a request: {error:?}");
+		Self::new(StatusCode::INTERNAL_SERVER_ERROR, "Internal error")
+	}
+}
+
+pub async fn serve(addr: SocketAddr, state: Arc<State>) -> Result<()> {
+	let make_service = make_service_fn(move |conn: &AddrStream| {
+		let state = state.clone();
+		let ip = conn.remote_addr().ip();
+		async move { Ok::<_, Infallible>(service_fn(move |req| handle(state.clone(), ip, req))) }
+	});
+
+	let server = Server::try_bind(&addr)?;
+	log::info!("Listening on {addr}");
+	server.serve(make_service).await?;
+	Ok(())
+}
+
+async fn handle(
+	state: Arc<State>,
+	ip: IpAddr,
+	req: Request<Body>,
+) -> Result<Response<Body>, Infallible> {
+	let response = match (req.method(), req.uri().path()) {
+		(&Method::POST, "/relay") => {
+			let response = relay(&state, ip, req).await.unwrap_or_else(|rejection| {
+				json_response(rejection.status, serde_json::json!({ "error": rejection.message }))
+			});
+			state.metrics.requests.with_label_values(&[response.status().as_str()]).inc();
+			response
+		},
+		(&Method::GET, "/metrics") => match state.metrics.render() {
+			Ok(metrics) => Response::builder()
+				.header(CONTENT_TYPE, "text/plain; version=0.0.4")
+				.body(metrics.into())
+				.expect("The response is valid"),
+			Err(e) => json_response(
+				StatusCode::INTERNAL_SERVER_ERROR,
+				serde_json::json!({ "error": e.to_string() }),
+			),
+		},
+		_ => json_response(StatusCode::NOT_FOUND, serde_json::json!({ "error": "Not found" })),
+	};
+	Ok(response)
+}
+
+/// Validate the signed payload of the request and queue its extrinsic, responds with the
+/// extrinsic hash.
+async fn relay(state: &State, ip: IpAddr, req: Request<Body>) -> Result<Response<Body>, Rejection> {
+	if !state.ip_limiter.check(ip) {
+		return Err(Rejection::new(StatusCode::TOO_MANY_REQUESTS, "Too many requests"))
+	}
+	// Bodies without a known size, i.e. chunked, are rejected as well
+	if req.body().size_hint().upper().map_or(true, |size| size > MAX_BODY_SIZE) {
+		return Err(Rejection::new(StatusCode::PAYLOAD_TOO_LARGE, "The body is too large"))
+	}
+
+	let body = hyper::body::to_bytes(req.into_body()).await.map_err(Rejection::internal)?;
+	let request: RelayRequest = serde_json::from_slice(&body)
+		.map_err(|e| Rejection::bad_request(format!("Invalid request: {e}")))?;
+	let meta_call = request.into_meta_call()?;
+	if !state.account_limiter.check(meta_call.who.clone()) {
+		return Err(Rejection::new(StatusCode::TOO_MANY_REQUESTS, "Too many requests"))
+	}
+
+	let extrinsic = meta_call.extrinsic(&state.api.metadata()).map_err(Rejection::internal)?;
+	if let Err(e) = chain::validate(&state.api, &extrinsic).await.map_err(Rejection::internal)? {
+		return Err(Rejection::new(StatusCode::UNPROCESSABLE_ENTITY, format!("{e:?}")))
+	}
+
+	let hash = blake2_256(&extrinsic);
+	state.queue.push(extrinsic).map_err(Rejection::internal)?;
+	state.metrics.queue_length.set(state.queue.len() as i64);
+
+	Ok(json_response(
+		StatusCode::ACCEPTED,
+		serde_json::json!({ "hash": format!("0x{}", hex::encode(hash)) }),
+	))
+}
+
+fn json_response(status: StatusCode, body: serde_json::Value) -> Response<Body> {
+	Response::builder()
+		.status(status)
+		.header(CONTENT_TYPE, "application/json")
+		.body(body.to_string().into())
+		.expect("The response is valid")
+}
+
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+	hex::decode(s.strip_prefix("0x")?).ok()
+}
@@ -13,7 +13,8 @@ use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{
-		AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, One, Verify,
+		AccountIdLookup, BlakeTwo256, Block as BlockT, Convert, IdentifyAccount, NumberFor, One,
+		Verify,
 	},
 	transaction_validity::{TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, MultiSignature,
@@ -136,6 +137,19 @@ pub fn native_version() -> NativeVersion {
 
 type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
 
+/// Handle the transaction fees, of the signed transactions and of the meta-transactions alike.
+pub struct DealWithFees;
+impl OnUnbalanced<NegativeImbalance> for DealWithFees {
+	fn on_nonzero_unbalanced(amount: NegativeImbalance) {
+		drop(amount);
+	}
+}
+impl OnUnbalanced<Credit<AccountId, Balances>> for DealWithFees {
+	fn on_nonzero_unbalanced(amount: Credit<AccountId, Balances>) {
+		drop(amount);
+	}
+}
+
 pub struct DealWithServiceFee;
 impl OnUnbalanced<NegativeImbalance> for DealWithServiceFee {
 	fn on_nonzero_unbalanced(amount: NegativeImbalance) {
@@ -198,6 +212,8 @@ impl frame_system::Config for Runtime {
 	/// This is used as an identifier of the chain. 42 is the generic substrate prefix.
 	type SS58Prefix = SS58Prefix;
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
+	/// Remove the meta-transaction nonces of the reaped accounts if enabled.
+	type OnKilledAccount = EvmAccountMapping;
 }
 
 impl pallet_aura::Config for Runtime {
@@ -245,7 +261,7 @@ impl pallet_balances::Config for Runtime {
 	type FreezeIdentifier = ();
 	type MaxLocks = ConstU32<50>;
 	type MaxReserves = ();
-	type MaxHolds = ();
+	type MaxHolds = ConstU32<4>;
 	type MaxFreezes = ();
 }
 
@@ -255,7 +271,7 @@ parameter_types! {
 
 impl pallet_transaction_payment::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
+	type OnChargeTransaction = CurrencyAdapter<Balances, DealWithFees>;
 	type OperationalFeeMultiplier = ConstU8<5>;
 	type WeightToFee = IdentityFee<Balance>;
 	type LengthToFee = IdentityFee<Balance>;
@@ -273,20 +289,99 @@ parameter_types! {
 	pub EIP712Version: Vec<u8> = b"1".to_vec();
 	pub EIP712ChainID: pallet_evm_account_mapping::EIP712ChainID = sp_core::U256::from(0);
 	pub EIP712VerifyingContractAddress: pallet_evm_account_mapping::EIP712VerifyingContractAddress = sp_core::H160::from([0u8; 20]);
+	pub const EvmAccountMappingTagPrefix: &'static str = "EVMAccountMapping";
+	pub const EvmAccountMappingSpendExistenceRequirement: frame_support::traits::ExistenceRequirement =
+		frame_support::traits::ExistenceRequirement::AllowDeath;
+}
+
+/// The account of the Aura authority authoring the current block.
+pub struct AuraBlockAuthor;
+impl frame_support::traits::Get<Option<AccountId>> for AuraBlockAuthor {
+	fn get() -> Option<AccountId> {
+		use frame_support::traits::FindAuthor;
+
+		let digest = System::digest();
+		let pre_runtime_digests = digest.logs().iter().filter_map(|d| d.as_pre_runtime());
+		pallet_aura::FindAccountFromAuthorIndex::<Runtime, Aura>::find_author(pre_runtime_digests)
+			.and_then(|author| AccountId::try_from(AsRef::<[u8]>::as_ref(&author)).ok())
+	}
+}
+
+pub struct ConvertTypedCall;
+impl Convert<pallet_evm_account_mapping::TypedCallOf<Runtime>, Option<RuntimeCall>>
+	for ConvertTypedCall
+{
+	fn convert(
+		typed_call: pallet_evm_account_mapping::TypedCallOf<Runtime>,
+	) -> Option<RuntimeCall> {
+		use pallet_evm_account_mapping::TypedCall;
+
+		Some(match typed_call {
+			TypedCall::BalanceTransfer { to, amount } =>
+				RuntimeCall::Balances(BalancesCall::transfer_keep_alive {
+					dest: to.into(),
+					value: amount,
+				}),
+			TypedCall::Remark { remark } => RuntimeCall::System(SystemCall::remark { remark }),
+		})
+	}
 }
 
 impl pallet_evm_account_mapping::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
 	type RuntimeCall = RuntimeCall;
+	type RuntimeHoldReason = RuntimeHoldReason;
 	type Currency = Balances;
+	type LinkDeposit = ConstU128<10000000000>;
+	type GrantDeposit = ConstU128<10000000000>;
+	type SponsorshipDeposit = ConstU128<10000000000>;
 	type AddressConverter = pallet_evm_account_mapping::SubstrateAddressConverter;
 	type ServiceFee = ConstU128<10000000000>;
+	type TypedCallConverter = ConvertTypedCall;
+	type FeeAdjustment = ();
 	type OnUnbalancedForServiceFee = DealWithServiceFee;
+	type OnChargeMetaTransaction =
+		pallet_evm_account_mapping::HoldingFeeAdapter<Balances, DealWithFees>;
+	type SpendExistenceRequirement = EvmAccountMappingSpendExistenceRequirement;
 	type CallFilter = frame_support::traits::Everything;
+	type CallFilterOrigin = frame_system::EnsureRoot<AccountId>;
+	type ServiceFeeWaiver = frame_support::traits::Nothing;
+	type InspectInnerCalls = ();
+	type ReceiptRetention = ConstU32<{ DAYS }>;
+	type IndexMetaTransactions = ConstBool<true>;
+	type FreeCallsPerAccount = ConstU32<3>;
+	type RateLimitWindow = ConstU32<{ MINUTES }>;
+	type MaxCallsPerWindow = ConstU32<10>;
+	type MaxCallDepth = ConstU32<8>;
+	type AllowOperationalCalls = ConstBool<false>;
+	type DispatchAsEvmMapped = ConstBool<false>;
+	type TipAssetId = u32;
+	type TipHandler = ();
+	type PermitAssetId = u32;
+	type AssetApprovals = ();
+	type ProxyType = ();
+	type PriorityPolicy = pallet_evm_account_mapping::DefaultPriority<Runtime>;
+	type UnsignedLongevity = ConstU64<5>;
+	type MaxNonceGap = ConstU64<16>;
+	type ReplayProtection = pallet_evm_account_mapping::SequentialNonces<Runtime>;
+	type MaxMetaTxPerBlock = ConstU32<256>;
+	type PurgeNonceOnReap = ConstBool<false>;
+	type MetaMortality = ConstBool<false>;
+	type UnsignedTagPrefix = EvmAccountMappingTagPrefix;
+	type BlockAuthor = AuraBlockAuthor;
+	type TipToBlockAuthor = ConstBool<false>;
+	type AdminOrigin = frame_system::EnsureRoot<AccountId>;
+	type RecoveryOrigin = frame_system::EnsureRoot<AccountId>;
+	type RecoveryDelay = ConstU32<{ 7 * DAYS }>;
+	type ControllerRotationDelay = ConstU32<{ 7 * DAYS }>;
 	type EIP712Name = EIP712Name;
 	type EIP712Version = EIP712Version;
 	type EIP712ChainID = EIP712ChainID;
 	type EIP712VerifyingContractAddress = EIP712VerifyingContractAddress;
+	type EIP712ExtraVerifyingContracts = ();
+	type EIP712Salt = ();
+	type EIP712ParaId = ();
 	type WeightInfo = pallet_evm_account_mapping::weights::SubstrateWeight<Runtime>;
 }
 
@@ -326,7 +421,10 @@ pub type SignedExtra = (
 ///
 /// This can be a tuple of types, each implementing `OnRuntimeUpgrade`.
 #[allow(unused_parens)]
-type Migrations = ();
+type Migrations = (
+	pallet_evm_account_mapping::migrations::v1::MigrateToV1<Runtime>,
+	pallet_evm_account_mapping::migrations::v2::MigrateToV2<Runtime>,
+);
 
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic =
@@ -527,6 +625,49 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_evm_account_mapping_runtime_api::EvmAccountMappingApi<Block, Balance, AccountId> for Runtime {
+		fn estimate_meta_fee(
+			call_data: Vec<u8>,
+			tip: Balance,
+		) -> Option<pallet_evm_account_mapping_runtime_api::MetaFeeEstimate<Balance>> {
+			EvmAccountMapping::estimate_meta_fee(&call_data, tip)
+		}
+
+		fn quote_meta_transaction(
+			who: AccountId,
+			call_data: Vec<u8>,
+			tip: Balance,
+			_sponsor: Option<AccountId>,
+		) -> Option<pallet_evm_account_mapping_runtime_api::FeeQuote<AccountId, Balance>> {
+			let estimate = EvmAccountMapping::estimate_meta_fee_of(&who, &call_data, tip)?;
+			let payer = EvmAccountMapping::fee_payer_of(&who, &call_data, estimate.total)?;
+			Some(pallet_evm_account_mapping_runtime_api::FeeQuote::new(estimate, payer))
+		}
+
+		fn map_evm_address(
+			evm_address: pallet_evm_account_mapping_runtime_api::EvmAddress,
+		) -> Option<AccountId> {
+			EvmAccountMapping::map_evm_address(&evm_address)
+		}
+
+		fn evm_address_of(
+			who: AccountId,
+		) -> Option<pallet_evm_account_mapping_runtime_api::EvmAddress> {
+			EvmAccountMapping::evm_address_of_account(&who)
+		}
+
+		fn receipt(
+			who: AccountId,
+			nonce: pallet_evm_account_mapping_runtime_api::Nonce,
+		) -> Option<pallet_evm_account_mapping_runtime_api::Receipt<BlockNumber, Balance>> {
+			EvmAccountMapping::receipt(&who, nonce)
+		}
+
+		fn rejection_stats() -> pallet_evm_account_mapping_runtime_api::RejectionStats {
+			EvmAccountMapping::rejection_stats()
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (